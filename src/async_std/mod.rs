@@ -0,0 +1,49 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+//! A set of [`async_std`]-specific type aliases and features.
+//!
+//! # Usage
+//! Unlike [`tokio`](https://docs.rs/tokio), [`async_std`]'s IO types already implement the [`futures_lite`] async IO
+//! traits which the [`base`] implementation is built upon. As a result, no compatibility wrappers are needed and the
+//! [`base`] types can be constructed directly from them:
+//! ```no_run
+//! # use async_zip::base::write::ZipFileWriter;
+//! # use async_zip::error::Result;
+//! # use async_std::fs::File;
+//! #
+//! async fn run() -> Result<()> {
+//!     let mut writer = ZipFileWriter::new(File::create("./foo.zip").await?);
+//!     // ...
+//!     writer.close().await?;
+//!     Ok(())
+//! }
+//! ```
+//!
+//! This module provides type aliases over the [`base`] types for the concrete [`async_std`] file types, as well as a
+//! concurrent reader which acts over a file system path (analogous to the one provided for [`tokio`]).
+//!
+//! [`tokio`]: https://docs.rs/async_zip/latest/async_zip/tokio/read/fs/index.html
+
+#[cfg(doc)]
+use crate::base;
+#[cfg(doc)]
+use async_std;
+#[cfg(doc)]
+use futures_lite;
+
+pub mod read;
+
+pub mod write {
+    //! A module which supports writing ZIP files.
+
+    #[cfg(doc)]
+    use crate::base;
+    use async_std::fs::File;
+
+    /// An [`async_std`]-specific type alias for [`base::write::ZipFileWriter`] over a [`File`].
+    pub type ZipFileWriter = crate::base::write::ZipFileWriter<File>;
+
+    /// An [`async_std`]-specific type alias for [`base::write::EntryStreamWriter`] over a [`File`].
+    pub type EntryStreamWriter<'a> = crate::base::write::EntryStreamWriter<'a, File>;
+}
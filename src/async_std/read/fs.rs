@@ -0,0 +1,165 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+//! A concurrent ZIP reader which acts over a file system path.
+//!
+//! Concurrency is achieved as a result of:
+//! - Wrapping the provided path within an [`Arc`] to allow shared ownership.
+//! - Constructing a new [`File`] from the path when reading.
+//!
+//! ### Usage
+//! Unlike the [`seek`] module, we no longer hold a mutable reference to any inner reader which in turn, allows the
+//! construction of concurrent [`ZipEntryReader`]s. Though, note that each individual [`ZipEntryReader`] cannot be sent
+//! between thread boundaries due to the masked lifetime requirement. Therefore, the overarching [`ZipFileReader`]
+//! should be cloned and moved into those contexts when needed.
+//!
+//! ### Concurrent Example
+//! ```no_run
+//! # use async_zip::async_std::read::fs::ZipFileReader;
+//! # use async_zip::error::Result;
+//! # use futures_lite::io::AsyncReadExt;
+//! #
+//! async fn run() -> Result<()> {
+//!     let reader = ZipFileReader::new("./foo.zip").await?;
+//!     let result = futures_lite::future::zip(read(&reader, 0), read(&reader, 1)).await;
+//!
+//!     let data_0 = result.0?;
+//!     let data_1 = result.1?;
+//!
+//!     // Use data within current scope.
+//!
+//!     Ok(())
+//! }
+//!
+//! async fn read(reader: &ZipFileReader, index: usize) -> Result<Vec<u8>> {
+//!     let mut entry = reader.reader_without_entry(index).await?;
+//!     let mut data = Vec::new();
+//!     entry.read_to_end(&mut data).await?;
+//!     Ok(data)
+//! }
+//! ```
+//!
+//! ### Parallel Example
+//! ```no_run
+//! # use async_zip::async_std::read::fs::ZipFileReader;
+//! # use async_zip::error::Result;
+//! # use futures_lite::io::AsyncReadExt;
+//! #
+//! async fn run() -> Result<()> {
+//!     let reader = ZipFileReader::new("./foo.zip").await?;
+//!
+//!     let handle_0 = async_std::task::spawn(read(reader.clone(), 0));
+//!     let handle_1 = async_std::task::spawn(read(reader.clone(), 1));
+//!
+//!     let data_0 = handle_0.await?;
+//!     let data_1 = handle_1.await?;
+//!
+//!     // Use data within current scope.
+//!
+//!     Ok(())
+//! }
+//!
+//! async fn read(reader: ZipFileReader, index: usize) -> Result<Vec<u8>> {
+//!     let mut entry = reader.reader_without_entry(index).await?;
+//!     let mut data = Vec::new();
+//!     entry.read_to_end(&mut data).await?;
+//!     Ok(data)
+//! }
+//! ```
+
+#[cfg(doc)]
+use crate::base::read::seek;
+
+use crate::base::read::io::entry::{WithEntry, WithoutEntry, ZipEntryReader};
+use crate::codec::{Codec, CodecRegistry};
+use crate::error::{Result, ZipError};
+use crate::file::ZipFile;
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use async_std::fs::File;
+use async_std::io::BufReader;
+
+#[derive(Debug)]
+struct Inner {
+    path: PathBuf,
+    file: ZipFile,
+}
+
+/// A concurrent ZIP reader which acts over a file system path.
+#[derive(Clone, Debug)]
+pub struct ZipFileReader {
+    inner: Arc<Inner>,
+    codecs: CodecRegistry,
+}
+
+impl ZipFileReader {
+    /// Constructs a new ZIP reader from a file system path.
+    pub async fn new<P>(path: P) -> Result<ZipFileReader>
+    where
+        P: AsRef<Path>,
+    {
+        let file = crate::base::read::file(File::open(path.as_ref()).await?).await?;
+        Ok(ZipFileReader::from_raw_parts(path, file))
+    }
+
+    /// Constructs a ZIP reader from a file system path and ZIP file information derived from that path.
+    ///
+    /// Providing a [`ZipFile`] that wasn't derived from that path may lead to inaccurate parsing.
+    pub fn from_raw_parts<P>(path: P, file: ZipFile) -> ZipFileReader
+    where
+        P: AsRef<Path>,
+    {
+        ZipFileReader {
+            inner: Arc::new(Inner { path: path.as_ref().to_owned(), file }),
+            codecs: CodecRegistry::default(),
+        }
+    }
+
+    /// Returns this ZIP file's information.
+    pub fn file(&self) -> &ZipFile {
+        &self.inner.file
+    }
+
+    /// Registers a codec for reading entries which use the provided custom compression method id, replacing any codec
+    /// previously registered for it.
+    ///
+    /// Returns [`ZipError::CompressionMethodReserved`] if the method id belongs to a method implemented by this crate.
+    /// See the [`codec`](crate::codec) module for more information.
+    pub fn register_codec(&mut self, method: u16, codec: Arc<dyn Codec>) -> Result<()> {
+        self.codecs.register(method, codec)
+    }
+
+    /// Returns the file system path provided to the reader during construction.
+    pub fn path(&self) -> &Path {
+        &self.inner.path
+    }
+
+    /// Returns a new entry reader if the provided index is valid.
+    pub async fn reader_without_entry(
+        &self,
+        index: usize,
+    ) -> Result<ZipEntryReader<'static, BufReader<File>, WithoutEntry>> {
+        let stored_entry = self.inner.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
+        let mut fs_file = BufReader::new(File::open(&self.inner.path).await?);
+
+        stored_entry.seek_to_data_offset(&mut fs_file).await?;
+
+        let size = stored_entry.entry.compressed_size();
+        ZipEntryReader::new_with_owned(fs_file, &stored_entry.entry, size, &self.codecs)
+    }
+
+    /// Returns a new entry reader if the provided index is valid.
+    pub async fn reader_with_entry(&self, index: usize) -> Result<ZipEntryReader<'_, BufReader<File>, WithEntry<'_>>> {
+        let stored_entry = self.inner.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
+        let mut fs_file = BufReader::new(File::open(&self.inner.path).await?);
+
+        stored_entry.seek_to_data_offset(&mut fs_file).await?;
+
+        let size = stored_entry.entry.compressed_size();
+        let reader = ZipEntryReader::new_with_owned(fs_file, &stored_entry.entry, size, &self.codecs)?;
+
+        Ok(reader.into_with_entry(stored_entry))
+    }
+}
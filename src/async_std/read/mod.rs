@@ -0,0 +1,18 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+//! A module which supports reading ZIP files.
+
+pub mod fs;
+
+pub mod seek {
+    //! A ZIP reader which acts over a seekable source.
+
+    #[cfg(doc)]
+    use crate::base;
+    use async_std::fs::File;
+    use async_std::io::BufReader;
+
+    /// An [`async_std`]-specific type alias for [`base::read::seek::ZipFileReader`] over a buffered [`File`].
+    pub type ZipFileReader = crate::base::read::seek::ZipFileReader<BufReader<File>>;
+}
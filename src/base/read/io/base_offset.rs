@@ -0,0 +1,90 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use std::io::{Error, ErrorKind, SeekFrom};
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use futures_lite::io::{AsyncBufRead, AsyncRead, AsyncSeek};
+use pin_project::pin_project;
+
+/// A reader which treats a given offset within the inner reader as its start.
+///
+/// Some archives are preceded by data which isn't part of the archive itself (eg. a self-extracting stub, a shebang
+/// line, or a byte-order mark), whilst the offsets stored within them are relative to the start of the archive. This
+/// reader adds the base offset to every absolute seek and subtracts it from every reported position, so all offsets
+/// (including [`StoredZipEntry::header_offset()`]) remain relative to the logical start of the archive.
+///
+/// [`StoredZipEntry::header_offset()`]: crate::StoredZipEntry::header_offset
+#[pin_project]
+pub struct BaseOffsetReader<R> {
+    #[pin]
+    inner: R,
+    base: u64,
+}
+
+impl<R> BaseOffsetReader<R> {
+    /// Constructs a new reader which treats `base` as the start of the inner reader.
+    pub fn new(inner: R, base: u64) -> Self {
+        Self { inner, base }
+    }
+
+    /// Returns the offset within the inner reader at which the archive starts.
+    pub fn base(&self) -> u64 {
+        self.base
+    }
+
+    /// Returns a mutable reference to the inner reader.
+    pub fn inner_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Consumes this reader and returns the inner reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R> AsyncRead for BaseOffsetReader<R>
+where
+    R: AsyncRead,
+{
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<Result<usize, Error>> {
+        self.project().inner.poll_read(cx, buf)
+    }
+}
+
+impl<R> AsyncBufRead for BaseOffsetReader<R>
+where
+    R: AsyncBufRead,
+{
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<&[u8], Error>> {
+        self.project().inner.poll_fill_buf(cx)
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        self.project().inner.consume(amt)
+    }
+}
+
+impl<R> AsyncSeek for BaseOffsetReader<R>
+where
+    R: AsyncSeek,
+{
+    fn poll_seek(self: Pin<&mut Self>, cx: &mut Context<'_>, pos: SeekFrom) -> Poll<Result<u64, Error>> {
+        let this = self.project();
+
+        let pos = match pos {
+            SeekFrom::Start(target) => SeekFrom::Start(
+                target.checked_add(*this.base).ok_or_else(|| Error::new(ErrorKind::InvalidInput, "seek overflowed"))?,
+            ),
+            pos => pos,
+        };
+        let position = ready!(this.inner.poll_seek(cx, pos))?;
+
+        let position = position
+            .checked_sub(*this.base)
+            .ok_or_else(|| Error::new(ErrorKind::InvalidInput, "invalid seek to a position before the base offset"))?;
+        Poll::Ready(Ok(position))
+    }
+}
@@ -0,0 +1,177 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use std::io::{Error, ErrorKind, SeekFrom};
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use futures_lite::io::{AsyncBufRead, AsyncRead, AsyncSeek};
+use pin_project::pin_project;
+
+/// The default capacity of the internal buffer.
+const DEFAULT_CAPACITY: usize = 8 * 1024;
+
+/// A buffered reader which retains its buffer across seeks.
+///
+/// Typical [`AsyncBufRead`] + [`AsyncSeek`] implementations (such as [`futures_lite::io::BufReader`]) discard their
+/// buffer on every seek. As reading an entry via [`ZipFileReader`] involves seeking to its local file header and then
+/// past its filename & extra field, this results in the same data being read from the source multiple times.
+///
+/// This reader instead only seeks the inner reader when the target position falls outside of the current buffer. As
+/// a result, reading many small adjacent entries requires far fewer reads & seeks of the underlying source.
+///
+/// [`ZipFileReader`]: crate::base::read::seek::ZipFileReader
+#[pin_project]
+pub struct BufferedSeekReader<R> {
+    #[pin]
+    inner: R,
+    buffer: Box<[u8]>,
+    pos: usize,
+    filled: usize,
+    /// The position of the inner reader (ie. the position directly after the end of the buffer), if known.
+    offset: Option<u64>,
+    /// Whether a seek of the inner reader is in progress.
+    seeking: bool,
+}
+
+impl<R> BufferedSeekReader<R>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    /// Constructs a new buffered reader with the default capacity of 8 KiB.
+    pub fn new(inner: R) -> Self {
+        Self::with_capacity(DEFAULT_CAPACITY, inner)
+    }
+
+    /// Constructs a new buffered reader with the provided buffer capacity.
+    pub fn with_capacity(capacity: usize, inner: R) -> Self {
+        Self { inner, buffer: vec![0; capacity].into_boxed_slice(), pos: 0, filled: 0, offset: None, seeking: false }
+    }
+
+    /// Returns a mutable reference to the inner reader.
+    ///
+    /// Reading from or seeking the inner reader directly may lead to inaccurate reads from this reader.
+    pub fn inner_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Consumes this reader and returns the inner reader, discarding any buffered data.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R> BufferedSeekReader<R> {
+    /// Discards the buffer if a seek of the inner reader was cancelled part way through (eg. by dropping the future
+    /// awaiting it), as the position of the inner reader is then unknown.
+    fn discard_cancelled_seek(self: Pin<&mut Self>) {
+        let this = self.project();
+
+        if *this.seeking {
+            *this.pos = 0;
+            *this.filled = 0;
+            *this.offset = None;
+            *this.seeking = false;
+        }
+    }
+}
+
+impl<R> AsyncRead for BufferedSeekReader<R>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    fn poll_read(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<Result<usize, Error>> {
+        self.as_mut().discard_cancelled_seek();
+
+        // Bypass the buffer entirely for large reads when it's empty.
+        if self.pos == self.filled && buf.len() >= self.buffer.len() {
+            let this = self.project();
+            let read = ready!(this.inner.poll_read(cx, buf))?;
+
+            *this.pos = 0;
+            *this.filled = 0;
+            *this.offset = this.offset.map(|offset| offset + read as u64);
+            return Poll::Ready(Ok(read));
+        }
+
+        let available = ready!(self.as_mut().poll_fill_buf(cx))?;
+        let read = std::cmp::min(available.len(), buf.len());
+        buf[..read].copy_from_slice(&available[..read]);
+        self.consume(read);
+
+        Poll::Ready(Ok(read))
+    }
+}
+
+impl<R> AsyncBufRead for BufferedSeekReader<R>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    fn poll_fill_buf(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<&[u8], Error>> {
+        self.as_mut().discard_cancelled_seek();
+        let this = self.project();
+
+        if *this.pos >= *this.filled {
+            let read = ready!(this.inner.poll_read(cx, this.buffer))?;
+
+            *this.pos = 0;
+            *this.filled = read;
+            *this.offset = this.offset.map(|offset| offset + read as u64);
+        }
+
+        Poll::Ready(Ok(&this.buffer[*this.pos..*this.filled]))
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        let this = self.project();
+        *this.pos = std::cmp::min(*this.pos + amt, *this.filled);
+    }
+}
+
+impl<R> AsyncSeek for BufferedSeekReader<R>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    fn poll_seek(self: Pin<&mut Self>, cx: &mut Context<'_>, pos: SeekFrom) -> Poll<Result<u64, Error>> {
+        let this = self.project();
+        let remaining = (*this.filled - *this.pos) as u64;
+
+        let target = match (pos, *this.offset) {
+            (SeekFrom::Start(target), _) => Some(target),
+            (SeekFrom::Current(relative), Some(offset)) => Some(
+                (offset - remaining)
+                    .checked_add_signed(relative)
+                    .ok_or_else(|| Error::new(ErrorKind::InvalidInput, "invalid seek to a negative position"))?,
+            ),
+            _ => None,
+        };
+
+        // Only move within the buffer if the target falls inside of it. If a seek of the inner reader is in progress,
+        // this is either a repeated poll of that seek or a new seek following its cancellation. In either case, the
+        // inner reader must be seeked as its position may no longer match the buffer.
+        if let (Some(target), Some(offset), false) = (target, *this.offset, *this.seeking) {
+            let start = offset - *this.filled as u64;
+
+            if target >= start && target <= offset {
+                *this.pos = (target - start) as usize;
+                return Poll::Ready(Ok(target));
+            }
+        }
+
+        let inner_pos = match (target, pos) {
+            (Some(target), _) => SeekFrom::Start(target),
+            // The inner reader is ahead of our position by the number of bytes remaining in the buffer.
+            (None, SeekFrom::Current(relative)) => SeekFrom::Current(relative - remaining as i64),
+            (None, pos) => pos,
+        };
+        *this.seeking = true;
+        let result = ready!(this.inner.poll_seek(cx, inner_pos));
+
+        *this.pos = 0;
+        *this.filled = 0;
+        *this.offset = result.as_ref().ok().copied();
+        *this.seeking = false;
+
+        Poll::Ready(result)
+    }
+}
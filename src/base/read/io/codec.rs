@@ -0,0 +1,57 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::codec::Decoder;
+use crate::error::ZipError;
+
+use std::io::{Error, ErrorKind};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_lite::io::{AsyncBufRead, AsyncRead};
+use pin_project::pin_project;
+
+/// A reader which decodes data compressed via a custom [`Decoder`] from an inner [`AsyncBufRead`] implementer.
+#[pin_project]
+pub(crate) struct CodecDecoder<R> {
+    #[pin]
+    reader: R,
+    method: u16,
+    decoder: Option<Box<dyn Decoder>>,
+}
+
+impl<R> CodecDecoder<R>
+where
+    R: AsyncBufRead + Unpin,
+{
+    /// Constructs a new decoder for the provided method id, which will fail to read if no decoder is available.
+    pub(crate) fn new(reader: R, method: u16, decoder: Option<Box<dyn Decoder>>) -> Self {
+        Self { reader, method, decoder }
+    }
+
+    /// Returns the compression method id which this decoder was constructed for.
+    pub(crate) fn method(&self) -> u16 {
+        self.method
+    }
+
+    /// Consumes this decoder and returns the inner reader.
+    pub(crate) fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R> AsyncRead for CodecDecoder<R>
+where
+    R: AsyncBufRead + Unpin,
+{
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<std::io::Result<usize>> {
+        let this = self.project();
+
+        match this.decoder.as_mut() {
+            Some(decoder) => decoder.poll_decode(cx, this.reader, buf),
+            None => {
+                Poll::Ready(Err(Error::new(ErrorKind::Unsupported, ZipError::CompressionNotSupported(*this.method))))
+            }
+        }
+    }
+}
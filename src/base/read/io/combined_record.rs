@@ -11,11 +11,16 @@ pub struct CombinedCentralDirectoryRecord {
     pub version_needed_to_extract: Option<u16>,
     pub disk_number: u32,
     pub disk_number_start_of_cd: u32,
+    /// The total number of disks, as stored within the Zip64EOCDL or implied by the EOCDR's disk number otherwise.
+    pub total_disks: u32,
     pub num_entries_in_directory_on_disk: u64,
     pub num_entries_in_directory: u64,
     pub directory_size: u64,
     pub offset_of_start_of_directory: u64,
+    #[allow(dead_code)]
     pub file_comment_length: u16,
+    /// The zip64 extensible data sector following the fixed fields of the Zip64EOCDR, if any.
+    pub zip64_extensible_data: Vec<u8>,
 }
 
 impl CombinedCentralDirectoryRecord {
@@ -27,6 +32,7 @@ impl CombinedCentralDirectoryRecord {
         let mut combined = Self::from(&eocdr);
         if eocdr.disk_num == u16::MAX {
             combined.disk_number = zip64eocdr.disk_number;
+            combined.total_disks = zip64eocdr.disk_number.saturating_add(1);
         }
         if eocdr.start_cent_dir_disk == u16::MAX {
             combined.disk_number_start_of_cd = zip64eocdr.disk_number_start_of_cd;
@@ -58,11 +64,13 @@ impl From<&EndOfCentralDirectoryHeader> for CombinedCentralDirectoryRecord {
             version_needed_to_extract: None,
             disk_number: header.disk_num as u32,
             disk_number_start_of_cd: header.start_cent_dir_disk as u32,
+            total_disks: header.disk_num as u32 + 1,
             num_entries_in_directory_on_disk: header.num_of_entries_disk as u64,
             num_entries_in_directory: header.num_of_entries as u64,
             directory_size: header.size_cent_dir as u64,
             offset_of_start_of_directory: header.cent_dir_offset as u64,
             file_comment_length: header.file_comm_length,
+            zip64_extensible_data: Vec::new(),
         }
     }
 }
@@ -1,10 +1,20 @@
 // Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
+use crate::base::read::io::codec::CodecDecoder;
+#[cfg(feature = "legacy")]
+use crate::base::read::io::implode::{ImplodeDecoder, ImplodeOptions};
+#[cfg(feature = "lzma")]
+use crate::base::read::io::lzma::LzmaHeaderReader;
+use crate::codec::Codec;
+use crate::entry::ZipEntry;
+use crate::error::ZipError;
 use crate::spec::Compression;
 
+use std::io::{Error, ErrorKind};
 use std::pin::Pin;
-use std::task::{Context, Poll};
+use std::sync::Arc;
+use std::task::{ready, Context, Poll};
 
 #[cfg(any(
     feature = "deflate",
@@ -23,58 +33,117 @@ use pin_project::pin_project;
 pub(crate) enum CompressedReader<R> {
     Stored(#[pin] R),
     #[cfg(feature = "deflate")]
-    Deflate(#[pin] bufread::DeflateDecoder<R>),
+    Deflate(#[pin] bufread::DeflateDecoder<UpstreamReader<R>>),
     #[cfg(feature = "deflate64")]
-    Deflate64(#[pin] bufread::Deflate64Decoder<R>),
+    Deflate64(#[pin] bufread::Deflate64Decoder<UpstreamReader<R>>),
     #[cfg(feature = "bzip2")]
-    Bz(#[pin] bufread::BzDecoder<R>),
+    Bz(#[pin] bufread::BzDecoder<UpstreamReader<R>>),
     #[cfg(feature = "lzma")]
-    Lzma(#[pin] bufread::LzmaDecoder<R>),
+    Lzma(#[pin] bufread::LzmaDecoder<LzmaHeaderReader<UpstreamReader<R>>>),
     #[cfg(feature = "zstd")]
-    Zstd(#[pin] bufread::ZstdDecoder<R>),
+    Zstd(#[pin] bufread::ZstdDecoder<UpstreamReader<R>>),
     #[cfg(feature = "xz")]
-    Xz(#[pin] bufread::XzDecoder<R>),
+    Xz(#[pin] bufread::XzDecoder<UpstreamReader<R>>),
+    #[cfg(feature = "legacy")]
+    Implode(#[pin] ImplodeDecoder<UpstreamReader<R>>),
+    Custom(#[pin] CodecDecoder<UpstreamReader<R>>),
 }
 
 impl<R> CompressedReader<R>
 where
     R: AsyncBufRead + Unpin,
 {
+    /// Constructs a new wrapping reader for the provided entry's data from a generic [`AsyncBufRead`] implementer.
+    ///
+    /// Unlike [`CompressedReader::new()`], this accounts for any options the compression method stores outside of the
+    /// compressed data (ie. within the general purpose flag).
+    pub(crate) fn from_entry(reader: R, entry: &ZipEntry, codec: Option<Arc<dyn Codec>>) -> Self {
+        match entry.compression() {
+            #[cfg(feature = "legacy")]
+            Compression::Implode => {
+                let size = (!entry.data_descriptor || entry.uncompressed_size != 0).then_some(entry.uncompressed_size);
+                CompressedReader::Implode(ImplodeDecoder::new(UpstreamReader(reader), entry.implode_options, size))
+            }
+            #[cfg(feature = "lzma")]
+            Compression::Lzma => {
+                // The uncompressed size is only needed if the data has no end-of-stream marker, and can only be relied
+                // upon if it wasn't deferred to a data descriptor.
+                let known_size = !entry.data_descriptor || entry.uncompressed_size != 0;
+                let size = (!entry.lzma_eos_marker && known_size).then_some(entry.uncompressed_size);
+                CompressedReader::Lzma(bufread::LzmaDecoder::new(LzmaHeaderReader::new(UpstreamReader(reader), size)))
+            }
+            Compression::Custom(method) => {
+                let decoder = codec.map(|codec| codec.decoder());
+                CompressedReader::Custom(CodecDecoder::new(UpstreamReader(reader), method, decoder))
+            }
+            compression => CompressedReader::new(reader, compression),
+        }
+    }
+
     /// Constructs a new wrapping reader from a generic [`AsyncBufRead`] implementer.
     pub(crate) fn new(reader: R, compression: Compression) -> Self {
+        // Errors from the inner reader are marked so that they aren't mistaken for invalid compressed data.
         match compression {
             Compression::Stored => CompressedReader::Stored(reader),
             #[cfg(feature = "deflate")]
-            Compression::Deflate => CompressedReader::Deflate(bufread::DeflateDecoder::new(reader)),
+            Compression::Deflate => CompressedReader::Deflate(bufread::DeflateDecoder::new(UpstreamReader(reader))),
             #[cfg(feature = "deflate64")]
-            Compression::Deflate64 => CompressedReader::Deflate64(bufread::Deflate64Decoder::new(reader)),
+            Compression::Deflate64 => {
+                CompressedReader::Deflate64(bufread::Deflate64Decoder::new(UpstreamReader(reader)))
+            }
             #[cfg(feature = "bzip2")]
-            Compression::Bz => CompressedReader::Bz(bufread::BzDecoder::new(reader)),
+            Compression::Bz => CompressedReader::Bz(bufread::BzDecoder::new(UpstreamReader(reader))),
             #[cfg(feature = "lzma")]
-            Compression::Lzma => CompressedReader::Lzma(bufread::LzmaDecoder::new(reader)),
+            Compression::Lzma => {
+                CompressedReader::Lzma(bufread::LzmaDecoder::new(LzmaHeaderReader::new(UpstreamReader(reader), None)))
+            }
             #[cfg(feature = "zstd")]
-            Compression::Zstd => CompressedReader::Zstd(bufread::ZstdDecoder::new(reader)),
+            Compression::Zstd => CompressedReader::Zstd(bufread::ZstdDecoder::new(UpstreamReader(reader))),
             #[cfg(feature = "xz")]
-            Compression::Xz => CompressedReader::Xz(bufread::XzDecoder::new(reader)),
+            Compression::Xz => CompressedReader::Xz(bufread::XzDecoder::new(UpstreamReader(reader))),
+            #[cfg(feature = "legacy")]
+            Compression::Implode => {
+                CompressedReader::Implode(ImplodeDecoder::new(UpstreamReader(reader), ImplodeOptions::default(), None))
+            }
+            Compression::Custom(method) => {
+                CompressedReader::Custom(CodecDecoder::new(UpstreamReader(reader), method, None))
+            }
         }
     }
 
+    /// Sets whether decoding should continue through multiple concatenated frames (incl. skippable frames) until the
+    /// inner reader is exhausted, rather than stopping at the end of the first.
+    ///
+    /// This only affects Zstd compressed data, and should only be enabled when the inner reader is bounded by the
+    /// entry's compressed size, as decoding would otherwise continue into any data which follows the entry.
+    #[cfg_attr(not(feature = "zstd"), allow(unused_mut, unused_variables))]
+    pub(crate) fn multiple_frames(mut self, enabled: bool) -> Self {
+        #[cfg(feature = "zstd")]
+        if let CompressedReader::Zstd(inner) = &mut self {
+            inner.multiple_members(enabled);
+        }
+        self
+    }
+
     /// Consumes this reader and returns the inner value.
     pub(crate) fn into_inner(self) -> R {
         match self {
             CompressedReader::Stored(inner) => inner,
             #[cfg(feature = "deflate")]
-            CompressedReader::Deflate(inner) => inner.into_inner(),
+            CompressedReader::Deflate(inner) => inner.into_inner().0,
             #[cfg(feature = "deflate64")]
-            CompressedReader::Deflate64(inner) => inner.into_inner(),
+            CompressedReader::Deflate64(inner) => inner.into_inner().0,
             #[cfg(feature = "bzip2")]
-            CompressedReader::Bz(inner) => inner.into_inner(),
+            CompressedReader::Bz(inner) => inner.into_inner().0,
             #[cfg(feature = "lzma")]
-            CompressedReader::Lzma(inner) => inner.into_inner(),
+            CompressedReader::Lzma(inner) => inner.into_inner().into_inner().0,
             #[cfg(feature = "zstd")]
-            CompressedReader::Zstd(inner) => inner.into_inner(),
+            CompressedReader::Zstd(inner) => inner.into_inner().0,
             #[cfg(feature = "xz")]
-            CompressedReader::Xz(inner) => inner.into_inner(),
+            CompressedReader::Xz(inner) => inner.into_inner().0,
+            #[cfg(feature = "legacy")]
+            CompressedReader::Implode(inner) => inner.into_inner().0,
+            CompressedReader::Custom(inner) => inner.into_inner().0,
         }
     }
 }
@@ -84,20 +153,83 @@ where
     R: AsyncBufRead + Unpin,
 {
     fn poll_read(self: Pin<&mut Self>, c: &mut Context<'_>, b: &mut [u8]) -> Poll<std::io::Result<usize>> {
-        match self.project() {
-            CompressedReaderProj::Stored(inner) => inner.poll_read(c, b),
+        let (method, result) = match self.project() {
+            CompressedReaderProj::Stored(inner) => return inner.poll_read(c, b),
             #[cfg(feature = "deflate")]
-            CompressedReaderProj::Deflate(inner) => inner.poll_read(c, b),
+            CompressedReaderProj::Deflate(inner) => (Compression::Deflate, inner.poll_read(c, b)),
             #[cfg(feature = "deflate64")]
-            CompressedReaderProj::Deflate64(inner) => inner.poll_read(c, b),
+            CompressedReaderProj::Deflate64(inner) => (Compression::Deflate64, inner.poll_read(c, b)),
             #[cfg(feature = "bzip2")]
-            CompressedReaderProj::Bz(inner) => inner.poll_read(c, b),
+            CompressedReaderProj::Bz(inner) => (Compression::Bz, inner.poll_read(c, b)),
             #[cfg(feature = "lzma")]
-            CompressedReaderProj::Lzma(inner) => inner.poll_read(c, b),
+            CompressedReaderProj::Lzma(inner) => (Compression::Lzma, inner.poll_read(c, b)),
             #[cfg(feature = "zstd")]
-            CompressedReaderProj::Zstd(inner) => inner.poll_read(c, b),
+            CompressedReaderProj::Zstd(inner) => (Compression::Zstd, inner.poll_read(c, b)),
             #[cfg(feature = "xz")]
-            CompressedReaderProj::Xz(inner) => inner.poll_read(c, b),
+            CompressedReaderProj::Xz(inner) => (Compression::Xz, inner.poll_read(c, b)),
+            #[cfg(feature = "legacy")]
+            CompressedReaderProj::Implode(inner) => (Compression::Implode, inner.poll_read(c, b)),
+            CompressedReaderProj::Custom(inner) => (Compression::Custom(inner.method()), inner.poll_read(c, b)),
+        };
+
+        Poll::Ready(ready!(result).map_err(|err| match UpstreamError::take(err) {
+            Ok(err) => err,
+            Err(err) => Error::new(ErrorKind::InvalidData, ZipError::DecompressionError { method, source: err }),
+        }))
+    }
+}
+
+/// A wrapping reader which marks any errors returned by the inner reader, so that they can be told apart from errors
+/// produced by a decoder when it encounters invalid data.
+#[pin_project]
+pub(crate) struct UpstreamReader<R>(#[pin] R);
+
+/// An error returned by the reader wrapped by an [`UpstreamReader`].
+#[derive(Debug)]
+struct UpstreamError(Error);
+
+impl UpstreamError {
+    /// Unwraps an error which was marked by an [`UpstreamReader`], or returns it as-is if it wasn't.
+    fn take(err: Error) -> std::result::Result<Error, Error> {
+        if !err.get_ref().is_some_and(|inner| inner.is::<UpstreamError>()) {
+            return Err(err);
         }
+
+        let inner = err.into_inner().expect("error has an inner value");
+        Ok(inner.downcast::<UpstreamError>().expect("inner value is an upstream error").0)
+    }
+}
+
+impl std::fmt::Display for UpstreamError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
     }
 }
+
+impl std::error::Error for UpstreamError {}
+
+impl<R> AsyncRead for UpstreamReader<R>
+where
+    R: AsyncRead,
+{
+    fn poll_read(self: Pin<&mut Self>, c: &mut Context<'_>, b: &mut [u8]) -> Poll<std::io::Result<usize>> {
+        Poll::Ready(ready!(self.project().0.poll_read(c, b)).map_err(mark_upstream))
+    }
+}
+
+impl<R> AsyncBufRead for UpstreamReader<R>
+where
+    R: AsyncBufRead,
+{
+    fn poll_fill_buf(self: Pin<&mut Self>, c: &mut Context<'_>) -> Poll<std::io::Result<&[u8]>> {
+        Poll::Ready(ready!(self.project().0.poll_fill_buf(c)).map_err(mark_upstream))
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        self.project().0.consume(amt)
+    }
+}
+
+fn mark_upstream(err: Error) -> Error {
+    Error::new(err.kind(), UpstreamError(err))
+}
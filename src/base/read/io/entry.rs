@@ -2,14 +2,14 @@
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
 use crate::base::read::io::{compressed::CompressedReader, hashed::HashedReader, owned::OwnedReader};
+use crate::codec::CodecRegistry;
 use crate::entry::ZipEntry;
 use crate::error::{Result, ZipError};
-use crate::spec::Compression;
 
 use std::pin::Pin;
 use std::task::{Context, Poll};
 
-use futures_lite::io::{AsyncBufRead, AsyncRead, AsyncReadExt, Take};
+use futures_lite::io::{AsyncBufRead, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, Take};
 use pin_project::pin_project;
 
 /// A type which encodes that [`ZipEntryReader`] has associated entry data.
@@ -19,11 +19,19 @@ pub struct WithEntry<'a>(OwnedEntry<'a>);
 pub struct WithoutEntry;
 
 /// A ZIP entry reader which may implement decompression.
+///
+/// # Cancellation
+/// Dropping an entry reader (or any future reading from it) part way through is safe. The seek, mem, and fs ZIP
+/// readers locate an entry's data from scratch each time an entry reader is requested, so they remain usable
+/// afterwards and any subsequent entry reader will read its data from the start.
 #[pin_project]
 pub struct ZipEntryReader<'a, R, E> {
     #[pin]
     reader: HashedReader<CompressedReader<Take<OwnedReader<'a, R>>>>,
     entry: E,
+    /// Decompressed bytes which were read ahead to guess the content type, and are yet to be returned.
+    #[cfg(feature = "mime")]
+    peeked: Vec<u8>,
 }
 
 impl<'a, R> ZipEntryReader<'a, R, WithoutEntry>
@@ -31,23 +39,60 @@ where
     R: AsyncBufRead + Unpin,
 {
     /// Constructs a new entry reader from its required parameters (incl. an owned R).
-    pub(crate) fn new_with_owned(reader: R, compression: Compression, size: u64) -> Self {
-        let reader = HashedReader::new(CompressedReader::new(OwnedReader::Owned(reader).take(size), compression));
-        Self { reader, entry: WithoutEntry }
+    ///
+    /// Returns [`ZipError::CompressionNotSupported`] if the entry uses a custom compression method for which no codec
+    /// has been registered.
+    pub(crate) fn new_with_owned(reader: R, entry: &ZipEntry, size: u64, codecs: &CodecRegistry) -> Result<Self> {
+        let codec = codecs.resolve(entry)?;
+        let reader = CompressedReader::from_entry(OwnedReader::Owned(reader).take(size), entry, codec);
+        // A size of u64::MAX marks data of unknown length, which must end with the decoder's first frame.
+        let reader = HashedReader::new(reader.multiple_frames(size != u64::MAX));
+        Ok(Self {
+            reader,
+            entry: WithoutEntry,
+            #[cfg(feature = "mime")]
+            peeked: Vec::new(),
+        })
     }
 
     /// Constructs a new entry reader from its required parameters (incl. a mutable borrow of an R).
-    pub(crate) fn new_with_borrow(reader: &'a mut R, compression: Compression, size: u64) -> Self {
-        let reader = HashedReader::new(CompressedReader::new(OwnedReader::Borrow(reader).take(size), compression));
-        Self { reader, entry: WithoutEntry }
+    ///
+    /// Returns [`ZipError::CompressionNotSupported`] if the entry uses a custom compression method for which no codec
+    /// has been registered.
+    pub(crate) fn new_with_borrow(
+        reader: &'a mut R,
+        entry: &ZipEntry,
+        size: u64,
+        codecs: &CodecRegistry,
+    ) -> Result<Self> {
+        let codec = codecs.resolve(entry)?;
+        let reader = CompressedReader::from_entry(OwnedReader::Borrow(reader).take(size), entry, codec);
+        // A size of u64::MAX marks data of unknown length, which must end with the decoder's first frame.
+        let reader = HashedReader::new(reader.multiple_frames(size != u64::MAX));
+        Ok(Self {
+            reader,
+            entry: WithoutEntry,
+            #[cfg(feature = "mime")]
+            peeked: Vec::new(),
+        })
     }
 
     pub(crate) fn into_with_entry(self, entry: &'a ZipEntry) -> ZipEntryReader<'a, R, WithEntry<'a>> {
-        ZipEntryReader { reader: self.reader, entry: WithEntry(OwnedEntry::Borrow(entry)) }
+        ZipEntryReader {
+            reader: self.reader,
+            entry: WithEntry(OwnedEntry::Borrow(entry)),
+            #[cfg(feature = "mime")]
+            peeked: self.peeked,
+        }
     }
 
     pub(crate) fn into_with_entry_owned(self, entry: ZipEntry) -> ZipEntryReader<'a, R, WithEntry<'a>> {
-        ZipEntryReader { reader: self.reader, entry: WithEntry(OwnedEntry::Owned(entry)) }
+        ZipEntryReader {
+            reader: self.reader,
+            entry: WithEntry(OwnedEntry::Owned(Box::new(entry))),
+            #[cfg(feature = "mime")]
+            peeked: self.peeked,
+        }
     }
 }
 
@@ -56,7 +101,17 @@ where
     R: AsyncBufRead + Unpin,
 {
     fn poll_read(self: Pin<&mut Self>, c: &mut Context<'_>, b: &mut [u8]) -> Poll<std::io::Result<usize>> {
-        self.project().reader.poll_read(c, b)
+        let this = self.project();
+
+        #[cfg(feature = "mime")]
+        if !this.peeked.is_empty() {
+            let read = std::cmp::min(this.peeked.len(), b.len());
+            b[..read].copy_from_slice(&this.peeked[..read]);
+            this.peeked.drain(..read);
+            return Poll::Ready(Ok(read));
+        }
+
+        this.reader.poll_read(c, b)
     }
 }
 
@@ -71,6 +126,17 @@ where
         self.reader.swap_and_compute_hash()
     }
 
+    /// Returns the number of decompressed bytes returned by this reader so far.
+    ///
+    /// Once EOF has been reached, this is the actual uncompressed size of the entry, which may differ from the size
+    /// declared within its headers (eg. if they were left as zero by a streaming producer).
+    pub fn bytes_read(&self) -> u64 {
+        #[cfg(feature = "mime")]
+        return self.reader.read - self.peeked.len() as u64;
+        #[cfg(not(feature = "mime"))]
+        return self.reader.read;
+    }
+
     /// Consumes this reader and returns the inner value.
     pub(crate) fn into_inner(self) -> R {
         self.reader.into_inner().into_inner().into_inner().owned_into_inner()
@@ -86,6 +152,37 @@ where
         self.entry.0.entry()
     }
 
+    /// Returns a guess of the entry's content type based on the magic number at the start of its decompressed data,
+    /// falling back to its filename extension via [`ZipEntry::guessed_content_type()`].
+    ///
+    /// Magic numbers which are short enough to commonly begin other data (eg. `BM` for bitmaps) are only considered
+    /// when the filename's extension isn't recognised.
+    ///
+    /// The first few bytes of the entry's data are read ahead to do so, but are still returned by subsequent reads.
+    /// As such, this should be called before any data has been read.
+    ///
+    /// Note that this requires the `mime` feature.
+    #[cfg(feature = "mime")]
+    pub async fn guessed_content_type(&mut self) -> Result<Option<mime_guess::Mime>> {
+        let mut head = vec![0; crate::entry::mime::MAGIC_LENGTH];
+        let mut filled = 0;
+
+        while filled < head.len() {
+            match self.read(&mut head[filled..]).await? {
+                0 => break,
+                read => filled += read,
+            }
+        }
+
+        head.truncate(filled);
+        head.append(&mut self.peeked);
+        self.peeked = head;
+
+        let by_extension = self.entry().guessed_content_type();
+        let guess = crate::entry::mime::from_magic(&self.peeked, by_extension.is_none());
+        Ok(guess.or(by_extension))
+    }
+
     /// Reads all bytes until EOF has been reached, appending them to buf, and verifies the CRC32 values.
     ///
     /// This is a helper function synonymous to [`AsyncReadExt::read_to_end()`].
@@ -99,6 +196,66 @@ where
         }
     }
 
+    /// Copies all bytes until EOF has been reached into the provided writer, and verifies the CRC32 values.
+    ///
+    /// The writer is flushed once all bytes have been written. Returns the number of bytes written and the verified
+    /// CRC32 value. As the value is only verified once all bytes have been written, the writer may have received
+    /// corrupt data when [`ZipError::CRC32CheckError`] is returned.
+    ///
+    /// This is a helper function synonymous to [`futures_lite::io::copy()`].
+    pub async fn copy_to_checked<W>(&mut self, mut writer: W) -> Result<(u64, u32)>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        let written = futures_lite::io::copy(&mut *self, &mut writer).await?;
+        writer.flush().await?;
+
+        let crc32 = self.compute_hash();
+        if crc32 == self.entry.0.entry().crc32() {
+            Ok((written, crc32))
+        } else {
+            Err(ZipError::CRC32CheckError)
+        }
+    }
+
+    /// Reads all bytes until EOF has been reached, appending them to buf, and verifies both the CRC32 and CRC-32C
+    /// values.
+    ///
+    /// If the entry wasn't written with a CRC-32C extra field, only the CRC32 value is verified.
+    ///
+    /// Note that this requires the `crc32c` feature.
+    #[cfg(feature = "crc32c")]
+    pub async fn read_to_end_checked_crc32c(&mut self, buf: &mut Vec<u8>) -> Result<usize> {
+        let start = buf.len();
+        let read = self.read_to_end_checked(buf).await?;
+
+        match self.entry.0.entry().crc32c() {
+            Some(expected) if crc32c::crc32c(&buf[start..]) != expected => Err(ZipError::CRC32CCheckError),
+            _ => Ok(read),
+        }
+    }
+
+    /// Reads all bytes until EOF has been reached, decodes them from the provided encoding, and appends the result to
+    /// buf.
+    ///
+    /// The CRC32 value is verified against the raw decompressed bytes before they're decoded. Decoding follows the
+    /// WHATWG behaviour of `encoding_rs`, so a byte order mark will override the provided encoding and any malformed
+    /// sequences are replaced with U+FFFD. Returns the number of raw bytes read.
+    ///
+    /// Note that this requires the `encoding_rs` feature.
+    #[cfg(feature = "encoding_rs")]
+    pub async fn read_to_string_with_encoding(
+        &mut self,
+        encoding: &'static encoding_rs::Encoding,
+        buf: &mut String,
+    ) -> Result<usize> {
+        let mut raw = Vec::new();
+        let read = self.read_to_end_checked(&mut raw).await?;
+
+        buf.push_str(&encoding.decode(&raw).0);
+        Ok(read)
+    }
+
     /// Reads all bytes until EOF has been reached, placing them into buf, and verifies the CRC32 values.
     ///
     /// This is a helper function synonymous to [`AsyncReadExt::read_to_string()`].
@@ -114,7 +271,7 @@ where
 }
 
 enum OwnedEntry<'a> {
-    Owned(ZipEntry),
+    Owned(Box<ZipEntry>),
     Borrow(&'a ZipEntry),
 }
 
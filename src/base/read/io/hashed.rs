@@ -16,6 +16,8 @@ pub(crate) struct HashedReader<R> {
     #[pin]
     pub(crate) reader: R,
     pub(crate) hasher: Hasher,
+    /// The number of bytes read so far.
+    pub(crate) read: u64,
 }
 
 impl<R> HashedReader<R>
@@ -24,7 +26,7 @@ where
 {
     /// Constructs a new wrapping reader from a generic [`AsyncRead`] implementer.
     pub(crate) fn new(reader: R) -> Self {
-        Self { reader, hasher: Hasher::default() }
+        Self { reader, hasher: Hasher::default(), read: 0 }
     }
 
     /// Swaps the internal hasher and returns the computed CRC32 hash.
@@ -50,6 +52,7 @@ where
         let project = self.project();
         let written = poll_result_ok!(ready!(project.reader.poll_read(c, b)));
         project.hasher.update(&b[..written]);
+        *project.read += written as u64;
 
         Poll::Ready(Ok(written))
     }
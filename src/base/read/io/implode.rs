@@ -0,0 +1,263 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+//! A decoder for the legacy Implode compression method (method 6), as produced by PKZIP 1.x.
+//!
+//! Imploded data consists of two or three Shannon-Fano trees followed by a stream of literals & back-references into
+//! a 4K or 8K sliding dictionary. As the data has no end-of-stream marker, decoding relies on the uncompressed size.
+//!
+//! <https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md#53>
+
+use crate::spec::header::GeneralPurposeFlag;
+
+use std::io::{Error, ErrorKind};
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use futures_lite::io::{AsyncBufRead, AsyncRead};
+use pin_project::pin_project;
+
+/// The maximum bit length of a Shannon-Fano code.
+const MAX_BITS: usize = 16;
+
+/// The options of an Implode entry, as stored within bits 1 & 2 of its general purpose flag.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct ImplodeOptions {
+    /// Whether an 8K (rather than 4K) sliding dictionary is used.
+    pub(crate) large_dictionary: bool,
+    /// Whether literals are encoded via a third tree (rather than as raw bytes).
+    pub(crate) literal_tree: bool,
+}
+
+impl From<GeneralPurposeFlag> for ImplodeOptions {
+    fn from(flags: GeneralPurposeFlag) -> Self {
+        Self { large_dictionary: flags.compression_option_1, literal_tree: flags.compression_option_2 }
+    }
+}
+
+/// A reader which decodes Implode compressed data from an inner [`AsyncBufRead`] implementer.
+///
+/// The compressed data is buffered in full and decoded once the inner reader reaches EOF. This is acceptable as
+/// archives using this method were produced for machines with very little memory, and so entries tend to be small.
+#[pin_project]
+pub(crate) struct ImplodeDecoder<R> {
+    #[pin]
+    reader: R,
+    options: ImplodeOptions,
+    size: Option<u64>,
+    input: Vec<u8>,
+    output: Option<Vec<u8>>,
+    pos: usize,
+}
+
+impl<R> ImplodeDecoder<R>
+where
+    R: AsyncBufRead + Unpin,
+{
+    /// Constructs a new decoder which will produce `size` bytes of data, or decode until the input runs out if `None`.
+    pub(crate) fn new(reader: R, options: ImplodeOptions, size: Option<u64>) -> Self {
+        Self { reader, options, size, input: Vec::new(), output: None, pos: 0 }
+    }
+
+    /// Consumes this decoder and returns the inner reader.
+    pub(crate) fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R> AsyncRead for ImplodeDecoder<R>
+where
+    R: AsyncBufRead + Unpin,
+{
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<std::io::Result<usize>> {
+        let mut this = self.project();
+
+        loop {
+            if let Some(output) = this.output.as_ref() {
+                let read = std::cmp::min(output.len() - *this.pos, buf.len());
+                buf[..read].copy_from_slice(&output[*this.pos..*this.pos + read]);
+                *this.pos += read;
+
+                return Poll::Ready(Ok(read));
+            }
+
+            let data = ready!(this.reader.as_mut().poll_fill_buf(cx))?;
+
+            if data.is_empty() {
+                let output = explode(this.input, *this.options, *this.size)
+                    .map_err(|message| Error::new(ErrorKind::InvalidData, message))?;
+
+                *this.input = Vec::new();
+                *this.output = Some(output);
+                continue;
+            }
+
+            let length = data.len();
+            this.input.extend_from_slice(data);
+            this.reader.as_mut().consume(length);
+        }
+    }
+}
+
+/// A reader of the least significant bit first stream which follows the trees.
+struct BitReader<'a> {
+    data: &'a [u8],
+    buffer: u32,
+    count: u32,
+}
+
+impl<'a> BitReader<'a> {
+    /// Reads & returns `count` bits (at most 8), or `None` if the input has run out.
+    fn bits(&mut self, count: u32) -> Option<u32> {
+        while self.count < count {
+            let (byte, rest) = self.data.split_first()?;
+            self.buffer |= (*byte as u32) << self.count;
+            self.count += 8;
+            self.data = rest;
+        }
+
+        let value = self.buffer & ((1 << count) - 1);
+        self.buffer >>= count;
+        self.count -= count;
+
+        Some(value)
+    }
+}
+
+/// A Shannon-Fano tree in canonical form (ie. the number of codes of each bit length & the symbols ordered by them).
+struct Tree {
+    counts: [u16; MAX_BITS + 1],
+    symbols: Vec<u16>,
+}
+
+impl Tree {
+    /// Parses a tree of `size` symbols from the start of the provided data, returning the remaining data.
+    ///
+    /// Trees are stored as a byte holding the number of following bytes minus one, where each following byte holds
+    /// a bit length minus one (in the low nibble) & the number of consecutive symbols using it minus one (in the high
+    /// nibble).
+    fn parse(data: &[u8], size: usize) -> Result<(Tree, &[u8]), &'static str> {
+        let (length, data) = data.split_first().ok_or("truncated implode tree")?;
+        let length = *length as usize + 1;
+
+        if data.len() < length {
+            return Err("truncated implode tree");
+        }
+
+        let mut lengths = Vec::with_capacity(size);
+        for byte in &data[..length] {
+            let bit_length = (byte & 0x0F) + 1;
+            let repeat = (byte >> 4) as usize + 1;
+            lengths.extend(std::iter::repeat_n(bit_length, repeat));
+        }
+
+        if lengths.len() != size {
+            return Err("invalid implode tree size");
+        }
+
+        let mut counts = [0; MAX_BITS + 1];
+        for bit_length in &lengths {
+            counts[*bit_length as usize] += 1;
+        }
+
+        let mut symbols = Vec::with_capacity(size);
+        for bit_length in 1..=MAX_BITS {
+            let matching = lengths.iter().enumerate().filter(|(_, length)| **length as usize == bit_length);
+            symbols.extend(matching.map(|(symbol, _)| symbol as u16));
+        }
+
+        Ok((Tree { counts, symbols }, &data[length..]))
+    }
+
+    /// Decodes the next symbol, or returns `None` if the input has run out or the code is invalid.
+    ///
+    /// Codes are stored with all bits inverted, and are otherwise assigned in the same canonical order as Deflate.
+    fn decode(&self, reader: &mut BitReader) -> Option<u16> {
+        let mut code = 0;
+        let mut first = 0;
+        let mut index = 0;
+
+        for count in &self.counts[1..] {
+            code |= reader.bits(1)? as i32 ^ 1;
+            let count = *count as i32;
+
+            if code - count < first {
+                return self.symbols.get((index + code - first) as usize).copied();
+            }
+
+            index += count;
+            first = (first + count) << 1;
+            code <<= 1;
+        }
+
+        None
+    }
+}
+
+/// Decodes the provided Implode compressed data.
+fn explode(data: &[u8], options: ImplodeOptions, size: Option<u64>) -> Result<Vec<u8>, &'static str> {
+    let (literal_tree, data) = match options.literal_tree {
+        true => Tree::parse(data, 256).map(|(tree, data)| (Some(tree), data))?,
+        false => (None, data),
+    };
+    let (length_tree, data) = Tree::parse(data, 64)?;
+    let (distance_tree, data) = Tree::parse(data, 64)?;
+
+    let distance_bits = if options.large_dictionary { 7 } else { 6 };
+    let minimum_length = if options.literal_tree { 3 } else { 2 };
+    let limit = size.unwrap_or(u64::MAX);
+
+    let mut reader = BitReader { data, buffer: 0, count: 0 };
+    let mut output = Vec::with_capacity(size.unwrap_or_default().min(1 << 20) as usize);
+
+    while (output.len() as u64) < limit {
+        let symbol = match reader.bits(1) {
+            Some(flag) => flag,
+            None if size.is_none() => break,
+            None => return Err("truncated implode data"),
+        };
+
+        // Each step below decodes a complete symbol, so only running out of input between symbols is accepted.
+        let mut step = || -> Option<()> {
+            if symbol == 1 {
+                let literal = match &literal_tree {
+                    Some(tree) => tree.decode(&mut reader)? as u8,
+                    None => reader.bits(8)? as u8,
+                };
+                output.push(literal);
+                return Some(());
+            }
+
+            let low = reader.bits(distance_bits)? as usize;
+            let high = distance_tree.decode(&mut reader)? as usize;
+            let distance = ((high << distance_bits) | low) + 1;
+
+            let mut length = length_tree.decode(&mut reader)? as usize;
+            if length == 63 {
+                length += reader.bits(8)? as usize;
+            }
+            length += minimum_length;
+
+            // Distances reaching before the start of the data refer to an implicitly zeroed dictionary.
+            for _ in 0..length {
+                let byte = output.len().checked_sub(distance).map(|index| output[index]).unwrap_or_default();
+                output.push(byte);
+            }
+
+            Some(())
+        };
+
+        if step().is_none() {
+            if size.is_none() {
+                break;
+            }
+            return Err("truncated implode data");
+        }
+    }
+
+    if let Some(size) = size {
+        output.truncate(size as usize);
+    }
+
+    Ok(output)
+}
@@ -48,6 +48,11 @@ where
     R: AsyncRead + AsyncSeek + Unpin,
 {
     let length = reader.seek(SeekFrom::End(0)).await?;
+
+    if length == 0 {
+        return Err(ZipError::EmptyArchive);
+    }
+
     let signature = &EOCDR_SIGNATURE.to_le_bytes();
     let mut buffer: [u8; BUFFER_SIZE] = [0; BUFFER_SIZE];
 
@@ -55,7 +60,7 @@ where
     reader.seek(SeekFrom::Start(position)).await?;
 
     loop {
-        let read = reader.read(&mut buffer).await?;
+        let read = read_full(&mut reader, &mut buffer).await?;
 
         if let Some(match_index) = reverse_search_buffer(&buffer[..read], signature) {
             return Ok(position + (match_index + 1) as u64);
@@ -74,6 +79,53 @@ where
     }
 }
 
+/// Reads into the buffer until it's full or EOF is reached, returning the number of bytes read.
+///
+/// Sources such as network streams may return fewer bytes than requested from a single read, which would otherwise
+/// cause the EOCDR to be missed.
+async fn read_full<R>(mut reader: R, buffer: &mut [u8]) -> std::io::Result<usize>
+where
+    R: AsyncRead + Unpin,
+{
+    let mut read = 0;
+
+    while read < buffer.len() {
+        match reader.read(&mut buffer[read..]).await? {
+            0 => break,
+            length => read += length,
+        }
+    }
+
+    Ok(read)
+}
+
+/// Locate the offset of the next signature at or after the provided position, if one exists.
+///
+/// Unlike [`eocdr()`], this searches forwards in buffered chunks and is only used when scanning for headers during
+/// recovery, where no EOCDR can be relied upon.
+pub(crate) async fn forward_search<R>(mut reader: R, mut position: u64, signature: u32) -> ZipResult<Option<u64>>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    let signature = &signature.to_le_bytes();
+    let mut buffer: [u8; BUFFER_SIZE] = [0; BUFFER_SIZE];
+
+    loop {
+        reader.seek(SeekFrom::Start(position)).await?;
+        let read = reader.read(&mut buffer).await?;
+
+        if read == 0 {
+            return Ok(None);
+        }
+        if let Some(match_index) = buffer[..read].windows(SIGNATURE_LENGTH).position(|window| window == signature) {
+            return Ok(Some(position + match_index as u64));
+        }
+
+        // As with the reverse search, overlap reads so that signatures crossing buffer boundaries are still found.
+        position += read.saturating_sub(SIGNATURE_LENGTH - 1).max(1) as u64;
+    }
+}
+
 /// A naive reverse linear search along the buffer for the specified signature bytes.
 ///
 /// This is already surprisingly performant. For instance, using memchr::memchr() to match for the first byte of the
@@ -0,0 +1,118 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::spec::consts::{LZMA_ALONE_HEADER_LENGTH, LZMA_PROPERTIES_LENGTH, LZMA_ZIP_HEADER_LENGTH};
+
+use std::io::{Error, ErrorKind};
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use futures_lite::io::{AsyncBufRead, AsyncRead};
+use pin_project::pin_project;
+
+/// A wrapping reader which converts the framing of LZMA data within ZIP entries into the standalone `.lzma` format
+/// expected by the LZMA decoder.
+///
+/// The ZIP-specific header (the LZMA SDK version, the length of the properties, and the properties themselves) is
+/// replaced by the properties followed by the uncompressed size. If the size isn't provided, it's marked as unknown and
+/// the LZMA data must instead be terminated by an end-of-stream marker (as is the case for data written by this crate).
+#[pin_project]
+pub(crate) struct LzmaHeaderReader<R> {
+    #[pin]
+    inner: R,
+    zip_header: [u8; LZMA_ZIP_HEADER_LENGTH],
+    zip_header_read: usize,
+    alone_header: [u8; LZMA_ALONE_HEADER_LENGTH],
+    alone_header_read: usize,
+    uncompressed_size: Option<u64>,
+}
+
+impl<R> LzmaHeaderReader<R>
+where
+    R: AsyncBufRead + Unpin,
+{
+    /// Constructs a new wrapping reader from a generic [`AsyncBufRead`] implementer.
+    ///
+    /// The uncompressed size should be provided if the LZMA data isn't terminated by an end-of-stream marker (ie. bit 1
+    /// of the entry's general purpose flag is clear), as the decoder otherwise has no way of knowing where it ends.
+    pub(crate) fn new(inner: R, uncompressed_size: Option<u64>) -> Self {
+        Self {
+            inner,
+            zip_header: [0; LZMA_ZIP_HEADER_LENGTH],
+            zip_header_read: 0,
+            alone_header: [0; LZMA_ALONE_HEADER_LENGTH],
+            alone_header_read: 0,
+            uncompressed_size,
+        }
+    }
+
+    /// Consumes this reader and returns the inner value.
+    pub(crate) fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R> AsyncRead for LzmaHeaderReader<R>
+where
+    R: AsyncBufRead + Unpin,
+{
+    fn poll_read(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<std::io::Result<usize>> {
+        let available = ready!(self.as_mut().poll_fill_buf(cx))?;
+        let length = std::cmp::min(available.len(), buf.len());
+
+        buf[..length].copy_from_slice(&available[..length]);
+        self.consume(length);
+
+        Poll::Ready(Ok(length))
+    }
+}
+
+impl<R> AsyncBufRead for LzmaHeaderReader<R>
+where
+    R: AsyncBufRead + Unpin,
+{
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<&[u8]>> {
+        let mut this = self.project();
+
+        if *this.zip_header_read < LZMA_ZIP_HEADER_LENGTH {
+            while *this.zip_header_read < LZMA_ZIP_HEADER_LENGTH {
+                let available = ready!(this.inner.as_mut().poll_fill_buf(cx))?;
+
+                if available.is_empty() {
+                    return Poll::Ready(Err(Error::from(ErrorKind::UnexpectedEof)));
+                }
+
+                let length = std::cmp::min(available.len(), LZMA_ZIP_HEADER_LENGTH - *this.zip_header_read);
+                this.zip_header[*this.zip_header_read..*this.zip_header_read + length]
+                    .copy_from_slice(&available[..length]);
+                this.inner.as_mut().consume(length);
+                *this.zip_header_read += length;
+            }
+
+            let properties_length = u16::from_le_bytes([this.zip_header[2], this.zip_header[3]]);
+            if properties_length as usize != LZMA_PROPERTIES_LENGTH {
+                return Poll::Ready(Err(Error::new(ErrorKind::InvalidData, "unexpected LZMA properties length")));
+            }
+
+            this.alone_header[..LZMA_PROPERTIES_LENGTH].copy_from_slice(&this.zip_header[4..]);
+            this.alone_header[LZMA_PROPERTIES_LENGTH..]
+                .copy_from_slice(&this.uncompressed_size.unwrap_or(u64::MAX).to_le_bytes());
+        }
+
+        if *this.alone_header_read < LZMA_ALONE_HEADER_LENGTH {
+            return Poll::Ready(Ok(&this.alone_header[*this.alone_header_read..]));
+        }
+
+        this.inner.poll_fill_buf(cx)
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        let this = self.project();
+
+        if *this.alone_header_read < LZMA_ALONE_HEADER_LENGTH {
+            *this.alone_header_read = std::cmp::min(*this.alone_header_read + amt, LZMA_ALONE_HEADER_LENGTH);
+        } else {
+            this.inner.consume(amt);
+        }
+    }
+}
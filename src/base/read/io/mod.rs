@@ -1,11 +1,18 @@
 // Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
+pub(crate) mod base_offset;
+pub(crate) mod buffered;
+pub(crate) mod codec;
 pub(crate) mod combined_record;
 pub(crate) mod compressed;
 pub(crate) mod entry;
 pub(crate) mod hashed;
+#[cfg(feature = "legacy")]
+pub(crate) mod implode;
 pub(crate) mod locator;
+#[cfg(feature = "lzma")]
+pub(crate) mod lzma;
 pub(crate) mod owned;
 
 pub use combined_record::CombinedCentralDirectoryRecord;
@@ -4,7 +4,9 @@
 //! A concurrent ZIP reader which acts over an owned vector of bytes.
 //!
 //! Concurrency is achieved as a result of:
-//! - Wrapping the provided vector of bytes within an [`Arc`] to allow shared ownership.
+//! - Wrapping the provided vector of bytes within an [`Arc`] to allow shared ownership. Bytes which are already shared
+//!   (eg. an `Arc<[u8]>` held within a cache) can instead be provided via [`ZipFileReader::from_shared()`], or via
+//!   `ZipFileReader::from_bytes()` for a `bytes::Bytes` buffer (requiring the `bytes` feature).
 //! - Wrapping this [`Arc`] around a [`Cursor`] when reading (as the [`Arc`] can deref and coerce into a `&[u8]`).
 //!
 //! ### Usage
@@ -71,9 +73,12 @@
 use crate::base::read::seek;
 
 use crate::base::read::io::entry::ZipEntryReader;
+use crate::codec::{Codec, CodecRegistry};
+use crate::entry::ZipEntry;
 use crate::error::{Result, ZipError};
 use crate::file::ZipFile;
 
+use std::ops::Deref;
 use std::sync::Arc;
 
 use futures_lite::io::Cursor;
@@ -81,14 +86,47 @@ use futures_lite::io::Cursor;
 use super::io::entry::{WithEntry, WithoutEntry};
 
 struct Inner {
-    data: Vec<u8>,
+    data: Data,
     file: ZipFile,
 }
 
+/// The bytes backing a reader, which are either owned or shared with other owners.
+enum Data {
+    Owned(Vec<u8>),
+    Shared(Arc<[u8]>),
+    #[cfg(feature = "bytes")]
+    Bytes(bytes::Bytes),
+}
+
+impl Deref for Data {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match self {
+            Data::Owned(data) => data,
+            Data::Shared(data) => data,
+            #[cfg(feature = "bytes")]
+            Data::Bytes(data) => data,
+        }
+    }
+}
+
 // A concurrent ZIP reader which acts over an owned vector of bytes.
 #[derive(Clone)]
 pub struct ZipFileReader {
     inner: Arc<Inner>,
+    codecs: CodecRegistry,
+}
+
+impl std::fmt::Debug for ZipFileReader {
+    // The archive data itself is omitted as it'd otherwise dominate the output.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ZipFileReader")
+            .field("data_length", &self.inner.data.len())
+            .field("file", &self.inner.file)
+            .field("codecs", &self.codecs)
+            .finish()
+    }
 }
 
 impl ZipFileReader {
@@ -98,11 +136,59 @@ impl ZipFileReader {
         Ok(ZipFileReader::from_raw_parts(data, file))
     }
 
+    /// Constructs a new ZIP reader from an owned vector of bytes, returning an error before reading the central
+    /// directory if the estimated memory needed to hold it exceeds the provided budget (in bytes).
+    ///
+    /// See [`estimate_cd_memory()`] for how this is estimated.
+    ///
+    /// [`estimate_cd_memory()`]: crate::base::read::estimate_cd_memory
+    pub async fn new_with_memory_budget(data: Vec<u8>, budget: usize) -> Result<ZipFileReader> {
+        let file = crate::base::read::file_with_budget(Cursor::new(&data), Some(budget)).await?;
+        Ok(ZipFileReader::from_raw_parts(data, file))
+    }
+
+    /// Constructs a new ZIP reader from an owned vector of bytes, falling back to recovery if the central directory
+    /// can't be read.
+    ///
+    /// In recovery mode, the bytes are scanned from the start for local file headers to reconstruct a best-effort list
+    /// of entries. Whether this occurred can be checked via [`ZipFile::recovered()`].
+    pub async fn new_with_recovery(data: Vec<u8>) -> Result<ZipFileReader> {
+        let file = match crate::base::read::file(Cursor::new(&data)).await {
+            Ok(file) => file,
+            Err(_) => crate::base::read::recovery::file(Cursor::new(&data)).await?,
+        };
+        Ok(ZipFileReader::from_raw_parts(data, file))
+    }
+
     /// Constructs a ZIP reader from an owned vector of bytes and ZIP file information derived from those bytes.
     ///
     /// Providing a [`ZipFile`] that wasn't derived from those bytes may lead to inaccurate parsing.
     pub fn from_raw_parts(data: Vec<u8>, file: ZipFile) -> ZipFileReader {
-        ZipFileReader { inner: Arc::new(Inner { data, file }) }
+        ZipFileReader { inner: Arc::new(Inner { data: Data::Owned(data), file }), codecs: CodecRegistry::default() }
+    }
+
+    /// Constructs a new ZIP reader from shared bytes, without copying them.
+    ///
+    /// This is useful when the same archive is held in memory (eg. within a cache) and read by many concurrent
+    /// consumers, as entry readers borrow directly from the shared bytes.
+    pub async fn from_shared(data: Arc<[u8]>) -> Result<ZipFileReader> {
+        let file = crate::base::read::file(Cursor::new(&data[..])).await?;
+        Ok(ZipFileReader {
+            inner: Arc::new(Inner { data: Data::Shared(data), file }),
+            codecs: CodecRegistry::default(),
+        })
+    }
+
+    /// Constructs a new ZIP reader from a [`Bytes`](bytes::Bytes) buffer, without copying it.
+    ///
+    /// This is useful when the archive was received via a library which provides its data as [`Bytes`](bytes::Bytes)
+    /// (eg. a request body within `hyper`), as the buffer's reference count is held rather than its data being cloned.
+    ///
+    /// Note that this requires the `bytes` feature.
+    #[cfg(feature = "bytes")]
+    pub async fn from_bytes(data: bytes::Bytes) -> Result<ZipFileReader> {
+        let file = crate::base::read::file(Cursor::new(&data[..])).await?;
+        Ok(ZipFileReader { inner: Arc::new(Inner { data: Data::Bytes(data), file }), codecs: CodecRegistry::default() })
     }
 
     /// Returns this ZIP file's information.
@@ -110,38 +196,53 @@ impl ZipFileReader {
         &self.inner.file
     }
 
+    /// Registers a codec for reading entries which use the provided custom compression method id, replacing any codec
+    /// previously registered for it.
+    ///
+    /// Returns [`ZipError::CompressionMethodReserved`] if the method id belongs to a method implemented by this crate.
+    /// See the [`codec`](crate::codec) module for more information.
+    pub fn register_codec(&mut self, method: u16, codec: Arc<dyn Codec>) -> Result<()> {
+        self.codecs.register(method, codec)
+    }
+
     /// Returns the raw bytes provided to the reader during construction.
     pub fn data(&self) -> &[u8] {
         &self.inner.data
     }
 
     /// Returns a new entry reader if the provided index is valid.
-    pub async fn reader_without_entry(&self, index: usize) -> Result<ZipEntryReader<Cursor<&[u8]>, WithoutEntry>> {
+    pub async fn reader_without_entry(&self, index: usize) -> Result<ZipEntryReader<'_, Cursor<&[u8]>, WithoutEntry>> {
         let stored_entry = self.inner.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
         let mut cursor = Cursor::new(&self.inner.data[..]);
 
         stored_entry.seek_to_data_offset(&mut cursor).await?;
 
-        Ok(ZipEntryReader::new_with_owned(
-            cursor,
-            stored_entry.entry.compression(),
-            stored_entry.entry.compressed_size(),
-        ))
+        let size = stored_entry.entry.compressed_size();
+        ZipEntryReader::new_with_owned(cursor, &stored_entry.entry, size, &self.codecs)
     }
 
     /// Returns a new entry reader if the provided index is valid.
-    pub async fn reader_with_entry(&self, index: usize) -> Result<ZipEntryReader<Cursor<&[u8]>, WithEntry<'_>>> {
+    pub async fn reader_with_entry(&self, index: usize) -> Result<ZipEntryReader<'_, Cursor<&[u8]>, WithEntry<'_>>> {
         let stored_entry = self.inner.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
         let mut cursor = Cursor::new(&self.inner.data[..]);
 
         stored_entry.seek_to_data_offset(&mut cursor).await?;
 
-        let reader = ZipEntryReader::new_with_owned(
-            cursor,
-            stored_entry.entry.compression(),
-            stored_entry.entry.compressed_size(),
-        );
+        let size = stored_entry.entry.compressed_size();
+        let reader = ZipEntryReader::new_with_owned(cursor, &stored_entry.entry, size, &self.codecs)?;
 
         Ok(reader.into_with_entry(stored_entry))
     }
+
+    /// Returns a new entry reader along with a copy of the entry's metadata if the provided index is valid.
+    ///
+    /// This is useful when the entry's metadata (eg. its filename or size) is needed alongside the reader, such as for
+    /// logging, as the returned reader holds a borrow of this ZIP reader.
+    pub async fn reader_with_entry_meta(
+        &self,
+        index: usize,
+    ) -> Result<(ZipEntry, ZipEntryReader<'_, Cursor<&[u8]>, WithEntry<'_>>)> {
+        let entry = self.file().entries().get(index).ok_or(ZipError::EntryIndexOutOfBounds)?.entry.clone();
+        Ok((entry, self.reader_with_entry(index).await?))
+    }
 }
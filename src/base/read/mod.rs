@@ -4,10 +4,13 @@
 //! A module which supports reading ZIP files.
 
 pub mod mem;
+pub mod multi;
+pub mod range;
 pub mod seek;
 pub mod stream;
 
 pub(crate) mod io;
+pub(crate) mod recovery;
 
 use crate::ZipString;
 // Re-exported as part of the public API.
@@ -18,10 +21,14 @@ pub use crate::base::read::io::entry::ZipEntryReader;
 use crate::date::ZipDateTime;
 use crate::entry::{StoredZipEntry, ZipEntry};
 use crate::error::{Result, ZipError};
-use crate::file::ZipFile;
+use crate::file::{ArchiveSummary, ZipFile};
 use crate::spec::attribute::AttributeCompatibility;
 use crate::spec::consts::LFH_LENGTH;
-use crate::spec::consts::{CDH_SIGNATURE, LFH_SIGNATURE, NON_ZIP64_MAX_SIZE, SIGNATURE_LENGTH, ZIP64_EOCDL_LENGTH};
+use crate::spec::consts::{
+    AES_COMPRESSION_METHOD, CDH_SIGNATURE, DATA_DESCRIPTOR_SIGNATURE, DIGITAL_SIGNATURE_SIGNATURE, EOCDR_LENGTH,
+    EOCDR_SIGNATURE, LFH_SIGNATURE, NON_ZIP64_MAX_SIZE, SIGNATURE_LENGTH, ZIP64_EOCDL_LENGTH, ZIP64_EOCDL_SIGNATURE,
+    ZIP64_EOCDR_LENGTH, ZIP64_EOCDR_SIGNATURE,
+};
 use crate::spec::header::InfoZipUnicodeCommentExtraField;
 use crate::spec::header::InfoZipUnicodePathExtraField;
 use crate::spec::header::{
@@ -34,9 +41,171 @@ use crate::string::StringEncoding;
 use crate::base::read::io::CombinedCentralDirectoryRecord;
 use crate::spec::parse::parse_extra_fields;
 
-use futures_lite::io::{AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt, SeekFrom};
+use futures_lite::io::{AsyncBufRead, AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt, SeekFrom};
+
+use std::sync::OnceLock;
+
+/// Reads only the end of central directory records of a ZIP file, returning a summary of the archive.
+///
+/// This locates and parses the EOCDR (and the ZIP64 EOCDL & EOCDR, if present) without reading any central directory
+/// records, so is considerably faster than reading the full [`ZipFile`] when only the headline numbers are needed.
+pub async fn read_summary<R>(mut reader: R) -> Result<ArchiveSummary>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    let (eocdr, comment, zip64, _) = eocdr(&mut reader).await?;
+
+    Ok(ArchiveSummary {
+        num_entries: eocdr.num_entries_in_directory,
+        central_directory_size: eocdr.directory_size,
+        central_directory_offset: eocdr.offset_of_start_of_directory,
+        comment,
+        zip64,
+        disk_number: eocdr.disk_number,
+        total_disks: eocdr.total_disks,
+    })
+}
+
+/// Returns whether two ZIP files are structurally equivalent, ignoring timestamps, compression, and the order of their
+/// entries.
+///
+/// ZIP files are considered equivalent if they contain the same entry filenames, with each having the same CRC32 value
+/// and uncompressed size. This is determined from their central directories, so no entries are decompressed unless
+/// their CRC32 value is absent (ie. zero for an entry which isn't empty).
+pub async fn archives_equivalent<A, B>(a: A, b: B) -> Result<bool>
+where
+    A: AsyncBufRead + AsyncSeek + Unpin,
+    B: AsyncBufRead + AsyncSeek + Unpin,
+{
+    let mut a = seek::ZipFileReader::new(a).await?;
+    let mut b = seek::ZipFileReader::new(b).await?;
+
+    if a.file().entries().len() != b.file().entries().len() {
+        return Ok(false);
+    }
+
+    Ok(entry_digests(&mut a).await? == entry_digests(&mut b).await?)
+}
+
+/// Returns the filename, CRC32 value, and uncompressed size of each entry, sorted by filename.
+async fn entry_digests<R>(reader: &mut seek::ZipFileReader<R>) -> Result<Vec<(Vec<u8>, u32, u64)>>
+where
+    R: AsyncBufRead + AsyncSeek + Unpin,
+{
+    let mut digests = Vec::with_capacity(reader.file().entries().len());
+
+    for index in 0..reader.file().entries().len() {
+        let entry = &reader.file().entries()[index];
+        let (filename, mut crc32, size) =
+            (entry.filename().as_bytes().to_vec(), entry.crc32(), entry.uncompressed_size());
+
+        if crc32 == 0 && size > 0 {
+            let mut entry_reader = reader.reader_without_entry(index).await?;
+            futures_lite::io::copy(&mut entry_reader, futures_lite::io::sink()).await?;
+            crc32 = entry_reader.compute_hash();
+        }
+
+        digests.push((filename, crc32, size));
+    }
 
-pub(crate) async fn file<R>(mut reader: R) -> Result<ZipFile>
+    digests.sort_unstable();
+    Ok(digests)
+}
+
+/// The assumed average number of bytes allocated for each entry's filename, extra fields, and comment.
+const AVERAGE_ENTRY_HEAP_SIZE: usize = 64;
+
+/// Returns an estimate of the number of bytes of memory needed to hold a central directory with the provided number of
+/// entries once parsed.
+///
+/// The estimate assumes an average of 64 bytes for each entry's filename, extra fields, and comment, alongside the
+/// fixed size of each entry. As the number of entries is declared by the end of central directory record, this can be
+/// checked (eg. via [`read_summary()`]) before the central directory itself is read.
+pub fn estimate_cd_memory(num_entries: u64) -> usize {
+    let entry_size = std::mem::size_of::<StoredZipEntry>() + AVERAGE_ENTRY_HEAP_SIZE;
+    usize::try_from(num_entries).unwrap_or(usize::MAX).saturating_mul(entry_size)
+}
+
+pub(crate) async fn file<R>(reader: R) -> Result<ZipFile>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    file_with_budget(reader, None).await
+}
+
+/// Reads the ZIP file's information, first returning an error if the estimated memory needed to hold its central
+/// directory exceeds the provided budget.
+pub(crate) async fn file_with_budget<R>(mut reader: R, budget: Option<usize>) -> Result<ZipFile>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    let (mut eocdr, comment, zip64, comment_length_mismatch) = eocdr(&mut reader).await?;
+
+    if let Some(budget) = budget {
+        let estimate = estimate_cd_memory(eocdr.num_entries_in_directory);
+        if estimate > budget {
+            return Err(ZipError::MemoryBudgetExceeded(estimate, budget));
+        }
+    }
+
+    // Outdated feature so unlikely to ever make it into this crate.
+    if eocdr.disk_number != eocdr.disk_number_start_of_cd
+        || eocdr.num_entries_in_directory != eocdr.num_entries_in_directory_on_disk
+    {
+        return Err(ZipError::FeatureNotSupported("Spanned/split files"));
+    }
+
+    // Find and parse the central directory.
+    reader.seek(SeekFrom::Start(eocdr.offset_of_start_of_directory)).await?;
+    let entries = crate::base::read::cd(reader, eocdr.num_entries_in_directory, zip64).await?;
+
+    let zip64_extensible_data = std::mem::take(&mut eocdr.zip64_extensible_data);
+    Ok(ZipFile {
+        entries,
+        comment,
+        zip64,
+        recovered: false,
+        comment_length_mismatch,
+        zip64_extensible_data,
+        disk_number: eocdr.disk_number,
+        total_disks: eocdr.total_disks,
+        #[cfg(feature = "indexmap")]
+        entry_map: std::sync::OnceLock::new(),
+    })
+}
+
+/// Detects the length of any data prepended to a ZIP file (eg. a self-extracting stub).
+///
+/// This is the difference between the actual position of the end of central directory records and the position implied
+/// by the central directory's offset & size (or for ZIP64 files, the offset stored within the ZIP64 EOCDL). The ZIP64
+/// EOCDR is assumed to have no extensible data, as its size can't be read before the prefix's length is known.
+pub(crate) async fn base_offset<R>(mut reader: R) -> Result<u64>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    let eocdr_offset = crate::base::read::io::locator::eocdr(&mut reader).await?;
+
+    reader.seek(SeekFrom::Start(eocdr_offset)).await?;
+    let eocdr = EndOfCentralDirectoryHeader::from_reader(&mut reader).await?;
+
+    if let Some(offset) = eocdr_offset.checked_sub(ZIP64_EOCDL_LENGTH + SIGNATURE_LENGTH as u64) {
+        reader.seek(SeekFrom::Start(offset)).await?;
+
+        if let Some(locator) = Zip64EndOfCentralDirectoryLocator::try_from_reader(&mut reader).await? {
+            let zip64_eocdr_offset = offset.saturating_sub((SIGNATURE_LENGTH + ZIP64_EOCDR_LENGTH) as u64);
+            return Ok(zip64_eocdr_offset.saturating_sub(locator.relative_offset));
+        }
+    }
+
+    let cd_end = eocdr.cent_dir_offset as u64 + eocdr.size_cent_dir as u64;
+    Ok((eocdr_offset - SIGNATURE_LENGTH as u64).saturating_sub(cd_end))
+}
+
+/// Locates and parses the EOCDR, combining it with the ZIP64 EOCDR if present.
+///
+/// Returns the combined record, the archive comment, whether the archive is ZIP64, and whether the comment's declared
+/// length didn't match the number of bytes following the EOCDR.
+async fn eocdr<R>(mut reader: R) -> Result<(CombinedCentralDirectoryRecord, ZipString, bool, bool)>
 where
     R: AsyncRead + AsyncSeek + Unpin,
 {
@@ -46,7 +215,15 @@ where
     reader.seek(SeekFrom::Start(eocdr_offset)).await?;
     let eocdr = EndOfCentralDirectoryHeader::from_reader(&mut reader).await?;
 
-    let comment = io::read_string(&mut reader, eocdr.file_comm_length.into(), crate::StringEncoding::Utf8).await?;
+    // Some tools write a comment length which doesn't account for the bytes actually following the EOCDR, so clamp the
+    // read to those available rather than trusting the declared length.
+    let comment_offset = eocdr_offset + EOCDR_LENGTH as u64;
+    let available = reader.seek(SeekFrom::End(0)).await?.saturating_sub(comment_offset);
+    let comment_length_mismatch = available != eocdr.file_comm_length as u64;
+
+    reader.seek(SeekFrom::Start(comment_offset)).await?;
+    let comment_length = available.min(eocdr.file_comm_length as u64) as usize;
+    let comment = io::read_string(&mut reader, comment_length, crate::StringEncoding::Utf8).await?;
 
     // Check the 20 bytes before the EOCDR for the Zip64 EOCDL, plus an extra 4 bytes because the offset
     // does not include the signature. If the ECODL exists we are dealing with a Zip64 file.
@@ -60,40 +237,118 @@ where
                 Some(locator) => {
                     reader.seek(SeekFrom::Start(locator.relative_offset + SIGNATURE_LENGTH as u64)).await?;
                     let zip64_eocdr = Zip64EndOfCentralDirectoryRecord::from_reader(&mut reader).await?;
-                    (CombinedCentralDirectoryRecord::combine(eocdr, zip64_eocdr), true)
+
+                    // The extensible data sector follows the fixed fields (of which 44 bytes are counted within the
+                    // record's size) and can't extend beyond the locator, so clamp its size to guard against corrupt
+                    // records requesting a large allocation.
+                    let available =
+                        offset.saturating_sub(locator.relative_offset + (SIGNATURE_LENGTH + ZIP64_EOCDR_LENGTH) as u64);
+                    let extensible_size = zip64_eocdr.size_of_zip64_end_of_cd_record.saturating_sub(44).min(available);
+                    let extensible_data = io::read_bytes(&mut reader, extensible_size as usize).await?;
+
+                    let mut combined = CombinedCentralDirectoryRecord::combine(eocdr, zip64_eocdr);
+                    combined.total_disks = locator.total_number_of_disks;
+                    combined.zip64_extensible_data = extensible_data;
+                    (combined, true)
                 }
                 None => (CombinedCentralDirectoryRecord::from(&eocdr), false),
             }
         }
     };
 
-    // Outdated feature so unlikely to ever make it into this crate.
-    if eocdr.disk_number != eocdr.disk_number_start_of_cd
-        || eocdr.num_entries_in_directory != eocdr.num_entries_in_directory_on_disk
-    {
-        return Err(ZipError::FeatureNotSupported("Spanned/split files"));
-    }
-
-    // Find and parse the central directory.
-    reader.seek(SeekFrom::Start(eocdr.offset_of_start_of_directory)).await?;
-    let entries = crate::base::read::cd(reader, eocdr.num_entries_in_directory, zip64).await?;
-
-    Ok(ZipFile { entries, comment, zip64 })
+    Ok((eocdr, comment, zip64, comment_length_mismatch))
 }
 
 pub(crate) async fn cd<R>(mut reader: R, num_of_entries: u64, zip64: bool) -> Result<Vec<StoredZipEntry>>
 where
     R: AsyncRead + Unpin,
 {
+    let declared = num_of_entries;
     let num_of_entries = num_of_entries.try_into().map_err(|_| ZipError::TargetZip64NotSupported)?;
     let mut entries = Vec::with_capacity(num_of_entries);
 
     for _ in 0..num_of_entries {
-        let entry = cd_record(&mut reader, zip64).await?;
+        let entry = match cd_record(&mut reader, zip64).await {
+            Err(ZipError::UnexpectedHeaderError(_, CDH_SIGNATURE)) => {
+                return Err(ZipError::CentralDirectoryMismatch(declared));
+            }
+            result => result?,
+        };
         entries.push(entry);
     }
 
-    Ok(entries)
+    // The central directory should be directly followed by the EOCDR (or one of the structures which may precede it),
+    // otherwise it contains more records than declared or is followed by unexpected data.
+    let mut buffer = [0; SIGNATURE_LENGTH];
+    reader.read_exact(&mut buffer).await?;
+
+    match u32::from_le_bytes(buffer) {
+        EOCDR_SIGNATURE | ZIP64_EOCDR_SIGNATURE | DIGITAL_SIGNATURE_SIGNATURE => Ok(entries),
+        _ => Err(ZipError::CentralDirectoryMismatch(declared)),
+    }
+}
+
+/// Reads the remainder of the central directory & the end of central directory records sequentially, returning a
+/// summary of the archive.
+///
+/// This is intended for non-seekable sources, so assumes that the signature of the first record has already been
+/// consumed (ie. by [`lfh()`]) and is provided.
+pub(crate) async fn cd_trailer<R>(mut reader: R, mut signature: u32) -> Result<ArchiveSummary>
+where
+    R: AsyncRead + Unpin,
+{
+    let mut zip64_eocdr = None;
+    let mut total_disks = None;
+
+    loop {
+        match signature {
+            CDH_SIGNATURE => {
+                let header = CentralDirectoryRecord::from_reader(&mut reader).await?;
+                let trailing_size = header.file_name_length as u64
+                    + header.extra_field_length as u64
+                    + header.file_comment_length as u64;
+                futures_lite::io::copy((&mut reader).take(trailing_size), futures_lite::io::sink()).await?;
+            }
+            ZIP64_EOCDR_SIGNATURE => {
+                let record = Zip64EndOfCentralDirectoryRecord::from_reader(&mut reader).await?;
+
+                // Skip the extensible data sector, if any.
+                let extensible_size = record.size_of_zip64_end_of_cd_record.saturating_sub(44);
+                futures_lite::io::copy((&mut reader).take(extensible_size), futures_lite::io::sink()).await?;
+                zip64_eocdr = Some(record);
+            }
+            ZIP64_EOCDL_SIGNATURE => {
+                let mut buffer = [0; ZIP64_EOCDL_LENGTH as usize - SIGNATURE_LENGTH];
+                reader.read_exact(&mut buffer).await?;
+                total_disks = Some(u32::from_le_bytes(buffer[12..16].try_into().unwrap()));
+            }
+            EOCDR_SIGNATURE => break,
+            actual => return Err(ZipError::UnexpectedHeaderError(actual, EOCDR_SIGNATURE)),
+        }
+
+        let mut buffer = [0; SIGNATURE_LENGTH];
+        reader.read_exact(&mut buffer).await?;
+        signature = u32::from_le_bytes(buffer);
+    }
+
+    let eocdr = EndOfCentralDirectoryHeader::from_reader(&mut reader).await?;
+    let comment = io::read_string(&mut reader, eocdr.file_comm_length.into(), crate::StringEncoding::Utf8).await?;
+
+    let zip64 = zip64_eocdr.is_some();
+    let eocdr = match zip64_eocdr {
+        Some(zip64_eocdr) => CombinedCentralDirectoryRecord::combine(eocdr, zip64_eocdr),
+        None => CombinedCentralDirectoryRecord::from(&eocdr),
+    };
+
+    Ok(ArchiveSummary {
+        num_entries: eocdr.num_entries_in_directory,
+        central_directory_size: eocdr.directory_size,
+        central_directory_offset: eocdr.offset_of_start_of_directory,
+        comment,
+        zip64,
+        disk_number: eocdr.disk_number,
+        total_disks: total_disks.unwrap_or(eocdr.total_disks),
+    })
 }
 
 pub(crate) fn get_zip64_extra_field(extra_fields: &[ExtraField]) -> Option<&Zip64ExtendedInformationExtraField> {
@@ -136,6 +391,30 @@ fn get_combined_sizes(
     Ok((uncompressed_size, compressed_size))
 }
 
+/// Returns the entry's compression method, resolving the AES marker method (99) to the method within its AES extra
+/// field.
+fn detect_compression(compression: u16, extra_fields: &[ExtraField]) -> Result<Compression> {
+    let aes_compression = extra_fields.iter().find_map(|field| match field {
+        ExtraField::Aes(field) => Some(field.compression),
+        _ => None,
+    });
+
+    let compression = match (compression, aes_compression) {
+        (AES_COMPRESSION_METHOD, Some(compression)) => Compression::try_from(compression),
+        // Without a recognised AES field, the entry can still be listed but its data can't be read.
+        (AES_COMPRESSION_METHOD, None) => return Ok(Compression::Custom(AES_COMPRESSION_METHOD)),
+        _ => Compression::try_from(compression),
+    };
+
+    // Methods which aren't implemented by this crate are left to be read via a codec registered with the reader.
+    match compression {
+        Err(ZipError::CompressionNotSupported(method)) if !Compression::is_reserved(method) => {
+            Ok(Compression::Custom(method))
+        }
+        compression => compression,
+    }
+}
+
 pub(crate) async fn cd_record<R>(mut reader: R, _zip64: bool) -> Result<StoredZipEntry>
 where
     R: AsyncRead + Unpin,
@@ -146,9 +425,9 @@ where
     let header_size = (SIGNATURE_LENGTH + LFH_LENGTH) as u64;
     let trailing_size = header.file_name_length as u64 + header.extra_field_length as u64;
     let filename_basic = io::read_bytes(&mut reader, header.file_name_length.into()).await?;
-    let compression = Compression::try_from(header.compression)?;
     let extra_field = io::read_bytes(&mut reader, header.extra_field_length.into()).await?;
     let extra_fields = parse_extra_fields(extra_field, header.uncompressed_size, header.compressed_size)?;
+    let compression = detect_compression(header.compression, &extra_fields)?;
     let comment_basic = io::read_bytes(reader, header.file_comment_length.into()).await?;
 
     let zip64_extra_field = get_zip64_extra_field(&extra_fields);
@@ -170,6 +449,7 @@ where
     let entry = ZipEntry {
         filename,
         compression,
+        aes_marker: header.compression == AES_COMPRESSION_METHOD,
         #[cfg(any(
             feature = "deflate",
             feature = "bzip2",
@@ -179,8 +459,9 @@ where
             feature = "deflate64"
         ))]
         compression_level: async_compression::Level::Default,
-        attribute_compatibility: AttributeCompatibility::Unix,
-        // FIXME: Default to Unix for the moment
+        // FIXME: Default to Unix for unsupported hosts for the moment
+        attribute_compatibility: AttributeCompatibility::try_from(header.v_made_by >> 8)
+            .unwrap_or(AttributeCompatibility::Unix),
         crc32: header.crc,
         uncompressed_size,
         compressed_size,
@@ -190,12 +471,104 @@ where
         extra_fields,
         comment,
         data_descriptor: header.flags.data_descriptor,
+        encrypted: header.flags.encrypted,
+        strong_encryption: header.flags.strong_encryption,
+        version_needed: Some(header.v_needed),
+        utf8_flag: Some(header.flags.filename_unicode),
+        skip_crc: false,
+        explicit_text: false,
+        directory: false,
+        codec: None,
+        #[cfg(feature = "legacy")]
+        implode_options: header.flags.into(),
+        #[cfg(feature = "lzma")]
+        lzma_eos_marker: header.flags.compression_option_1,
     };
 
-    Ok(StoredZipEntry { entry, file_offset, header_size: header_size + trailing_size })
+    Ok(StoredZipEntry {
+        entry,
+        file_offset,
+        header_size: header_size + trailing_size,
+        version_made_by: header.v_made_by,
+        local_header_size: OnceLock::new(),
+    })
+}
+
+/// Reads the data descriptor directly following an entry's data and checks it against the entry.
+///
+/// The descriptor's sizes are expected to be 8 bytes long for entries whose central directory record holds ZIP64 sizes,
+/// and 4 bytes long otherwise. As some writers instead saturate 4 byte sizes for ZIP64 entries, that layout is also
+/// accepted for them. Returns whether the descriptor matched.
+pub(crate) async fn data_descriptor<R>(reader: R, entry: &ZipEntry) -> Result<bool>
+where
+    R: AsyncRead + Unpin,
+{
+    let mut buffer = Vec::with_capacity(SIGNATURE_LENGTH + 20);
+    reader.take(SIGNATURE_LENGTH as u64 + 20).read_to_end(&mut buffer).await?;
+
+    let zip64 = get_zip64_extra_field(&entry.extra_fields)
+        .is_some_and(|field| field.uncompressed_size.is_some() || field.compressed_size.is_some());
+
+    if let Ok((crc, compressed_size, uncompressed_size)) = read_data_descriptor(buffer.as_slice(), zip64).await {
+        if crc == entry.crc32
+            && compressed_size == entry.compressed_size
+            && uncompressed_size == entry.uncompressed_size
+        {
+            return Ok(true);
+        }
+    }
+
+    if zip64 {
+        if let Ok((crc, compressed_size, uncompressed_size)) = read_data_descriptor(buffer.as_slice(), false).await {
+            let saturated = NON_ZIP64_MAX_SIZE as u64;
+            return Ok(crc == entry.crc32 && compressed_size == saturated && uncompressed_size == saturated);
+        }
+    }
+
+    Ok(false)
 }
 
-pub(crate) async fn lfh<R>(mut reader: R) -> Result<Option<ZipEntry>>
+/// Reads a data descriptor, returning its CRC32 value, compressed size, and uncompressed size.
+///
+/// The descriptor's optional signature is skipped if present. Sizes are read as 8 bytes each for ZIP64 entries, and as
+/// 4 bytes each otherwise.
+pub(crate) async fn read_data_descriptor<R>(mut reader: R, zip64: bool) -> Result<(u32, u64, u64)>
+where
+    R: AsyncRead + Unpin,
+{
+    let mut buffer = [0; 4];
+    reader.read_exact(&mut buffer).await?;
+
+    if u32::from_le_bytes(buffer) == DATA_DESCRIPTOR_SIGNATURE {
+        reader.read_exact(&mut buffer).await?;
+    }
+    let crc = u32::from_le_bytes(buffer);
+
+    if zip64 {
+        let mut buffer = [0; 16];
+        reader.read_exact(&mut buffer).await?;
+        let compressed_size = u64::from_le_bytes(buffer[0..8].try_into().unwrap());
+        let uncompressed_size = u64::from_le_bytes(buffer[8..16].try_into().unwrap());
+        Ok((crc, compressed_size, uncompressed_size))
+    } else {
+        let mut buffer = [0; 8];
+        reader.read_exact(&mut buffer).await?;
+        let compressed_size = u32::from_le_bytes(buffer[0..4].try_into().unwrap());
+        let uncompressed_size = u32::from_le_bytes(buffer[4..8].try_into().unwrap());
+        Ok((crc, compressed_size as u64, uncompressed_size as u64))
+    }
+}
+
+/// The record which follows an entry's data (or the start of an archive) within a non-seekable source.
+pub(crate) enum NextRecord {
+    /// The local file header of the next entry.
+    Entry(Box<ZipEntry>),
+    /// The first record of the central directory, or the end of central directory record(s) if the archive has no
+    /// entries, whose signature has been consumed.
+    Trailer(u32),
+}
+
+pub(crate) async fn lfh<R>(mut reader: R) -> Result<NextRecord>
 where
     R: AsyncRead + Unpin,
 {
@@ -205,35 +578,51 @@ where
         u32::from_le_bytes(buffer)
     };
     match signature {
-        actual if actual == LFH_SIGNATURE => (),
-        actual if actual == CDH_SIGNATURE => return Ok(None),
+        LFH_SIGNATURE => (),
+        CDH_SIGNATURE | ZIP64_EOCDR_SIGNATURE | EOCDR_SIGNATURE => return Ok(NextRecord::Trailer(signature)),
         actual => return Err(ZipError::UnexpectedHeaderError(actual, LFH_SIGNATURE)),
     };
 
+    let entry = lfh_entry(&mut reader).await?;
+
+    // The stream reader has to decode each entry's data in order to advance past it, so we can't defer this error.
+    if entry.strong_encryption {
+        return Err(ZipError::StrongEncryptionUnsupported);
+    }
+    if entry.encrypted {
+        return Err(ZipError::FeatureNotSupported("encryption"));
+    }
+
+    if entry.data_descriptor && entry.compression == Compression::Stored {
+        return Err(ZipError::FeatureNotSupported(
+            "stream reading entries with data descriptors & Stored compression mode",
+        ));
+    }
+
+    Ok(NextRecord::Entry(Box::new(entry)))
+}
+
+/// Parses a local file header (excluding its signature) and the trailing filename & extra fields into an entry.
+pub(crate) async fn lfh_entry<R>(mut reader: R) -> Result<ZipEntry>
+where
+    R: AsyncRead + Unpin,
+{
     let header = LocalFileHeader::from_reader(&mut reader).await?;
     let filename_basic = io::read_bytes(&mut reader, header.file_name_length.into()).await?;
-    let compression = Compression::try_from(header.compression)?;
     let extra_field = io::read_bytes(&mut reader, header.extra_field_length.into()).await?;
     let extra_fields = parse_extra_fields(extra_field, header.uncompressed_size, header.compressed_size)?;
+    let compression = detect_compression(header.compression, &extra_fields)?;
 
     let zip64_extra_field = get_zip64_extra_field(&extra_fields);
     let (uncompressed_size, compressed_size) =
         get_combined_sizes(header.uncompressed_size, header.compressed_size, &zip64_extra_field)?;
 
-    if header.flags.data_descriptor && compression == Compression::Stored {
-        return Err(ZipError::FeatureNotSupported(
-            "stream reading entries with data descriptors & Stored compression mode",
-        ));
-    }
-    if header.flags.encrypted {
-        return Err(ZipError::FeatureNotSupported("encryption"));
-    }
-
     let filename = detect_filename(filename_basic, header.flags.filename_unicode, extra_fields.as_ref());
 
     let entry = ZipEntry {
         filename,
         compression,
+        aes_marker: header.compression == AES_COMPRESSION_METHOD,
         #[cfg(any(
             feature = "deflate",
             feature = "bzip2",
@@ -254,14 +643,38 @@ where
         extra_fields,
         comment: String::new().into(),
         data_descriptor: header.flags.data_descriptor,
+        encrypted: header.flags.encrypted,
+        strong_encryption: header.flags.strong_encryption,
+        version_needed: Some(header.version),
+        utf8_flag: Some(header.flags.filename_unicode),
+        skip_crc: false,
+        explicit_text: false,
+        directory: false,
+        codec: None,
+        #[cfg(feature = "legacy")]
+        implode_options: header.flags.into(),
+        #[cfg(feature = "lzma")]
+        lzma_eos_marker: header.flags.compression_option_1,
     };
 
-    Ok(Some(entry))
+    Ok(entry)
+}
+
+/// Returns the string with its leading UTF-8 byte order mark removed, if it has one and is otherwise valid UTF-8.
+///
+/// Some Windows tools prefix UTF-8 filenames & comments with a byte order mark, which would otherwise be kept within
+/// the decoded string. Callers keep the original bytes as the string's alternative so it can be rewritten faithfully.
+fn strip_utf8_bom(basic: &[u8]) -> Option<String> {
+    let stripped = basic.strip_prefix(&[0xEF, 0xBB, 0xBF])?;
+    std::str::from_utf8(stripped).ok().map(str::to_owned)
 }
 
 fn detect_comment(basic: Vec<u8>, basic_is_utf8: bool, extra_fields: &[ExtraField]) -> ZipString {
     if basic_is_utf8 {
-        ZipString::new(basic, StringEncoding::Utf8)
+        match strip_utf8_bom(&basic) {
+            Some(stripped) => ZipString::new_with_alternative(stripped, basic),
+            None => ZipString::new(basic, StringEncoding::Utf8),
+        }
     } else {
         let unicode_extra = extra_fields.iter().find_map(|field| match field {
             ExtraField::InfoZipUnicodeComment(InfoZipUnicodeCommentExtraField::V1 { crc32, unicode }) => {
@@ -275,6 +688,8 @@ fn detect_comment(basic: Vec<u8>, basic_is_utf8: bool, extra_fields: &[ExtraFiel
         });
         if let Some(Ok(s)) = unicode_extra {
             ZipString::new_with_alternative(s, basic)
+        } else if let Some(stripped) = strip_utf8_bom(&basic) {
+            ZipString::new_with_alternative(stripped, basic)
         } else {
             // Do not treat as UTF-8 if UTF-8 flags are not set,
             // some string in MBCS may be valid UTF-8 in form, but they are not in truth.
@@ -289,9 +704,12 @@ fn detect_comment(basic: Vec<u8>, basic_is_utf8: bool, extra_fields: &[ExtraFiel
     }
 }
 
-fn detect_filename(basic: Vec<u8>, basic_is_utf8: bool, extra_fields: &[ExtraField]) -> ZipString {
+pub(crate) fn detect_filename(basic: Vec<u8>, basic_is_utf8: bool, extra_fields: &[ExtraField]) -> ZipString {
     if basic_is_utf8 {
-        ZipString::new(basic, StringEncoding::Utf8)
+        match strip_utf8_bom(&basic) {
+            Some(stripped) => ZipString::new_with_alternative(stripped, basic),
+            None => ZipString::new(basic, StringEncoding::Utf8),
+        }
     } else {
         let unicode_extra = extra_fields.iter().find_map(|field| match field {
             ExtraField::InfoZipUnicodePath(InfoZipUnicodePathExtraField::V1 { crc32, unicode }) => {
@@ -305,6 +723,8 @@ fn detect_filename(basic: Vec<u8>, basic_is_utf8: bool, extra_fields: &[ExtraFie
         });
         if let Some(Ok(s)) = unicode_extra {
             ZipString::new_with_alternative(s, basic)
+        } else if let Some(stripped) = strip_utf8_bom(&basic) {
+            ZipString::new_with_alternative(stripped, basic)
         } else {
             // Do not treat as UTF-8 if UTF-8 flags are not set,
             // some string in MBCS may be valid UTF-8 in form, but they are not in truth.
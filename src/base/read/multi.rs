@@ -0,0 +1,89 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+//! A ZIP reader which sequentially reads the entries of many archives, each over a non-seekable source.
+//!
+//! This composes the [stream reader](crate::base::read::stream) so that batch processing many archives doesn't require
+//! each caller to write the outer loop over sources. Each entry is passed to a closure alongside the index of the
+//! archive it belongs to, and any of its data left unread by the closure is skipped.
+//!
+//! # Example
+//! ```no_run
+//! # use futures_lite::io::Cursor;
+//! # use async_zip::error::Result;
+//! # use async_zip::base::read::multi::MultiArchiveReader;
+//! #
+//! # async fn run() -> Result<()> {
+//! let sources = vec![Cursor::new(Vec::new()), Cursor::new(Vec::new())];
+//!
+//! // Print the name of every file across all of the archives.
+//! MultiArchiveReader::new(sources)
+//!     .for_each_entry(async |archive, reader| {
+//!         println!("{archive}: {}", reader.entry().filename().as_str()?);
+//!         Ok(())
+//!     })
+//!     .await?;
+//! #
+//! #     Ok(())
+//! # }
+//! ```
+
+use crate::base::read::io::entry::{WithEntry, ZipEntryReader};
+use crate::base::read::stream::ZipFileReader;
+use crate::codec::{Codec, CodecRegistry};
+use crate::error::Result;
+
+use std::sync::Arc;
+
+use futures_lite::io::AsyncBufRead;
+
+/// A ZIP reader which sequentially reads the entries of many archives, each over a non-seekable source.
+///
+/// See the [module-level docs](.) for more information.
+#[derive(Clone, Debug)]
+pub struct MultiArchiveReader<I> {
+    sources: I,
+    codecs: CodecRegistry,
+}
+
+impl<I, R> MultiArchiveReader<I>
+where
+    I: Iterator<Item = R>,
+    R: AsyncBufRead + Unpin,
+{
+    /// Constructs a new reader over the provided sources, which are read in order.
+    pub fn new<S: IntoIterator<IntoIter = I>>(sources: S) -> Self {
+        Self { sources: sources.into_iter(), codecs: CodecRegistry::default() }
+    }
+
+    /// Registers a codec for reading the entries of every archive which use the provided custom compression method id,
+    /// replacing any codec previously registered for it.
+    ///
+    /// Returns [`ZipError::CompressionMethodReserved`](crate::error::ZipError::CompressionMethodReserved) if the method
+    /// id belongs to a method implemented by this crate. See the [`codec`](crate::codec) module for more information.
+    pub fn register_codec(&mut self, method: u16, codec: Arc<dyn Codec>) -> Result<()> {
+        self.codecs.register(method, codec)
+    }
+
+    /// Passes each entry of every archive to the provided closure, alongside the index of the archive it belongs to.
+    ///
+    /// Archives are read one after another, and each archive's source isn't pulled from the iterator until the
+    /// previous archive's entries have all been read. Iteration stops at the first error, whether returned by the
+    /// closure or encountered whilst reading an archive.
+    pub async fn for_each_entry<'a, F>(self, mut f: F) -> Result<()>
+    where
+        R: 'a,
+        F: AsyncFnMut(usize, &mut ZipEntryReader<'a, R, WithEntry<'a>>) -> Result<()>,
+    {
+        for (archive, source) in self.sources.enumerate() {
+            let mut zip = ZipFileReader::new_with_codecs(source, self.codecs.clone());
+
+            while let Some(mut entry) = zip.next_with_entry().await? {
+                f(archive, entry.reader_mut()).await?;
+                zip = entry.skip().await?;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,321 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+//! A ZIP reader which fetches byte ranges on demand, rather than acting over a seekable stream.
+//!
+//! This suits backends which serve arbitrary byte ranges of a file cheaply but have no notion of a seekable stream,
+//! such as object stores (eg. S3 or GCS) via HTTP range requests. Only the end of the archive (containing the end of
+//! central directory record and the central directory) is fetched upfront, and the data of each entry is then only
+//! fetched as it's read, in chunks of at most [`ZipFileReader::chunk_size()`] bytes.
+//!
+//! ### Example
+//! ```no_run
+//! # use async_zip::base::read::range::{RangeFetcher, ZipFileReader};
+//! # use async_zip::error::Result;
+//! # use futures_lite::io::AsyncReadExt;
+//! #
+//! struct Fetcher(Vec<u8>);
+//!
+//! impl RangeFetcher for Fetcher {
+//!     async fn fetch(&self, offset: u64, length: u64) -> Result<Vec<u8>> {
+//!         // Issue a range request for `bytes={offset}-{offset + length - 1}` here.
+//!         Ok(self.0[offset as usize..(offset + length) as usize].to_vec())
+//!     }
+//! }
+//!
+//! async fn run(fetcher: Fetcher, size: u64) -> Result<()> {
+//!     let reader = ZipFileReader::new(fetcher, size).await?;
+//!
+//!     let mut data = Vec::new();
+//!     reader.reader_with_entry(0).await?.read_to_end_checked(&mut data).await?;
+//!     Ok(())
+//! }
+//! ```
+
+use crate::base::read::io::entry::{WithEntry, WithoutEntry, ZipEntryReader};
+use crate::codec::{Codec, CodecRegistry};
+use crate::entry::StoredZipEntry;
+use crate::error::{Result, ZipError};
+use crate::file::ZipFile;
+use crate::spec::consts::{EOCDR_LENGTH, LFH_LENGTH, SIGNATURE_LENGTH, ZIP64_EOCDL_LENGTH, ZIP64_EOCDR_LENGTH};
+
+use std::future::Future;
+use std::io::{Error, ErrorKind, SeekFrom};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{ready, Context, Poll};
+
+use futures_lite::io::{AsyncBufRead, AsyncRead, AsyncSeek, Cursor};
+
+/// The number of bytes fetched from the end of the archive when constructing a reader, which is enough to contain the
+/// EOCDR with the largest possible comment, as well as the ZIP64 EOCDR & EOCDL.
+const TAIL_LENGTH: u64 = (SIGNATURE_LENGTH + EOCDR_LENGTH) as u64
+    + u16::MAX as u64
+    + (SIGNATURE_LENGTH + ZIP64_EOCDR_LENGTH) as u64
+    + ZIP64_EOCDL_LENGTH
+    + SIGNATURE_LENGTH as u64;
+
+/// The default maximum number of bytes fetched per request whilst reading an entry's data.
+pub const DEFAULT_CHUNK_SIZE: u64 = 1024 * 1024;
+
+/// A source of arbitrary byte ranges of a ZIP file.
+pub trait RangeFetcher {
+    /// Fetches `length` bytes of the ZIP file, starting at `offset`.
+    ///
+    /// Implementations should return exactly the bytes requested, as a shorter range will be treated as truncated
+    /// data.
+    fn fetch(&self, offset: u64, length: u64) -> impl Future<Output = Result<Vec<u8>>> + Send;
+}
+
+/// A ZIP reader which fetches byte ranges on demand via a [`RangeFetcher`] implementer.
+#[derive(Debug)]
+pub struct ZipFileReader<F> {
+    fetcher: F,
+    file: ZipFile,
+    codecs: CodecRegistry,
+    chunk_size: u64,
+}
+
+impl<F> ZipFileReader<F>
+where
+    F: RangeFetcher,
+{
+    /// Constructs a new ZIP reader from a fetcher and the total size of the ZIP file.
+    ///
+    /// The end of the ZIP file is fetched first, followed by any part of the central directory which it didn't contain.
+    pub async fn new(fetcher: F, size: u64) -> Result<ZipFileReader<F>> {
+        ZipFileReader::from_tail(fetcher, Vec::new(), size).await
+    }
+
+    /// Constructs a new ZIP reader from a fetcher and a pre-fetched buffer containing the end of the ZIP file.
+    ///
+    /// `offset` is the offset within the ZIP file at which the buffer starts. If the buffer doesn't contain enough of
+    /// the end of the ZIP file to locate the end of central directory records, more will be fetched. Likewise, any
+    /// part of the central directory which the buffer doesn't contain will be fetched.
+    pub async fn from_tail(fetcher: F, mut tail: Vec<u8>, mut offset: u64) -> Result<ZipFileReader<F>> {
+        let summary = match crate::base::read::read_summary(OffsetCursor::new(&tail, offset)).await {
+            Ok(summary) => summary,
+            Err(_) if (tail.len() as u64) < TAIL_LENGTH && offset > 0 => {
+                let start = offset.saturating_sub(TAIL_LENGTH - tail.len() as u64);
+                let mut data = fetch_exact(&fetcher, start, offset - start).await?;
+
+                data.append(&mut tail);
+                (tail, offset) = (data, start);
+                crate::base::read::read_summary(OffsetCursor::new(&tail, offset)).await?
+            }
+            Err(err) => return Err(err),
+        };
+        let cd_offset = summary.central_directory_offset();
+
+        let file = if cd_offset < offset {
+            let mut data = fetch_exact(&fetcher, cd_offset, offset - cd_offset).await?;
+            data.extend_from_slice(&tail);
+            crate::base::read::file(OffsetCursor::new(&data, cd_offset)).await?
+        } else {
+            crate::base::read::file(OffsetCursor::new(&tail, offset)).await?
+        };
+
+        Ok(ZipFileReader { fetcher, file, codecs: CodecRegistry::default(), chunk_size: DEFAULT_CHUNK_SIZE })
+    }
+
+    /// Returns this ZIP file's information.
+    pub fn file(&self) -> &ZipFile {
+        &self.file
+    }
+
+    /// Registers a codec for reading entries which use the provided custom compression method id, replacing any codec
+    /// previously registered for it.
+    ///
+    /// Returns [`ZipError::CompressionMethodReserved`] if the method id belongs to a method implemented by this crate.
+    /// See the [`codec`](crate::codec) module for more information.
+    pub fn register_codec(&mut self, method: u16, codec: Arc<dyn Codec>) -> Result<()> {
+        self.codecs.register(method, codec)
+    }
+
+    /// Returns the maximum number of bytes fetched per request whilst reading an entry's data.
+    pub fn chunk_size(&self) -> u64 {
+        self.chunk_size
+    }
+
+    /// Sets the maximum number of bytes fetched per request whilst reading an entry's data, which defaults to
+    /// [`DEFAULT_CHUNK_SIZE`].
+    ///
+    /// This bounds how much of an entry's data is held in memory at once. A chunk size of zero is treated as one.
+    pub fn set_chunk_size(&mut self, chunk_size: u64) {
+        self.chunk_size = std::cmp::max(chunk_size, 1);
+    }
+
+    /// Returns a reference to the inner fetcher.
+    pub fn inner(&self) -> &F {
+        &self.fetcher
+    }
+
+    /// Consumes this reader and returns the inner fetcher.
+    pub fn into_inner(self) -> F {
+        self.fetcher
+    }
+
+    /// Returns a new entry reader if the provided index is valid.
+    ///
+    /// The entry's local file header is fetched before the reader is returned, and its compressed data is then fetched
+    /// in chunks as it's read.
+    pub async fn reader_without_entry(
+        &self,
+        index: usize,
+    ) -> Result<ZipEntryReader<'_, RangeReader<'_, F>, WithoutEntry>> {
+        let stored_entry = self.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
+        let reader = self.data_reader(stored_entry).await?;
+
+        let size = stored_entry.entry.compressed_size();
+        ZipEntryReader::new_with_owned(reader, &stored_entry.entry, size, &self.codecs)
+    }
+
+    /// Returns a new entry reader if the provided index is valid.
+    ///
+    /// The entry's local file header is fetched before the reader is returned, and its compressed data is then fetched
+    /// in chunks as it's read.
+    pub async fn reader_with_entry(
+        &self,
+        index: usize,
+    ) -> Result<ZipEntryReader<'_, RangeReader<'_, F>, WithEntry<'_>>> {
+        let stored_entry = self.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
+        let reader = self.data_reader(stored_entry).await?;
+
+        let size = stored_entry.entry.compressed_size();
+        let reader = ZipEntryReader::new_with_owned(reader, &stored_entry.entry, size, &self.codecs)?;
+
+        Ok(reader.into_with_entry(stored_entry))
+    }
+
+    /// Fetches the entry's local file header to locate its data, and returns a reader which fetches the data itself.
+    async fn data_reader(&self, stored_entry: &StoredZipEntry) -> Result<RangeReader<'_, F>> {
+        let header_length = (SIGNATURE_LENGTH + LFH_LENGTH) as u64;
+        let header = fetch_exact(&self.fetcher, stored_entry.file_offset, header_length).await?;
+
+        let mut cursor = OffsetCursor::new(&header, stored_entry.file_offset);
+        stored_entry.seek_to_data_offset(&mut cursor).await?;
+
+        Ok(RangeReader {
+            fetcher: &self.fetcher,
+            offset: cursor.position(),
+            remaining: stored_entry.entry.compressed_size(),
+            chunk_size: self.chunk_size,
+            buffer: Cursor::new(Vec::new()),
+            pending: None,
+        })
+    }
+}
+
+/// A reader over an entry's compressed data, which fetches the data in chunks via a [`RangeFetcher`] as it's read.
+///
+/// At most one chunk is held in memory at a time, and the next chunk is only fetched once the current one has been
+/// read in full.
+pub struct RangeReader<'a, F> {
+    fetcher: &'a F,
+    offset: u64,
+    remaining: u64,
+    chunk_size: u64,
+    buffer: Cursor<Vec<u8>>,
+    pending: Option<(u64, PendingFetch<'a>)>,
+}
+
+/// A fetch of the next chunk which is in progress, whose type is erased as it can't otherwise be named.
+type PendingFetch<'a> = Pin<Box<dyn Future<Output = Result<Vec<u8>>> + Send + 'a>>;
+
+impl<F: RangeFetcher> AsyncBufRead for RangeReader<'_, F> {
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<&[u8]>> {
+        let this = self.get_mut();
+
+        if this.buffer.position() == this.buffer.get_ref().len() as u64 && this.remaining > 0 {
+            let (length, pending) = this.pending.get_or_insert_with(|| {
+                let length = std::cmp::min(this.chunk_size, this.remaining);
+                (length, Box::pin(this.fetcher.fetch(this.offset, length)))
+            });
+
+            let mut data = match ready!(pending.as_mut().poll(cx)) {
+                Ok(data) if data.len() as u64 >= *length => data,
+                Ok(_) => return Poll::Ready(Err(ErrorKind::UnexpectedEof.into())),
+                Err(err) => return Poll::Ready(Err(Error::other(err))),
+            };
+            data.truncate(*length as usize);
+
+            this.offset += *length;
+            this.remaining -= *length;
+            this.buffer = Cursor::new(data);
+            this.pending = None;
+        }
+
+        Pin::new(&mut this.buffer).poll_fill_buf(cx)
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        Pin::new(&mut self.get_mut().buffer).consume(amt)
+    }
+}
+
+impl<F: RangeFetcher> AsyncRead for RangeReader<'_, F> {
+    fn poll_read(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<std::io::Result<usize>> {
+        let data = ready!(self.as_mut().poll_fill_buf(cx))?;
+        let length = std::cmp::min(data.len(), buf.len());
+        buf[..length].copy_from_slice(&data[..length]);
+
+        self.consume(length);
+        Poll::Ready(Ok(length))
+    }
+}
+
+/// Fetches the provided range, returning an error if fewer bytes than requested were returned.
+async fn fetch_exact<F: RangeFetcher>(fetcher: &F, offset: u64, length: u64) -> Result<Vec<u8>> {
+    let mut data = fetcher.fetch(offset, length).await?;
+
+    if (data.len() as u64) < length {
+        return Err(Error::from(ErrorKind::UnexpectedEof).into());
+    }
+
+    data.truncate(length as usize);
+    Ok(data)
+}
+
+/// A cursor over a range of bytes fetched from the ZIP file, which maps offsets within the ZIP file to the range.
+///
+/// The fetched range is assumed to extend to the end of the ZIP file, so seeking relative to the end is supported.
+struct OffsetCursor<'a> {
+    cursor: Cursor<&'a [u8]>,
+    offset: u64,
+}
+
+impl<'a> OffsetCursor<'a> {
+    fn new(data: &'a [u8], offset: u64) -> Self {
+        Self { cursor: Cursor::new(data), offset }
+    }
+
+    /// Returns the current offset within the ZIP file.
+    fn position(&self) -> u64 {
+        self.offset + self.cursor.position()
+    }
+}
+
+impl AsyncRead for OffsetCursor<'_> {
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<std::io::Result<usize>> {
+        Pin::new(&mut self.get_mut().cursor).poll_read(cx, buf)
+    }
+}
+
+impl AsyncSeek for OffsetCursor<'_> {
+    fn poll_seek(self: Pin<&mut Self>, cx: &mut Context<'_>, pos: SeekFrom) -> Poll<std::io::Result<u64>> {
+        let this = self.get_mut();
+
+        let pos = match pos {
+            SeekFrom::Start(target) => match target.checked_sub(this.offset) {
+                Some(relative) => SeekFrom::Start(relative),
+                None => {
+                    let message = "seek to a position before the fetched range";
+                    return Poll::Ready(Err(Error::new(ErrorKind::InvalidInput, message)));
+                }
+            },
+            pos => pos,
+        };
+
+        Pin::new(&mut this.cursor).poll_seek(cx, pos).map_ok(|position| this.offset + position)
+    }
+}
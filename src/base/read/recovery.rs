@@ -0,0 +1,145 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+//! Best-effort recovery of entries from archives with a missing or corrupt end of central directory record.
+//!
+//! Rather than relying on the central directory, the source is scanned from the start for local file header
+//! signatures. Each candidate header is parsed and, if its data can be fully located within the source, recorded as an
+//! entry. For entries which use a data descriptor, the descriptor is located by scanning for its signature and
+//! checking that its compressed size matches the distance travelled.
+//!
+//! As local file headers don't store file comments or external attributes, those will be absent from recovered
+//! entries. Any entry whose data is truncated is skipped.
+
+use crate::entry::{StoredZipEntry, ZipEntry};
+use crate::error::Result;
+use crate::file::ZipFile;
+use crate::spec::consts::{DATA_DESCRIPTOR_SIGNATURE, LFH_SIGNATURE, SIGNATURE_LENGTH};
+use crate::spec::Compression;
+
+use futures_lite::io::{AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt, SeekFrom};
+
+use std::sync::OnceLock;
+
+/// Scans the source for local file headers, returning a [`ZipFile`] containing all entries which could be recovered.
+pub(crate) async fn file<R>(mut reader: R) -> Result<ZipFile>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    let length = reader.seek(SeekFrom::End(0)).await?;
+    let mut entries = Vec::new();
+    let mut position = 0;
+
+    while let Some(offset) = super::io::locator::forward_search(&mut reader, position, LFH_SIGNATURE).await? {
+        match entry(&mut reader, offset, length).await {
+            Ok(Some((entry, end))) => {
+                entries.push(entry);
+                position = end;
+            }
+            // The signature may have been part of another entry's data, so continue scanning after it.
+            _ => position = offset + 1,
+        }
+    }
+
+    let zip64 = entries.iter().any(|entry| entry.is_zip64());
+    Ok(ZipFile {
+        entries,
+        comment: String::new().into(),
+        zip64,
+        recovered: true,
+        comment_length_mismatch: false,
+        zip64_extensible_data: Vec::new(),
+        disk_number: 0,
+        total_disks: 1,
+        #[cfg(feature = "indexmap")]
+        entry_map: std::sync::OnceLock::new(),
+    })
+}
+
+/// Attempts to parse an entry from a local file header at the provided offset.
+///
+/// Returns the entry and the offset directly after its data (and data descriptor, if present), or None if the data
+/// couldn't be located within the source.
+async fn entry<R>(mut reader: R, offset: u64, length: u64) -> Result<Option<(StoredZipEntry, u64)>>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    reader.seek(SeekFrom::Start(offset + SIGNATURE_LENGTH as u64)).await?;
+    let mut entry = crate::base::read::lfh_entry(&mut reader).await?;
+
+    let data_offset = reader.seek(SeekFrom::Current(0)).await?;
+    let header_size = data_offset - offset;
+
+    let end = if entry.data_descriptor {
+        match data_descriptor(&mut reader, &mut entry, data_offset).await? {
+            Some(end) => end,
+            None => return Ok(None),
+        }
+    } else {
+        data_offset + entry.compressed_size
+    };
+
+    if end > length {
+        return Ok(None);
+    }
+
+    let version_made_by = crate::spec::version::as_made_by(entry.attribute_compatibility);
+    Ok(Some((
+        StoredZipEntry {
+            entry,
+            file_offset: offset,
+            header_size,
+            version_made_by,
+            local_header_size: OnceLock::from(header_size),
+        },
+        end,
+    )))
+}
+
+/// Locates an entry's data descriptor and updates the entry with its CRC32 value and sizes.
+///
+/// The standard and ZIP64 descriptor layouts are accepted, as are descriptors with saturated 4 byte sizes. Returns the
+/// offset directly after the descriptor.
+async fn data_descriptor<R>(mut reader: R, entry: &mut ZipEntry, data_offset: u64) -> Result<Option<u64>>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    let mut position = data_offset;
+
+    while let Some(offset) =
+        super::io::locator::forward_search(&mut reader, position, DATA_DESCRIPTOR_SIGNATURE).await?
+    {
+        reader.seek(SeekFrom::Start(offset + SIGNATURE_LENGTH as u64)).await?;
+
+        let mut buffer = Vec::with_capacity(20);
+        (&mut reader).take(20).read_to_end(&mut buffer).await?;
+        let compressed_size = offset - data_offset;
+
+        if buffer.len() >= 12 && u32::from_le_bytes(buffer[4..8].try_into().unwrap()) as u64 == compressed_size {
+            entry.crc32 = u32::from_le_bytes(buffer[0..4].try_into().unwrap());
+            entry.compressed_size = compressed_size;
+            entry.uncompressed_size = u32::from_le_bytes(buffer[8..12].try_into().unwrap()) as u64;
+            return Ok(Some(offset + SIGNATURE_LENGTH as u64 + 12));
+        }
+        // Streamed ZIP64 entries may instead have saturated sizes, in which case the uncompressed size can only be
+        // inferred for stored data.
+        if buffer.len() >= 12 && buffer[4..12].iter().all(|byte| *byte == 0xFF) {
+            entry.crc32 = u32::from_le_bytes(buffer[0..4].try_into().unwrap());
+            entry.compressed_size = compressed_size;
+            if entry.compression == Compression::Stored {
+                entry.uncompressed_size = compressed_size;
+            }
+            return Ok(Some(offset + SIGNATURE_LENGTH as u64 + 12));
+        }
+        if buffer.len() >= 20 && u64::from_le_bytes(buffer[4..12].try_into().unwrap()) == compressed_size {
+            entry.crc32 = u32::from_le_bytes(buffer[0..4].try_into().unwrap());
+            entry.compressed_size = compressed_size;
+            entry.uncompressed_size = u64::from_le_bytes(buffer[12..20].try_into().unwrap());
+            return Ok(Some(offset + SIGNATURE_LENGTH as u64 + 20));
+        }
+
+        position = offset + 1;
+    }
+
+    Ok(None)
+}
@@ -27,24 +27,37 @@
 //! ```
 
 use crate::base::read::io::entry::ZipEntryReader;
+use crate::codec::{Codec, CodecRegistry};
+use crate::entry::{StoredZipEntry, ZipEntry};
 use crate::error::{Result, ZipError};
 use crate::file::ZipFile;
+use crate::spec::Compression;
 
 #[cfg(feature = "tokio")]
 use crate::tokio::read::seek::ZipFileReader as TokioZipFileReader;
 
-use futures_lite::io::{AsyncBufRead, AsyncSeek};
+use futures_lite::io::{AsyncBufRead, AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt, SeekFrom};
+use std::sync::Arc;
 
 #[cfg(feature = "tokio")]
 use tokio_util::compat::{Compat, TokioAsyncReadCompatExt};
 
+#[cfg(feature = "sha2")]
+use std::pin::Pin;
+#[cfg(feature = "sha2")]
+use std::task::{Context, Poll};
+
 use super::io::entry::{WithEntry, WithoutEntry};
 
+pub use crate::base::read::io::base_offset::BaseOffsetReader;
+pub use crate::base::read::io::buffered::BufferedSeekReader;
+
 /// A ZIP reader which acts over a seekable source.
-#[derive(Clone)]
+#[derive(Clone, Debug)]
 pub struct ZipFileReader<R> {
     reader: R,
     file: ZipFile,
+    codecs: CodecRegistry,
 }
 
 impl<R> ZipFileReader<R>
@@ -57,11 +70,68 @@ where
         Ok(ZipFileReader::from_raw_parts(reader, file))
     }
 
+    /// Constructs a new ZIP reader from a seekable source, returning an error before reading the central directory if
+    /// the estimated memory needed to hold it exceeds the provided budget (in bytes).
+    ///
+    /// See [`estimate_cd_memory()`] for how this is estimated.
+    ///
+    /// [`estimate_cd_memory()`]: crate::base::read::estimate_cd_memory
+    pub async fn new_with_memory_budget(mut reader: R, budget: usize) -> Result<ZipFileReader<R>> {
+        let file = crate::base::read::file_with_budget(&mut reader, Some(budget)).await?;
+        Ok(ZipFileReader::from_raw_parts(reader, file))
+    }
+
+    /// Constructs a new ZIP reader from a seekable source, falling back to recovery if the central directory can't be
+    /// read.
+    ///
+    /// In recovery mode, the source is scanned from the start for local file headers to reconstruct a best-effort list
+    /// of entries. Whether this occurred can be checked via [`ZipFile::recovered()`].
+    pub async fn new_with_recovery(mut reader: R) -> Result<ZipFileReader<R>> {
+        let file = match crate::base::read::file(&mut reader).await {
+            Ok(file) => file,
+            Err(_) => crate::base::read::recovery::file(&mut reader).await?,
+        };
+        Ok(ZipFileReader::from_raw_parts(reader, file))
+    }
+
+    /// Constructs a lazy iterator over the entries of a seekable source, without reading the central directory up
+    /// front.
+    ///
+    /// Only the end of central directory record is read by this function. Each central directory record is then
+    /// parsed on demand via [`LazyEntries::next_entry()`], trading repeated small reads for a lower peak memory usage.
+    /// This is useful when only a few entries are needed from an archive with a very large central directory.
+    pub async fn entries_lazy(mut reader: R) -> Result<LazyEntries<R>> {
+        let (eocdr, _, zip64, _) = crate::base::read::eocdr(&mut reader).await?;
+
+        if eocdr.disk_number != eocdr.disk_number_start_of_cd
+            || eocdr.num_entries_in_directory != eocdr.num_entries_in_directory_on_disk
+        {
+            return Err(ZipError::FeatureNotSupported("Spanned/split files"));
+        }
+
+        Ok(LazyEntries {
+            reader,
+            offset: eocdr.offset_of_start_of_directory,
+            remaining: eocdr.num_entries_in_directory,
+            zip64,
+            codecs: CodecRegistry::default(),
+        })
+    }
+
     /// Constructs a ZIP reader from a seekable source and ZIP file information derived from that source.
     ///
     /// Providing a [`ZipFile`] that wasn't derived from that source may lead to inaccurate parsing.
     pub fn from_raw_parts(reader: R, file: ZipFile) -> ZipFileReader<R> {
-        ZipFileReader { reader, file }
+        ZipFileReader { reader, file, codecs: CodecRegistry::default() }
+    }
+
+    /// Registers a codec for reading entries which use the provided custom compression method id, replacing any codec
+    /// previously registered for it.
+    ///
+    /// Returns [`ZipError::CompressionMethodReserved`] if the method id belongs to a method implemented by this crate.
+    /// See the [`codec`](crate::codec) module for more information.
+    pub fn register_codec(&mut self, method: u16, codec: Arc<dyn Codec>) -> Result<()> {
+        self.codecs.register(method, codec)
     }
 
     /// Returns this ZIP file's information.
@@ -86,11 +156,8 @@ where
         let stored_entry = self.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
         stored_entry.seek_to_data_offset(&mut self.reader).await?;
 
-        Ok(ZipEntryReader::new_with_borrow(
-            &mut self.reader,
-            stored_entry.entry.compression(),
-            stored_entry.entry.compressed_size(),
-        ))
+        let size = stored_entry.entry.compressed_size();
+        ZipEntryReader::new_with_borrow(&mut self.reader, &stored_entry.entry, size, &self.codecs)
     }
 
     /// Returns a new entry reader if the provided index is valid.
@@ -101,13 +168,128 @@ where
 
         let reader = ZipEntryReader::new_with_borrow(
             &mut self.reader,
-            stored_entry.entry.compression(),
+            &stored_entry.entry,
             stored_entry.entry.compressed_size(),
-        );
+            &self.codecs,
+        )?;
 
         Ok(reader.into_with_entry(stored_entry))
     }
 
+    /// Returns a new entry reader along with a copy of the entry's metadata if the provided index is valid.
+    ///
+    /// This is useful when the entry's metadata (eg. its filename or size) is needed alongside the reader, such as for
+    /// logging, as the returned reader holds a borrow of this ZIP reader.
+    pub async fn reader_with_entry_meta(
+        &mut self,
+        index: usize,
+    ) -> Result<(ZipEntry, ZipEntryReader<'_, R, WithEntry<'_>>)> {
+        let entry = self.file().entries().get(index).ok_or(ZipError::EntryIndexOutOfBounds)?.entry.clone();
+        Ok((entry, self.reader_with_entry(index).await?))
+    }
+
+    /// Verifies the CRC32 value of the entry at the provided index by decompressing its data, if the index is valid.
+    ///
+    /// The decompressed data is streamed through a fixed-size buffer and discarded rather than being held in memory.
+    /// Returns [`ZipError::CRC32CheckError`] if the computed value doesn't match the central directory record.
+    pub async fn verify_entry(&mut self, index: usize) -> Result<()> {
+        self.reader_with_entry(index).await?.copy_to_checked(futures_lite::io::sink()).await?;
+        Ok(())
+    }
+
+    /// Reads the decompressed data of the entry at the provided index until EOF is reached, regardless of the sizes
+    /// declared within its headers, if the index is valid.
+    ///
+    /// Some streaming producers leave an entry's sizes as zero within both its local file header and central directory
+    /// record, even though its data is intact. If an entry's compressed size is zero, its data is instead decompressed
+    /// until the end of the compressed stream. This isn't possible for Stored entries as their data has no end marker,
+    /// so they're always read as per their declared size. The CRC32 value of the data is verified once read.
+    pub async fn read_entry_to_vec(&mut self, index: usize) -> Result<Vec<u8>> {
+        let stored_entry = self.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
+        stored_entry.seek_to_data_offset(&mut self.reader).await?;
+
+        let entry = &stored_entry.entry;
+        let size = match entry.compressed_size() == 0 && entry.compression() != Compression::Stored {
+            true => u64::MAX,
+            false => entry.compressed_size(),
+        };
+
+        let mut data = Vec::with_capacity(entry.uncompressed_size().min(1 << 20) as usize);
+        let reader = ZipEntryReader::new_with_borrow(&mut self.reader, entry, size, &self.codecs)?;
+        reader.into_with_entry(stored_entry).read_to_end_checked(&mut data).await?;
+
+        Ok(data)
+    }
+
+    /// Reads up to the first `length` bytes of the decompressed data of the entry at the provided index, if the index
+    /// is valid.
+    ///
+    /// Only as much of the entry as is needed is decompressed, which is useful for sniffing the content type of large
+    /// entries before deciding whether to extract them. Fewer bytes are returned if the entry is shorter. As the entry
+    /// isn't read in full, its CRC32 value isn't verified.
+    pub async fn read_entry_prefix(&mut self, index: usize, length: usize) -> Result<Vec<u8>> {
+        let entry = self.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
+        let mut data = Vec::with_capacity(std::cmp::min(length as u64, entry.entry.uncompressed_size()) as usize);
+
+        self.reader_without_entry(index).await?.take(length as u64).read_to_end(&mut data).await?;
+        Ok(data)
+    }
+
+    /// Computes a digest of the archive's contents which ignores all metadata other than entry names.
+    ///
+    /// Entries are visited in order of their filename bytes, with each entry's filename and then its uncompressed data
+    /// (each prefixed by its length) being fed into the provided hasher. Archives which only differ in their
+    /// timestamps, compression methods, entry order, or other metadata will therefore produce the same digest. Each
+    /// entry's data is streamed and its CRC32 value is verified along the way.
+    ///
+    /// Note that this requires the `sha2` feature.
+    #[cfg(feature = "sha2")]
+    pub async fn content_digest<D>(&mut self, mut hasher: D) -> Result<sha2::digest::Output<D>>
+    where
+        D: sha2::Digest,
+    {
+        let mut indexes: Vec<usize> = (0..self.file.entries.len()).collect();
+        indexes.sort_by(|a, b| {
+            let (a, b) = (&self.file.entries[*a].entry, &self.file.entries[*b].entry);
+            a.filename().as_bytes().cmp(b.filename().as_bytes())
+        });
+
+        for index in indexes {
+            let entry = &self.file.entries[index].entry;
+            let filename = entry.filename().as_bytes();
+            hasher.update((filename.len() as u64).to_le_bytes());
+            hasher.update(filename);
+            hasher.update(entry.uncompressed_size().to_le_bytes());
+
+            self.reader_with_entry(index).await?.copy_to_checked(DigestWriter(&mut hasher)).await?;
+        }
+
+        Ok(hasher.finalize())
+    }
+
+    /// Checks the data descriptor of the entry at the provided index against its central directory record.
+    ///
+    /// Entries without a data descriptor are always considered valid. Descriptors are accepted with or without their
+    /// optional signature, as some tools omit it. The descriptor's sizes are read as 8 bytes each for ZIP64 entries
+    /// (falling back to saturated 4 byte sizes), and as 4 bytes each otherwise. Returns
+    /// [`ZipError::DataDescriptorMismatch`] if the descriptor's CRC32 value or sizes don't match the central directory
+    /// record.
+    pub async fn validate_data_descriptor(&mut self, index: usize) -> Result<()> {
+        let stored_entry = self.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
+
+        if !stored_entry.entry.data_descriptor {
+            return Ok(());
+        }
+
+        stored_entry.seek_to_data_offset(&mut self.reader).await?;
+        self.reader.seek(SeekFrom::Current(stored_entry.entry.compressed_size() as i64)).await?;
+
+        match crate::base::read::data_descriptor(&mut self.reader, &stored_entry.entry).await? {
+            true => Ok(()),
+            false => Err(ZipError::DataDescriptorMismatch),
+        }
+    }
+
     /// Returns a new entry reader if the provided index is valid.
     /// Consumes self
     pub async fn into_entry<'a>(mut self, index: usize) -> Result<ZipEntryReader<'a, R, WithoutEntry>>
@@ -118,11 +300,112 @@ where
 
         stored_entry.seek_to_data_offset(&mut self.reader).await?;
 
-        Ok(ZipEntryReader::new_with_owned(
-            self.reader,
-            stored_entry.entry.compression(),
-            stored_entry.entry.compressed_size(),
-        ))
+        let size = stored_entry.entry.compressed_size();
+        ZipEntryReader::new_with_owned(self.reader, &stored_entry.entry, size, &self.codecs)
+    }
+}
+
+/// A lazy iterator over the central directory records of a seekable source.
+///
+/// Constructed via [`ZipFileReader::entries_lazy()`]. As the source is seeked to the next record before each is
+/// parsed, the source may be freely used (eg. to read an entry's data) between calls to [`LazyEntries::next_entry()`].
+#[derive(Debug)]
+pub struct LazyEntries<R> {
+    reader: R,
+    offset: u64,
+    remaining: u64,
+    zip64: bool,
+    codecs: CodecRegistry,
+}
+
+impl<R> LazyEntries<R>
+where
+    R: AsyncBufRead + AsyncSeek + Unpin,
+{
+    /// Parses and returns the next central directory record, or `None` if all records have been returned.
+    pub async fn next_entry(&mut self) -> Result<Option<StoredZipEntry>> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+
+        self.reader.seek(SeekFrom::Start(self.offset)).await?;
+        let entry = crate::base::read::cd_record(&mut self.reader, self.zip64).await?;
+
+        self.offset = self.reader.seek(SeekFrom::Current(0)).await?;
+        self.remaining -= 1;
+
+        Ok(Some(entry))
+    }
+
+    /// Registers a codec for reading entries which use the provided custom compression method id, replacing any codec
+    /// previously registered for it.
+    ///
+    /// Returns [`ZipError::CompressionMethodReserved`] if the method id belongs to a method implemented by this crate.
+    /// See the [`codec`](crate::codec) module for more information.
+    pub fn register_codec(&mut self, method: u16, codec: Arc<dyn Codec>) -> Result<()> {
+        self.codecs.register(method, codec)
+    }
+
+    /// Returns the number of central directory records which have yet to be returned.
+    pub fn remaining(&self) -> u64 {
+        self.remaining
+    }
+
+    /// Returns a new entry reader for the provided entry, which must have been returned by this iterator.
+    pub async fn reader_without_entry(
+        &mut self,
+        entry: &StoredZipEntry,
+    ) -> Result<ZipEntryReader<'_, R, WithoutEntry>> {
+        entry.seek_to_data_offset(&mut self.reader).await?;
+
+        ZipEntryReader::new_with_borrow(&mut self.reader, &entry.entry, entry.entry.compressed_size(), &self.codecs)
+    }
+
+    /// Returns a mutable reference to the inner seekable source.
+    pub fn inner_mut(&mut self) -> &mut R {
+        &mut self.reader
+    }
+
+    /// Returns the inner seekable source by consuming self.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R> ZipFileReader<BufferedSeekReader<R>>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    /// Constructs a new ZIP reader from an unbuffered seekable source, wrapping it in a [`BufferedSeekReader`].
+    ///
+    /// Unlike most buffered readers, the buffer is retained when seeking to a position within it. This reduces the
+    /// number of reads & seeks of the source when reading many adjacent entries.
+    pub async fn new_buffered(reader: R) -> Result<ZipFileReader<BufferedSeekReader<R>>> {
+        ZipFileReader::new(BufferedSeekReader::new(reader)).await
+    }
+}
+
+impl<R> ZipFileReader<BaseOffsetReader<R>>
+where
+    R: AsyncBufRead + AsyncSeek + Unpin,
+{
+    /// Constructs a new ZIP reader from a seekable source in which the archive starts at the provided offset.
+    ///
+    /// This allows reading archives which are preceded by other data (eg. a self-extracting stub, a shebang line, or a
+    /// byte-order mark) of a known length. All offsets, including those returned by
+    /// [`StoredZipEntry::header_offset()`], remain relative to the start of the archive rather than the source.
+    pub async fn new_with_base_offset(reader: R, base: u64) -> Result<ZipFileReader<BaseOffsetReader<R>>> {
+        ZipFileReader::new(BaseOffsetReader::new(reader, base)).await
+    }
+
+    /// Constructs a new ZIP reader from a seekable source in which the archive may be preceded by other data, detecting
+    /// the length of that data from the end of central directory records.
+    ///
+    /// See [`ZipFileReader::new_with_base_offset()`] for more information. The detected length can be retrieved via
+    /// [`BaseOffsetReader::base()`].
+    pub async fn new_with_prefix(mut reader: R) -> Result<ZipFileReader<BaseOffsetReader<R>>> {
+        let base = crate::base::read::base_offset(&mut reader).await?;
+        ZipFileReader::new_with_base_offset(reader, base).await
     }
 }
 
@@ -138,3 +421,26 @@ where
         Ok(ZipFileReader::from_raw_parts(reader, file))
     }
 }
+
+/// A writer which feeds all written data into a hasher.
+#[cfg(feature = "sha2")]
+struct DigestWriter<'a, D>(&'a mut D);
+
+#[cfg(feature = "sha2")]
+impl<D> futures_lite::io::AsyncWrite for DigestWriter<'_, D>
+where
+    D: sha2::Digest,
+{
+    fn poll_write(self: Pin<&mut Self>, _: &mut Context<'_>, buf: &[u8]) -> Poll<std::io::Result<usize>> {
+        self.get_mut().0.update(buf);
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
@@ -19,7 +19,8 @@
 //! to it. We have to rely on information provided within the local file header which may not be accurate or complete.
 //! This results in:
 //! - The inability to read ZIP entries using the combination of a data descriptor and the Stored compression method.
-//! - No file comment being available (defaults to an empty string).
+//! - No file comment being available (defaults to an empty string), unless the archive comment is read separately via
+//!   [`ZipFileReader::finish()`] once all entries have been read.
 //! - No internal or external file attributes being available (defaults to 0).
 //! - The extra field data potentially being inconsistent with what's stored in the central directory.
 //! - None of the following being available when the entry was written with a data descriptor (defaults to 0):
@@ -47,8 +48,11 @@
 //! ```
 
 use crate::base::read::io::entry::ZipEntryReader;
+use crate::base::read::NextRecord;
+use crate::codec::{Codec, CodecRegistry};
 use crate::error::Result;
 use crate::error::ZipError;
+use crate::file::ArchiveSummary;
 
 use crate::spec::consts::DATA_DESCRIPTOR_LENGTH;
 use crate::spec::consts::DATA_DESCRIPTOR_SIGNATURE;
@@ -56,6 +60,8 @@ use crate::spec::consts::SIGNATURE_LENGTH;
 #[cfg(feature = "tokio")]
 use crate::tokio::read::stream::Ready as TokioReady;
 
+use std::sync::Arc;
+
 use futures_lite::io::AsyncBufRead;
 use futures_lite::io::AsyncReadExt;
 
@@ -66,15 +72,16 @@ use super::io::entry::WithEntry;
 use super::io::entry::WithoutEntry;
 
 /// A type which encodes that [`ZipFileReader`] is ready to open a new entry.
-pub struct Ready<R>(R);
+#[derive(Debug)]
+pub struct Ready<R>(R, CodecRegistry);
 
 /// A type which encodes that [`ZipFileReader`] is currently reading an entry.
-pub struct Reading<'a, R, E>(ZipEntryReader<'a, R, E>, bool);
+pub struct Reading<'a, R, E>(ZipEntryReader<'a, R, E>, bool, CodecRegistry);
 
 /// A ZIP reader which acts over a non-seekable source.
 ///
 /// See the [module-level docs](.) for more information.
-#[derive(Clone)]
+#[derive(Clone, Debug)]
 pub struct ZipFileReader<S>(S);
 
 impl<'a, R> ZipFileReader<Ready<R>>
@@ -83,34 +90,72 @@ where
 {
     /// Constructs a new ZIP reader from a non-seekable source.
     pub fn new(reader: R) -> Self {
-        Self(Ready(reader))
+        Self::new_with_codecs(reader, CodecRegistry::default())
+    }
+
+    /// Constructs a new ZIP reader from a non-seekable source, with the provided codecs already registered.
+    pub(crate) fn new_with_codecs(reader: R, codecs: CodecRegistry) -> Self {
+        Self(Ready(reader, codecs))
+    }
+
+    /// Registers a codec for reading entries which use the provided custom compression method id, replacing any codec
+    /// previously registered for it.
+    ///
+    /// Returns [`ZipError::CompressionMethodReserved`] if the method id belongs to a method implemented by this crate.
+    /// See the [`codec`](crate::codec) module for more information.
+    pub fn register_codec(&mut self, method: u16, codec: Arc<dyn Codec>) -> Result<()> {
+        self.0 .1.register(method, codec)
     }
 
     /// Opens the next entry for reading if the central directory hasn’t yet been reached.
     pub async fn next_without_entry(mut self) -> Result<Option<ZipFileReader<Reading<'a, R, WithoutEntry>>>> {
-        let entry = match crate::base::read::lfh(&mut self.0 .0).await? {
-            Some(entry) => entry,
-            None => return Ok(None),
+        let NextRecord::Entry(entry) = crate::base::read::lfh(&mut self.0 .0).await? else {
+            return Ok(None);
         };
 
         let length = if entry.data_descriptor { u64::MAX } else { entry.compressed_size };
-        let reader = ZipEntryReader::new_with_owned(self.0 .0, entry.compression, length);
+        let reader = ZipEntryReader::new_with_owned(self.0 .0, &entry, length, &self.0 .1)?;
 
-        Ok(Some(ZipFileReader(Reading(reader, entry.data_descriptor))))
+        Ok(Some(ZipFileReader(Reading(reader, entry.data_descriptor, self.0 .1))))
     }
 
     /// Opens the next entry for reading if the central directory hasn’t yet been reached.
     pub async fn next_with_entry(mut self) -> Result<Option<ZipFileReader<Reading<'a, R, WithEntry<'a>>>>> {
-        let entry = match crate::base::read::lfh(&mut self.0 .0).await? {
-            Some(entry) => entry,
-            None => return Ok(None),
+        let NextRecord::Entry(entry) = crate::base::read::lfh(&mut self.0 .0).await? else {
+            return Ok(None);
         };
 
         let length = if entry.data_descriptor { u64::MAX } else { entry.compressed_size };
-        let reader = ZipEntryReader::new_with_owned(self.0 .0, entry.compression, length);
+        let reader = ZipEntryReader::new_with_owned(self.0 .0, &entry, length, &self.0 .1)?;
         let data_descriptor = entry.data_descriptor;
 
-        Ok(Some(ZipFileReader(Reading(reader.into_with_entry_owned(entry), data_descriptor))))
+        Ok(Some(ZipFileReader(Reading(reader.into_with_entry_owned(*entry), data_descriptor, self.0 .1))))
+    }
+
+    /// Skips any remaining entries and reads the central directory & end of central directory records, returning a
+    /// summary of the archive (incl. its comment & number of entries).
+    ///
+    /// This should be called in place of opening the next entry once all entries of interest have been read, and
+    /// requires that the source can be read until the end of the archive.
+    pub async fn finish(mut self) -> Result<ArchiveSummary> {
+        let Ready(reader, codecs) = &mut self.0;
+
+        let signature = loop {
+            let entry = match crate::base::read::lfh(&mut *reader).await? {
+                NextRecord::Entry(entry) => *entry,
+                NextRecord::Trailer(signature) => break signature,
+            };
+
+            let length = if entry.data_descriptor { u64::MAX } else { entry.compressed_size };
+            let mut entry_reader = ZipEntryReader::new_with_borrow(&mut *reader, &entry, length, codecs)?;
+            while entry_reader.read(&mut [0; 2048]).await? != 0 {}
+
+            if entry.data_descriptor {
+                consume_data_descriptor(&mut *reader).await?;
+            }
+        };
+
+        crate::base::read::cd_trailer(reader, signature).await
     }
 
     /// Consumes the `ZipFileReader` returning the original `reader`
@@ -126,7 +171,7 @@ where
 {
     /// Constructs a new tokio-specific ZIP reader from a non-seekable source.
     pub fn with_tokio(reader: R) -> ZipFileReader<TokioReady<R>> {
-        Self(Ready(reader.compat()))
+        Self(Ready(reader.compat(), CodecRegistry::default()))
     }
 }
 
@@ -157,7 +202,7 @@ where
             consume_data_descriptor(&mut inner).await?;
         }
 
-        Ok(ZipFileReader(Ready(inner)))
+        Ok(ZipFileReader(Ready(inner, self.0 .2)))
     }
 
     /// Reads until EOF and converts the reader back into the Ready state.
@@ -170,7 +215,7 @@ where
             consume_data_descriptor(&mut inner).await?;
         }
 
-        Ok(ZipFileReader(Ready(inner)))
+        Ok(ZipFileReader(Ready(inner, self.0 .2)))
     }
 }
 
@@ -0,0 +1,133 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+//! A module which supports writing ZIP files as a sequence of fixed-size chunks (eg. for multipart uploads).
+
+use crate::base::write::io::chunked::ChunkWriter;
+use crate::base::write::{EntryStreamWriter, ZipFileWriter};
+use crate::entry::ZipEntry;
+use crate::error::Result;
+
+use std::future::Future;
+
+use futures_lite::io::AsyncRead;
+
+/// The minimum size of each chunk, matching the minimum part size of S3 multipart uploads.
+pub const MIN_CHUNK_SIZE: usize = 5 * 1024 * 1024;
+
+/// A ZIP file writer which buffers its output and passes it on as a sequence of fixed-size chunks.
+///
+/// Each completed chunk is passed to the provided closure along with the absolute offset at which it starts, with only
+/// the final chunk (passed on when the writer is closed) being smaller than the chunk size. The future returned by the
+/// closure (eg. uploading the chunk) is awaited before any further data is buffered, so at most one chunk is held in
+/// memory whilst another is being passed on. As offsets are tracked across chunks, the central directory references
+/// absolute positions within the concatenated archive. This allows a large archive to be streamed to object storage via
+/// a multipart upload without staging it locally, where chunk `n` (starting from zero) is uploaded as part `n + 1`.
+///
+/// # Note
+/// - [`ChunkedZipFileWriter::close()`] must be called before the writer goes out of scope, otherwise the final chunk
+///   will never be passed on.
+///
+/// # Example
+/// ```no_run
+/// # use async_zip::{Compression, ZipEntryBuilder, base::write::{ChunkedZipFileWriter, MIN_CHUNK_SIZE}};
+/// # use async_zip::error::ZipError;
+/// #
+/// # async fn run() -> Result<(), ZipError> {
+/// let mut writer = ChunkedZipFileWriter::new(MIN_CHUNK_SIZE, |offset, data: Vec<u8>| async move {
+///     // Upload `data` as part `offset / MIN_CHUNK_SIZE + 1`.
+///     Ok(())
+/// });
+///
+/// let opts = ZipEntryBuilder::new(String::from("foo.txt").into(), Compression::Stored);
+/// writer.write_entry_whole(opts, &[0; 100_000]).await?;
+///
+/// let size = writer.close().await?;
+/// #   Ok(())
+/// # }
+/// ```
+pub struct ChunkedZipFileWriter<F, Fut> {
+    writer: ZipFileWriter<ChunkWriter<F, Fut>>,
+}
+
+impl<F, Fut> ChunkedZipFileWriter<F, Fut>
+where
+    F: FnMut(u64, Vec<u8>) -> Fut + Unpin,
+    Fut: Future<Output = std::io::Result<()>>,
+{
+    /// Construct a new chunked ZIP file writer which passes chunks of `chunk_size` bytes to `chunk`.
+    ///
+    /// The chunk size is raised to [`MIN_CHUNK_SIZE`] if smaller.
+    pub fn new(chunk_size: usize, chunk: F) -> Self {
+        Self::with_options(chunk_size, chunk, |writer| writer)
+    }
+
+    /// Construct a new chunked ZIP file writer which passes chunks of `chunk_size` bytes to `chunk`, configuring the
+    /// inner ZIP writer's options via the provided closure.
+    ///
+    /// This allows the options which consume the writer (eg. [`ZipFileWriter::force_zip64()`]) to be set before any
+    /// data is written. The chunk size is raised to [`MIN_CHUNK_SIZE`] if smaller.
+    pub fn with_options<O>(chunk_size: usize, chunk: F, options: O) -> Self
+    where
+        O: FnOnce(ZipFileWriter<ChunkWriter<F, Fut>>) -> ZipFileWriter<ChunkWriter<F, Fut>>,
+    {
+        let chunk_size = std::cmp::max(chunk_size, MIN_CHUNK_SIZE);
+        Self { writer: options(ZipFileWriter::new(ChunkWriter::new(chunk_size, chunk))) }
+    }
+
+    /// Returns a mutable reference to the inner ZIP writer.
+    ///
+    /// Options which consume the writer can't be set via this reference, and should instead be set at construction via
+    /// [`ChunkedZipFileWriter::with_options()`].
+    pub fn writer_mut(&mut self) -> &mut ZipFileWriter<ChunkWriter<F, Fut>> {
+        &mut self.writer
+    }
+
+    /// Returns the number of bytes which have been written so far, including those not yet passed on.
+    pub fn offset(&self) -> u64 {
+        self.writer.writer.offset()
+    }
+
+    /// Write a new ZIP entry of known size and data.
+    pub async fn write_entry_whole<E: Into<ZipEntry>>(&mut self, entry: E, data: &[u8]) -> Result<()> {
+        self.writer.write_entry_whole(entry, data).await
+    }
+
+    /// Write an entry of unknown size and data via streaming (ie. using a data descriptor).
+    ///
+    /// See [`ZipFileWriter::write_entry_stream()`] for more information.
+    pub async fn write_entry_stream<E: Into<ZipEntry>>(
+        &mut self,
+        entry: E,
+    ) -> Result<EntryStreamWriter<'_, ChunkWriter<F, Fut>>> {
+        self.writer.write_entry_stream(entry).await
+    }
+
+    /// Write a new ZIP entry via streaming, copying all data from the provided reader until EOF is reached.
+    pub async fn write_entry_from_reader<E, R>(&mut self, entry: E, reader: R) -> Result<()>
+    where
+        E: Into<ZipEntry>,
+        R: AsyncRead + Unpin,
+    {
+        self.writer.write_entry_from_reader(entry, reader).await
+    }
+
+    /// Set the ZIP file comment.
+    pub fn comment(&mut self, comment: String) {
+        self.writer.comment(comment);
+    }
+
+    /// Set the ZIP file comment from raw bytes, which needn't be valid UTF-8.
+    pub fn comment_bytes(&mut self, comment: Vec<u8>) {
+        self.writer.comment_bytes(comment);
+    }
+
+    /// Consumes this ZIP writer and completes all closing tasks, passing on the final chunk and returning the total
+    /// size of the archive.
+    ///
+    /// See [`ZipFileWriter::close()`] for more information.
+    pub async fn close(self) -> Result<u64> {
+        let mut writer = self.writer.close().await?;
+        Ok(writer.finish().await?)
+    }
+}
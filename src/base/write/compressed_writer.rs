@@ -1,11 +1,17 @@
 // Copyright (c) 2021 Harry [Majored] [hello@majored.pw]
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
+use crate::base::write::io::codec::CodecEncoder;
+#[cfg(feature = "lzma")]
+use crate::base::write::io::lzma::LzmaHeaderWriter;
 use crate::base::write::io::offset::AsyncOffsetWriter;
+use crate::codec::Codec;
+use crate::error::{Result, ZipError};
 use crate::spec::Compression;
 
 use std::io::Error;
 use std::pin::Pin;
+use std::sync::Arc;
 use std::task::{Context, Poll};
 
 #[cfg(any(feature = "deflate", feature = "bzip2", feature = "zstd", feature = "lzma", feature = "xz"))]
@@ -13,53 +19,72 @@ use async_compression::futures::write;
 use futures_lite::io::AsyncWrite;
 
 pub enum CompressedAsyncWriter<'b, W: AsyncWrite + Unpin> {
-    Stored(ShutdownIgnoredWriter<&'b mut AsyncOffsetWriter<W>>),
+    // Stored data has no encoder to finish, so the offset writer isn't wrapped in ShutdownIgnoredWriter. Closing the
+    // entry only flushes it instead (see poll_close below).
+    Stored(&'b mut AsyncOffsetWriter<W>),
     #[cfg(feature = "deflate")]
     Deflate(write::DeflateEncoder<ShutdownIgnoredWriter<&'b mut AsyncOffsetWriter<W>>>),
     #[cfg(feature = "bzip2")]
     Bz(write::BzEncoder<ShutdownIgnoredWriter<&'b mut AsyncOffsetWriter<W>>>),
     #[cfg(feature = "lzma")]
-    Lzma(write::LzmaEncoder<ShutdownIgnoredWriter<&'b mut AsyncOffsetWriter<W>>>),
+    Lzma(write::LzmaEncoder<LzmaHeaderWriter<ShutdownIgnoredWriter<&'b mut AsyncOffsetWriter<W>>>>),
     #[cfg(feature = "zstd")]
     Zstd(write::ZstdEncoder<ShutdownIgnoredWriter<&'b mut AsyncOffsetWriter<W>>>),
     #[cfg(feature = "xz")]
     Xz(write::XzEncoder<ShutdownIgnoredWriter<&'b mut AsyncOffsetWriter<W>>>),
+    Custom(CodecEncoder<&'b mut AsyncOffsetWriter<W>>),
 }
 
 impl<'b, W: AsyncWrite + Unpin> CompressedAsyncWriter<'b, W> {
-    pub fn from_raw(writer: &'b mut AsyncOffsetWriter<W>, compression: Compression) -> Self {
-        match compression {
-            Compression::Stored => CompressedAsyncWriter::Stored(ShutdownIgnoredWriter(writer)),
+    /// Constructs a new wrapping writer, where a codec must be provided if the compression method is custom.
+    pub fn from_raw(
+        writer: &'b mut AsyncOffsetWriter<W>,
+        compression: Compression,
+        codec: Option<Arc<dyn Codec>>,
+    ) -> Result<Self> {
+        Ok(match compression {
+            Compression::Stored => CompressedAsyncWriter::Stored(writer),
             #[cfg(feature = "deflate")]
             Compression::Deflate => {
                 CompressedAsyncWriter::Deflate(write::DeflateEncoder::new(ShutdownIgnoredWriter(writer)))
             }
             #[cfg(feature = "deflate64")]
-            Compression::Deflate64 => panic!("writing deflate64 is not supported"),
+            Compression::Deflate64 => {
+                return Err(ZipError::FeatureNotSupported("writing deflate64 compressed entries"));
+            }
             #[cfg(feature = "bzip2")]
             Compression::Bz => CompressedAsyncWriter::Bz(write::BzEncoder::new(ShutdownIgnoredWriter(writer))),
             #[cfg(feature = "lzma")]
-            Compression::Lzma => CompressedAsyncWriter::Lzma(write::LzmaEncoder::new(ShutdownIgnoredWriter(writer))),
+            Compression::Lzma => CompressedAsyncWriter::Lzma(write::LzmaEncoder::new(LzmaHeaderWriter::new(
+                ShutdownIgnoredWriter(writer),
+            ))),
             #[cfg(feature = "zstd")]
             Compression::Zstd => CompressedAsyncWriter::Zstd(write::ZstdEncoder::new(ShutdownIgnoredWriter(writer))),
             #[cfg(feature = "xz")]
             Compression::Xz => CompressedAsyncWriter::Xz(write::XzEncoder::new(ShutdownIgnoredWriter(writer))),
-        }
+            #[cfg(feature = "legacy")]
+            Compression::Implode => return Err(ZipError::FeatureNotSupported("writing implode compressed entries")),
+            Compression::Custom(method) => {
+                let codec = codec.ok_or(ZipError::CompressionNotSupported(method))?;
+                CompressedAsyncWriter::Custom(CodecEncoder::new(writer, codec.encoder()))
+            }
+        })
     }
 
     pub fn into_inner(self) -> &'b mut AsyncOffsetWriter<W> {
         match self {
-            CompressedAsyncWriter::Stored(inner) => inner.into_inner(),
+            CompressedAsyncWriter::Stored(inner) => inner,
             #[cfg(feature = "deflate")]
             CompressedAsyncWriter::Deflate(inner) => inner.into_inner().into_inner(),
             #[cfg(feature = "bzip2")]
             CompressedAsyncWriter::Bz(inner) => inner.into_inner().into_inner(),
             #[cfg(feature = "lzma")]
-            CompressedAsyncWriter::Lzma(inner) => inner.into_inner().into_inner(),
+            CompressedAsyncWriter::Lzma(inner) => inner.into_inner().into_inner().into_inner(),
             #[cfg(feature = "zstd")]
             CompressedAsyncWriter::Zstd(inner) => inner.into_inner().into_inner(),
             #[cfg(feature = "xz")]
             CompressedAsyncWriter::Xz(inner) => inner.into_inner().into_inner(),
+            CompressedAsyncWriter::Custom(inner) => inner.into_inner(),
         }
     }
 }
@@ -78,6 +103,7 @@ impl<'b, W: AsyncWrite + Unpin> AsyncWrite for CompressedAsyncWriter<'b, W> {
             CompressedAsyncWriter::Zstd(ref mut inner) => Pin::new(inner).poll_write(cx, buf),
             #[cfg(feature = "xz")]
             CompressedAsyncWriter::Xz(ref mut inner) => Pin::new(inner).poll_write(cx, buf),
+            CompressedAsyncWriter::Custom(ref mut inner) => Pin::new(inner).poll_write(cx, buf),
         }
     }
 
@@ -94,12 +120,14 @@ impl<'b, W: AsyncWrite + Unpin> AsyncWrite for CompressedAsyncWriter<'b, W> {
             CompressedAsyncWriter::Zstd(ref mut inner) => Pin::new(inner).poll_flush(cx),
             #[cfg(feature = "xz")]
             CompressedAsyncWriter::Xz(ref mut inner) => Pin::new(inner).poll_flush(cx),
+            CompressedAsyncWriter::Custom(ref mut inner) => Pin::new(inner).poll_flush(cx),
         }
     }
 
     fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<std::result::Result<(), Error>> {
         match *self {
-            CompressedAsyncWriter::Stored(ref mut inner) => Pin::new(inner).poll_close(cx),
+            // Closing an entry must not close the underlying writer, so we only flush it.
+            CompressedAsyncWriter::Stored(ref mut inner) => Pin::new(inner).poll_flush(cx),
             #[cfg(feature = "deflate")]
             CompressedAsyncWriter::Deflate(ref mut inner) => Pin::new(inner).poll_close(cx),
             #[cfg(feature = "bzip2")]
@@ -110,18 +138,22 @@ impl<'b, W: AsyncWrite + Unpin> AsyncWrite for CompressedAsyncWriter<'b, W> {
             CompressedAsyncWriter::Zstd(ref mut inner) => Pin::new(inner).poll_close(cx),
             #[cfg(feature = "xz")]
             CompressedAsyncWriter::Xz(ref mut inner) => Pin::new(inner).poll_close(cx),
+            CompressedAsyncWriter::Custom(ref mut inner) => Pin::new(inner).poll_close(cx),
         }
     }
 }
 
+#[cfg(any(feature = "deflate", feature = "bzip2", feature = "zstd", feature = "lzma", feature = "xz"))]
 pub struct ShutdownIgnoredWriter<W: AsyncWrite + Unpin>(W);
 
+#[cfg(any(feature = "deflate", feature = "bzip2", feature = "zstd", feature = "lzma", feature = "xz"))]
 impl<W: AsyncWrite + Unpin> ShutdownIgnoredWriter<W> {
     pub fn into_inner(self) -> W {
         self.0
     }
 }
 
+#[cfg(any(feature = "deflate", feature = "bzip2", feature = "zstd", feature = "lzma", feature = "xz"))]
 impl<W: AsyncWrite + Unpin> AsyncWrite for ShutdownIgnoredWriter<W> {
     fn poll_write(mut self: Pin<&mut Self>, cx: &mut Context, buf: &[u8]) -> Poll<std::result::Result<usize, Error>> {
         Pin::new(&mut self.0).poll_write(cx, buf)
@@ -2,14 +2,17 @@
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
 use crate::base::write::compressed_writer::CompressedAsyncWriter;
+use crate::base::write::entry_whole::{push_alignment_extra_field, strip_alignment_extra_field};
 use crate::base::write::get_or_put_info_zip_unicode_comment_extra_field_mut;
 use crate::base::write::get_or_put_info_zip_unicode_path_extra_field_mut;
 use crate::base::write::io::offset::AsyncOffsetWriter;
-use crate::base::write::CentralDirectoryEntry;
 use crate::base::write::ZipFileWriter;
+use crate::base::write::{record_last_name, CentralDirectoryEntry};
 use crate::entry::ZipEntry;
 use crate::error::{Result, Zip64ErrorCase, ZipError};
 use crate::spec::extra_field::ExtraFieldAsBytes;
+#[cfg(feature = "crc32c")]
+use crate::spec::header::Crc32cExtraField;
 use crate::spec::header::InfoZipUnicodeCommentExtraField;
 use crate::spec::header::InfoZipUnicodePathExtraField;
 use crate::spec::header::{
@@ -18,6 +21,7 @@ use crate::spec::header::{
 };
 use crate::string::StringEncoding;
 
+use std::collections::HashSet;
 use std::io::Error;
 use std::pin::Pin;
 use std::task::{Context, Poll};
@@ -25,7 +29,32 @@ use std::task::{Context, Poll};
 use crate::base::read::get_zip64_extra_field_mut;
 use crate::spec::consts::{NON_ZIP64_MAX_NUM_FILES, NON_ZIP64_MAX_SIZE};
 use crc32fast::Hasher;
-use futures_lite::io::{AsyncWrite, AsyncWriteExt};
+use futures_lite::io::{AsyncSeek, AsyncSeekExt, AsyncWrite, AsyncWriteExt, SeekFrom};
+#[cfg(feature = "sha2")]
+use sha2::{Digest, Sha256};
+
+/// The digests of an entry's uncompressed data, computed whilst it was written.
+///
+/// See [`EntryStreamWriter::close_with_digest()`].
+#[cfg(feature = "sha2")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EntryDigest {
+    crc32: u32,
+    sha256: Option<[u8; 32]>,
+}
+
+#[cfg(feature = "sha2")]
+impl EntryDigest {
+    /// Returns the CRC32 value of the entry's data, as stored within its headers.
+    pub fn crc32(&self) -> u32 {
+        self.crc32
+    }
+
+    /// Returns the SHA-256 digest of the entry's data, if requested via [`EntryStreamWriter::with_digest()`].
+    pub fn sha256(&self) -> Option<&[u8; 32]> {
+        self.sha256.as_ref()
+    }
+}
 
 /// An entry writer which supports the streaming of data (ie. the writing of unknown size or data at runtime).
 ///
@@ -36,12 +65,20 @@ use futures_lite::io::{AsyncWrite, AsyncWriteExt};
 pub struct EntryStreamWriter<'b, W: AsyncWrite + Unpin> {
     writer: AsyncOffsetWriter<CompressedAsyncWriter<'b, W>>,
     cd_entries: &'b mut Vec<CentralDirectoryEntry>,
+    /// To record the entry's filename once written, if duplicate filenames are rejected.
+    names: &'b mut Option<HashSet<Vec<u8>>>,
     entry: ZipEntry,
     hasher: Hasher,
+    #[cfg(feature = "crc32c")]
+    crc32c: Option<u32>,
+    #[cfg(feature = "sha2")]
+    sha256: Option<Sha256>,
     lfh: LocalFileHeader,
     lfh_offset: u64,
     data_offset: u64,
     force_no_zip64: bool,
+    /// Whether the local file header contains a zip64 extended information field.
+    lfh_zip64: bool,
     /// To write back to the original writer if zip64 is required.
     is_zip64: &'b mut bool,
 }
@@ -51,32 +88,65 @@ impl<'b, W: AsyncWrite + Unpin> EntryStreamWriter<'b, W> {
         writer: &'b mut ZipFileWriter<W>,
         mut entry: ZipEntry,
     ) -> Result<EntryStreamWriter<'b, W>> {
+        writer.apply_normalize_separators(&mut entry);
+        if writer.validate_filenames {
+            entry.validate_filename(false)?;
+        }
+        writer.check_duplicate_name(&entry)?;
+        writer.apply_store_extensions(&mut entry);
+        writer.apply_mmap_ready(&mut entry);
+        writer.apply_unicode_strategy(&mut entry);
+        writer.apply_extended_timestamp(&mut entry);
+        entry.check_skip_crc()?;
+
+        #[cfg(feature = "legacy")]
+        if matches!(entry.compression(), crate::spec::Compression::Implode) {
+            return Err(ZipError::FeatureNotSupported("writing implode compressed entries"));
+        }
+        let codec = entry.custom_codec()?;
+
+        #[cfg(feature = "crc32c")]
+        let crc32c = writer.crc32c.then_some(0);
+        #[cfg(feature = "crc32c")]
+        if crc32c.is_some() {
+            entry.extra_fields.retain(|field| !matches!(field, ExtraField::Crc32c(_)));
+        }
+
         let lfh_offset = writer.writer.offset();
         let lfh = EntryStreamWriter::write_lfh(writer, &mut entry).await?;
         let data_offset = writer.writer.offset();
         let force_no_zip64 = writer.force_no_zip64;
+        let lfh_zip64 = !writer.force_no_zip64 && !writer.lazy_zip64_stream;
 
         let cd_entries = &mut writer.cd_entries;
+        let names = &mut writer.names;
         let is_zip64 = &mut writer.is_zip64;
-        let writer = AsyncOffsetWriter::new(CompressedAsyncWriter::from_raw(&mut writer.writer, entry.compression()));
+        let writer =
+            AsyncOffsetWriter::new(CompressedAsyncWriter::from_raw(&mut writer.writer, entry.compression(), codec)?);
 
         Ok(EntryStreamWriter {
             writer,
             cd_entries,
+            names,
             entry,
             lfh,
             lfh_offset,
             data_offset,
             hasher: Hasher::new(),
+            #[cfg(feature = "crc32c")]
+            crc32c,
+            #[cfg(feature = "sha2")]
+            sha256: None,
             force_no_zip64,
+            lfh_zip64,
             is_zip64,
         })
     }
 
     async fn write_lfh(writer: &'b mut ZipFileWriter<W>, entry: &mut ZipEntry) -> Result<LocalFileHeader> {
-        // Always emit a zip64 extended field, even if we don't need it, because we *might* need it.
-        // If we are forcing no zip, we will have to error later if the file is too large.
-        let (lfh_compressed, lfh_uncompressed) = if !writer.force_no_zip64 {
+        // Always emit a zip64 extended field, even if we don't need it, because we *might* need it (unless the caller
+        // has opted out). If we are forcing no zip, we will have to error later if the file is too large.
+        let (lfh_compressed, lfh_uncompressed) = if !writer.force_no_zip64 && !writer.lazy_zip64_stream {
             if !writer.is_zip64 {
                 writer.is_zip64 = true;
             }
@@ -127,6 +197,10 @@ impl<'b, W: AsyncWrite + Unpin> EntryStreamWriter<'b, W> {
             }
         }
 
+        if let Some(alignment) = writer.mmap_alignment(entry) {
+            push_alignment_extra_field(entry, writer.writer.offset(), alignment);
+        }
+
         let filename_basic = entry.filename().alternative().unwrap_or_else(|| entry.filename().as_bytes());
 
         let lfh = LocalFileHeader {
@@ -142,11 +216,14 @@ impl<'b, W: AsyncWrite + Unpin> EntryStreamWriter<'b, W> {
             file_name_length: filename_basic.len().try_into().map_err(|_| ZipError::FileNameTooLarge)?,
             mod_time: entry.last_modification_date().time,
             mod_date: entry.last_modification_date().date,
-            version: crate::spec::version::as_needed_to_extract(entry),
+            version: entry.version_needed_to_extract(),
             flags: GeneralPurposeFlag {
                 data_descriptor: true,
                 encrypted: false,
-                filename_unicode: utf8_without_alternative,
+                filename_unicode: entry.utf8_flag.unwrap_or(utf8_without_alternative),
+                compression_option_1: entry.compression().has_eos_marker(),
+                compression_option_2: false,
+                strong_encryption: false,
             },
         };
 
@@ -158,6 +235,16 @@ impl<'b, W: AsyncWrite + Unpin> EntryStreamWriter<'b, W> {
         Ok(lfh)
     }
 
+    /// Flushes any data buffered by the entry's compression encoder through to the underlying writer.
+    ///
+    /// Unlike [`EntryStreamWriter::close()`], the entry remains open and further data may be written afterwards. This
+    /// is useful when streaming an archive to a consumer which should receive data as soon as it's available, at the
+    /// cost of a slightly lower compression ratio for each flush.
+    pub async fn flush_data(&mut self) -> Result<()> {
+        self.flush().await?;
+        Ok(())
+    }
+
     /// Consumes this entry writer and completes all closing tasks.
     ///
     /// This includes:
@@ -167,14 +254,69 @@ impl<'b, W: AsyncWrite + Unpin> EntryStreamWriter<'b, W> {
     /// - Pushing that central directory header to the [`ZipFileWriter`]'s store.
     ///
     /// Failure to call this function before going out of scope would result in a corrupted ZIP file.
-    pub async fn close(mut self) -> Result<()> {
+    pub async fn close(self) -> Result<()> {
+        self.finish(true).await?;
+        Ok(())
+    }
+
+    /// Additionally compute a SHA-256 digest of the entry's uncompressed data as it's written, continuing from the
+    /// state of the provided hasher.
+    ///
+    /// The digest is returned by [`EntryStreamWriter::close_with_digest()`]. As it would otherwise only cover part of
+    /// the entry's data, this returns [`ZipError::FeatureNotSupported`] if any data has already been written.
+    ///
+    /// Note that this requires the `sha2` feature.
+    #[cfg(feature = "sha2")]
+    pub fn with_digest(mut self, hasher: Sha256) -> Result<Self> {
+        if self.writer.offset() != 0 {
+            return Err(ZipError::FeatureNotSupported("enabling a digest after data has been written"));
+        }
+
+        self.sha256 = Some(hasher);
+        Ok(self)
+    }
+
+    /// Consumes this entry writer and completes all closing tasks, returning the digests of the written data.
+    ///
+    /// See [`EntryStreamWriter::close()`] for more information.
+    ///
+    /// Note that this requires the `sha2` feature.
+    #[cfg(feature = "sha2")]
+    pub async fn close_with_digest(mut self) -> Result<EntryDigest> {
+        let crc32 = self.hasher.clone().finalize();
+        let sha256 = self.sha256.take().map(|hasher| hasher.finalize().into());
+
+        self.finish(true).await?;
+        Ok(EntryDigest { crc32, sha256 })
+    }
+
+    /// Completes the entry and pushes its central directory header, writing a data descriptor if requested.
+    ///
+    /// Returns the underlying writer along with the bytes of a local file header updated with the entry's final CRC32
+    /// value and sizes. This header is only meaningful when no data descriptor was written.
+    async fn finish(mut self, data_descriptor: bool) -> Result<(&'b mut AsyncOffsetWriter<W>, Vec<u8>)> {
         self.writer.close().await?;
 
         let crc = self.hasher.finalize();
         let uncompressed_size = self.writer.offset();
+        self.entry.check_directory_size(uncompressed_size)?;
         let inner_writer = self.writer.into_inner().into_inner();
         let compressed_size = inner_writer.offset() - self.data_offset;
 
+        // The local file header's extra fields (which only differ by the sizes within the zip64 extended information
+        // field) are needed if the header is to be rewritten in place.
+        let mut lfh_extra_fields = self.entry.extra_fields.clone();
+        if let Some(zip64) = get_zip64_extra_field_mut(&mut lfh_extra_fields) {
+            zip64.uncompressed_size = Some(uncompressed_size);
+            zip64.compressed_size = Some(compressed_size);
+        }
+        strip_alignment_extra_field(&mut self.entry);
+
+        #[cfg(feature = "crc32c")]
+        if let Some(crc32c) = self.crc32c {
+            self.entry.extra_fields.push(ExtraField::Crc32c(Crc32cExtraField { crc32c }));
+        }
+
         let (cdr_compressed_size, cdr_uncompressed_size, lh_offset) = if self.force_no_zip64 {
             if uncompressed_size > NON_ZIP64_MAX_SIZE as u64
                 || compressed_size > NON_ZIP64_MAX_SIZE as u64
@@ -182,7 +324,31 @@ impl<'b, W: AsyncWrite + Unpin> EntryStreamWriter<'b, W> {
             {
                 return Err(ZipError::Zip64Needed(Zip64ErrorCase::LargeFile));
             }
-            (uncompressed_size as u32, compressed_size as u32, self.lfh_offset as u32)
+            (compressed_size as u32, uncompressed_size as u32, self.lfh_offset as u32)
+        } else if !self.lfh_zip64 {
+            // Without a zip64 extended field in the local file header, large sizes can't be represented within it or
+            // the data descriptor. A large offset only needs to be represented within the central directory.
+            if uncompressed_size > NON_ZIP64_MAX_SIZE as u64 || compressed_size > NON_ZIP64_MAX_SIZE as u64 {
+                return Err(ZipError::Zip64Needed(Zip64ErrorCase::LargeFile));
+            }
+
+            let lh_offset = if self.lfh_offset > NON_ZIP64_MAX_SIZE as u64 {
+                self.entry.extra_fields.push(ExtraField::Zip64ExtendedInformation(
+                    Zip64ExtendedInformationExtraField {
+                        header_id: HeaderId::ZIP64_EXTENDED_INFORMATION_EXTRA_FIELD,
+                        uncompressed_size: None,
+                        compressed_size: None,
+                        relative_header_offset: Some(self.lfh_offset),
+                        disk_start_number: None,
+                    },
+                ));
+                *self.is_zip64 = true;
+                NON_ZIP64_MAX_SIZE
+            } else {
+                self.lfh_offset as u32
+            };
+
+            (compressed_size as u32, uncompressed_size as u32, lh_offset)
         } else {
             // When streaming an entry, we are always using a zip64 field.
             match get_zip64_extra_field_mut(&mut self.entry.extra_fields) {
@@ -204,16 +370,35 @@ impl<'b, W: AsyncWrite + Unpin> EntryStreamWriter<'b, W> {
                     zip64.relative_header_offset = Some(self.lfh_offset);
                 }
             }
-            self.lfh.extra_field_length =
-                self.entry.extra_fields().count_bytes().try_into().map_err(|_| ZipError::ExtraFieldTooLarge)?;
 
             (NON_ZIP64_MAX_SIZE, NON_ZIP64_MAX_SIZE, NON_ZIP64_MAX_SIZE)
         };
 
-        inner_writer.write_all(&crate::spec::consts::DATA_DESCRIPTOR_SIGNATURE.to_le_bytes()).await?;
-        inner_writer.write_all(&crc.to_le_bytes()).await?;
-        inner_writer.write_all(&cdr_compressed_size.to_le_bytes()).await?;
-        inner_writer.write_all(&cdr_uncompressed_size.to_le_bytes()).await?;
+        let mut lfh_bytes = Vec::new();
+        if data_descriptor {
+            inner_writer.write_all(&crate::spec::consts::DATA_DESCRIPTOR_SIGNATURE.to_le_bytes()).await?;
+            inner_writer.write_all(&crc.to_le_bytes()).await?;
+            inner_writer.write_all(&cdr_compressed_size.to_le_bytes()).await?;
+            inner_writer.write_all(&cdr_uncompressed_size.to_le_bytes()).await?;
+        } else {
+            let filename_basic =
+                self.entry.filename().alternative().unwrap_or_else(|| self.entry.filename().as_bytes());
+
+            self.lfh.flags.data_descriptor = false;
+            self.lfh.crc = crc;
+            if !self.lfh_zip64 {
+                self.lfh.compressed_size = cdr_compressed_size;
+                self.lfh.uncompressed_size = cdr_uncompressed_size;
+            }
+
+            lfh_bytes.extend_from_slice(&crate::spec::consts::LFH_SIGNATURE.to_le_bytes());
+            lfh_bytes.extend_from_slice(&self.lfh.as_slice());
+            lfh_bytes.extend_from_slice(filename_basic);
+            lfh_bytes.extend_from_slice(&lfh_extra_fields.as_slice().as_bytes());
+        }
+
+        self.lfh.extra_field_length =
+            self.entry.extra_fields().count_bytes().try_into().map_err(|_| ZipError::ExtraFieldTooLarge)?;
 
         let comment_basic = self.entry.comment().alternative().unwrap_or_else(|| self.entry.comment().as_bytes());
 
@@ -221,7 +406,7 @@ impl<'b, W: AsyncWrite + Unpin> EntryStreamWriter<'b, W> {
             compressed_size: cdr_compressed_size,
             uncompressed_size: cdr_uncompressed_size,
             crc,
-            v_made_by: crate::spec::version::as_made_by(),
+            v_made_by: crate::spec::version::as_made_by(self.entry.attribute_compatibility()),
             v_needed: self.lfh.version,
             compression: self.lfh.compression,
             extra_field_length: self.lfh.extra_field_length,
@@ -237,6 +422,7 @@ impl<'b, W: AsyncWrite + Unpin> EntryStreamWriter<'b, W> {
         };
 
         self.cd_entries.push(CentralDirectoryEntry { header: cdh, entry: self.entry });
+        record_last_name(self.names, self.cd_entries);
         // Ensure that we can fit this many files in this archive if forcing no zip64
         if self.cd_entries.len() > NON_ZIP64_MAX_NUM_FILES as usize {
             if self.force_no_zip64 {
@@ -247,16 +433,55 @@ impl<'b, W: AsyncWrite + Unpin> EntryStreamWriter<'b, W> {
             }
         }
 
+        Ok((inner_writer, lfh_bytes))
+    }
+}
+
+impl<'b, W: AsyncWrite + AsyncSeek + Unpin> EntryStreamWriter<'b, W> {
+    /// Consumes this entry writer and completes all closing tasks, patching the local file header in place rather than
+    /// writing a data descriptor.
+    ///
+    /// Once the entry's data has been written, the underlying writer is seeked back to the entry's local file header,
+    /// which is rewritten with the final CRC32 value and sizes & without the data descriptor flag. The writer is then
+    /// seeked back to the end of the entry's data. This produces an archive equivalent to one written via
+    /// [`ZipFileWriter::write_entry_whole()`], which some strict readers prefer.
+    ///
+    /// As seeks are relative to the current position, the underlying writer needn't start at the beginning of the
+    /// seekable destination.
+    pub async fn close_and_patch(self) -> Result<()> {
+        let lfh_offset = self.lfh_offset;
+        let (writer, lfh_bytes) = self.finish(false).await?;
+
+        let distance = (writer.offset() - lfh_offset) as i64;
+        let inner = writer.inner_mut();
+
+        inner.seek(SeekFrom::Current(-distance)).await?;
+        inner.write_all(&lfh_bytes).await?;
+        inner.seek(SeekFrom::Current(distance - lfh_bytes.len() as i64)).await?;
+
         Ok(())
     }
 }
 
 impl<'a, W: AsyncWrite + Unpin> AsyncWrite for EntryStreamWriter<'a, W> {
     fn poll_write(mut self: Pin<&mut Self>, cx: &mut Context, buf: &[u8]) -> Poll<std::result::Result<usize, Error>> {
+        self.entry.check_directory_size(buf.len() as u64).map_err(Error::other)?;
         let poll = Pin::new(&mut self.writer).poll_write(cx, buf);
 
         if let Poll::Ready(Ok(written)) = poll {
-            self.hasher.update(&buf[0..written]);
+            if !self.entry.skip_crc {
+                self.hasher.update(&buf[0..written]);
+            }
+
+            #[cfg(feature = "crc32c")]
+            if let Some(crc32c) = self.crc32c {
+                self.crc32c = Some(crc32c::crc32c_append(crc32c, &buf[0..written]));
+            }
+
+            #[cfg(feature = "sha2")]
+            if let Some(sha256) = self.sha256.as_mut() {
+                sha256.update(&buf[0..written]);
+            }
         }
 
         poll
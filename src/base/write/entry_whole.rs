@@ -3,11 +3,14 @@
 
 use crate::base::write::get_or_put_info_zip_unicode_comment_extra_field_mut;
 use crate::base::write::get_or_put_info_zip_unicode_path_extra_field_mut;
-use crate::base::write::{CentralDirectoryEntry, ZipFileWriter};
+use crate::base::write::io::codec::CodecEncoder;
+use crate::base::write::{record_last_name, CentralDirectoryEntry, ZipFileWriter};
 use crate::entry::ZipEntry;
 use crate::error::{Result, Zip64ErrorCase, ZipError};
 use crate::spec::extra_field::Zip64ExtendedInformationExtraFieldBuilder;
-use crate::spec::header::{InfoZipUnicodeCommentExtraField, InfoZipUnicodePathExtraField};
+#[cfg(feature = "crc32c")]
+use crate::spec::header::Crc32cExtraField;
+use crate::spec::header::{HeaderId, InfoZipUnicodeCommentExtraField, InfoZipUnicodePathExtraField, UnknownExtraField};
 use crate::spec::{
     extra_field::ExtraFieldAsBytes,
     header::{CentralDirectoryRecord, ExtraField, GeneralPurposeFlag, LocalFileHeader},
@@ -16,8 +19,11 @@ use crate::spec::{
 use crate::StringEncoding;
 #[cfg(any(feature = "deflate", feature = "bzip2", feature = "zstd", feature = "lzma", feature = "xz"))]
 use futures_lite::io::Cursor;
+#[cfg(feature = "sha2")]
+use sha2::{Digest, Sha256};
+use std::borrow::Cow;
 
-use crate::spec::consts::{NON_ZIP64_MAX_NUM_FILES, NON_ZIP64_MAX_SIZE};
+use crate::spec::consts::{LFH_LENGTH, NON_ZIP64_MAX_NUM_FILES, NON_ZIP64_MAX_SIZE, SIGNATURE_LENGTH};
 #[cfg(any(feature = "deflate", feature = "bzip2", feature = "zstd", feature = "lzma", feature = "xz"))]
 use async_compression::futures::write;
 use futures_lite::io::{AsyncWrite, AsyncWriteExt};
@@ -34,175 +40,314 @@ impl<'b, 'c, W: AsyncWrite + Unpin> EntryWholeWriter<'b, 'c, W> {
     }
 
     pub async fn write(mut self) -> Result<()> {
-        let mut _compressed_data: Option<Vec<u8>> = None;
-        let compressed_data = match self.entry.compression() {
-            Compression::Stored => self.data,
-            #[cfg(any(
-                feature = "deflate",
-                feature = "bzip2",
-                feature = "zstd",
-                feature = "lzma",
-                feature = "xz",
-                feature = "deflate64"
-            ))]
-            _ => {
-                _compressed_data =
-                    Some(compress(self.entry.compression(), self.data, self.entry.compression_level).await);
-                _compressed_data.as_ref().unwrap()
-            }
-        };
+        self.entry.check_directory_size(self.data.len() as u64)?;
+        self.writer.apply_normalize_separators(&mut self.entry);
+        if self.writer.validate_filenames {
+            self.entry.validate_filename(false)?;
+        }
+        self.writer.check_duplicate_name(&self.entry)?;
+        self.writer.apply_store_extensions(&mut self.entry);
+        self.writer.apply_mmap_ready(&mut self.entry);
+        self.entry.check_skip_crc()?;
+
+        #[cfg(feature = "legacy")]
+        if matches!(self.entry.compression(), Compression::Implode) {
+            return Err(ZipError::FeatureNotSupported("writing implode compressed entries"));
+        }
+
+        if self.writer.auto_text_detection && !self.entry.explicit_text && is_text(self.data) {
+            self.entry.internal_file_attribute |= 0x1;
+        }
+
+        #[cfg(feature = "crc32c")]
+        if self.writer.crc32c {
+            self.entry.extra_fields.retain(|field| !matches!(field, ExtraField::Crc32c(_)));
+            let field = Crc32cExtraField { crc32c: crc32c::crc32c(self.data) };
+            self.entry.extra_fields.push(ExtraField::Crc32c(field));
+        }
 
-        let mut zip64_extra_field_builder = None;
+        let compressed_data = compress_or_reuse(self.writer, &self.entry, self.data).await?;
+        self.entry.set_eos_marker();
+
+        let crc = if self.entry.skip_crc { 0 } else { crc32fast::hash(self.data) };
+        write_compressed(self.writer, self.entry, &compressed_data, crc, self.data.len() as u64).await
+    }
+}
+
+/// Compresses the data as per the entry's compression method, reusing the compressed data of an earlier entry with
+/// identical contents if deduplication is enabled.
+async fn compress_or_reuse<'a, W: AsyncWrite + Unpin>(
+    writer: &mut ZipFileWriter<W>,
+    entry: &ZipEntry,
+    data: &'a [u8],
+) -> Result<Cow<'a, [u8]>> {
+    // Stored data is borrowed as-is, so there's nothing to save by deduplicating it.
+    #[cfg(feature = "sha2")]
+    if let Some(dedupe) = writer.dedupe.as_mut().filter(|_| entry.compression() != Compression::Stored) {
+        let key = (entry.compression().into(), Sha256::digest(data).into());
+
+        if let Some(compressed_data) = dedupe.get(&key) {
+            return Ok(Cow::Owned(compressed_data.clone()));
+        }
+
+        let compressed_data = compress_data(entry, data).await?;
+        dedupe.insert(key, compressed_data.to_vec());
+        return Ok(compressed_data);
+    }
 
-        let (lfh_uncompressed_size, lfh_compressed_size) = if self.data.len() as u64 > NON_ZIP64_MAX_SIZE as u64
-            || compressed_data.len() as u64 > NON_ZIP64_MAX_SIZE as u64
-        {
-            if self.writer.force_no_zip64 {
+    #[cfg(not(feature = "sha2"))]
+    let _ = writer;
+    compress_data(entry, data).await
+}
+
+/// Compresses the data as per the entry's compression method, or borrows it as-is if it should be stored.
+pub(crate) async fn compress_data<'a>(entry: &ZipEntry, data: &'a [u8]) -> Result<Cow<'a, [u8]>> {
+    Ok(match entry.compression() {
+        Compression::Stored => Cow::Borrowed(data),
+        #[cfg(feature = "deflate64")]
+        Compression::Deflate64 => return Err(ZipError::FeatureNotSupported("writing deflate64 compressed entries")),
+        #[cfg(feature = "legacy")]
+        Compression::Implode => return Err(ZipError::FeatureNotSupported("writing implode compressed entries")),
+        Compression::Custom(method) => {
+            let codec = entry.custom_codec()?.ok_or(ZipError::CompressionNotSupported(method))?;
+            let mut encoder = CodecEncoder::new(Vec::new(), codec.encoder());
+            encoder.write_all(data).await?;
+            encoder.close().await?;
+            Cow::Owned(encoder.into_inner())
+        }
+        #[cfg(any(
+            feature = "deflate",
+            feature = "bzip2",
+            feature = "zstd",
+            feature = "lzma",
+            feature = "xz",
+            feature = "deflate64"
+        ))]
+        _ => Cow::Owned(compress(entry.compression(), data, entry.compression_level).await),
+    })
+}
+
+/// Writes an entry whose data has already been compressed, along with its local file header, and records its central
+/// directory header.
+///
+/// Any validation of the entry, as well as options which depend on its uncompressed data, must be handled beforehand.
+pub(crate) async fn write_compressed<W: AsyncWrite + Unpin>(
+    writer: &mut ZipFileWriter<W>,
+    mut entry: ZipEntry,
+    compressed_data: &[u8],
+    crc: u32,
+    uncompressed_size: u64,
+) -> Result<()> {
+    writer.apply_unicode_strategy(&mut entry);
+    writer.apply_extended_timestamp(&mut entry);
+    let mut zip64_extra_field_builder = None;
+    let offset = writer.writer.offset();
+
+    let (lfh_uncompressed_size, lfh_compressed_size) =
+        if uncompressed_size > NON_ZIP64_MAX_SIZE as u64 || compressed_data.len() as u64 > NON_ZIP64_MAX_SIZE as u64 {
+            if writer.force_no_zip64 {
                 return Err(ZipError::Zip64Needed(Zip64ErrorCase::LargeFile));
             }
-            if !self.writer.is_zip64 {
-                self.writer.is_zip64 = true;
+            if !writer.is_zip64 {
+                writer.is_zip64 = true;
             }
             zip64_extra_field_builder = Some(
-                Zip64ExtendedInformationExtraFieldBuilder::new()
-                    .sizes(compressed_data.len() as u64, self.data.len() as u64),
+                Zip64ExtendedInformationExtraFieldBuilder::new().sizes(compressed_data.len() as u64, uncompressed_size),
             );
             (NON_ZIP64_MAX_SIZE, NON_ZIP64_MAX_SIZE)
         } else {
-            (self.data.len() as u32, compressed_data.len() as u32)
+            (uncompressed_size as u32, compressed_data.len() as u32)
         };
 
-        let lh_offset = if self.writer.writer.offset() > NON_ZIP64_MAX_SIZE as u64 {
-            if self.writer.force_no_zip64 {
-                return Err(ZipError::Zip64Needed(Zip64ErrorCase::LargeFile));
-            }
-            if !self.writer.is_zip64 {
-                self.writer.is_zip64 = true;
-            }
+    let lh_offset = if offset > NON_ZIP64_MAX_SIZE as u64 {
+        if writer.force_no_zip64 {
+            return Err(ZipError::Zip64Needed(Zip64ErrorCase::LargeFile));
+        }
+        if !writer.is_zip64 {
+            writer.is_zip64 = true;
+        }
 
-            if let Some(zip64_extra_field) = zip64_extra_field_builder {
-                zip64_extra_field_builder = Some(zip64_extra_field.relative_header_offset(self.writer.writer.offset()));
-            } else {
-                zip64_extra_field_builder = Some(
-                    Zip64ExtendedInformationExtraFieldBuilder::new()
-                        .relative_header_offset(self.writer.writer.offset()),
-                );
-            }
-            NON_ZIP64_MAX_SIZE
+        if let Some(zip64_extra_field) = zip64_extra_field_builder {
+            zip64_extra_field_builder = Some(zip64_extra_field.relative_header_offset(offset));
         } else {
-            self.writer.writer.offset() as u32
-        };
+            zip64_extra_field_builder =
+                Some(Zip64ExtendedInformationExtraFieldBuilder::new().relative_header_offset(offset));
+        }
+        NON_ZIP64_MAX_SIZE
+    } else {
+        offset as u32
+    };
 
-        if let Some(builder) = zip64_extra_field_builder {
-            if !builder.eof_only() {
-                self.entry.extra_fields.push(ExtraField::Zip64ExtendedInformation(builder.build()?));
-                zip64_extra_field_builder = None;
-            } else {
-                zip64_extra_field_builder = Some(builder);
-            }
+    if let Some(builder) = zip64_extra_field_builder {
+        if !builder.eof_only() {
+            entry.extra_fields.push(ExtraField::Zip64ExtendedInformation(builder.build()?));
+            zip64_extra_field_builder = None;
+        } else {
+            zip64_extra_field_builder = Some(builder);
+        }
+    }
+
+    let utf8_without_alternative =
+        entry.filename().is_utf8_without_alternative() && entry.comment().is_utf8_without_alternative();
+    put_unicode_extra_fields(&mut entry);
+
+    let alignment = writer.mmap_alignment(&entry);
+    if let Some(alignment) = alignment {
+        push_alignment_extra_field(&mut entry, offset, alignment);
+    }
+
+    let filename_basic = entry.filename().alternative().unwrap_or_else(|| entry.filename().as_bytes());
+    let comment_basic = entry.comment().alternative().unwrap_or_else(|| entry.comment().as_bytes());
+
+    let lf_header = LocalFileHeader {
+        compressed_size: lfh_compressed_size,
+        uncompressed_size: lfh_uncompressed_size,
+        compression: entry.compression().into(),
+        crc,
+        extra_field_length: entry.extra_fields().count_bytes().try_into().map_err(|_| ZipError::ExtraFieldTooLarge)?,
+        file_name_length: filename_basic.len().try_into().map_err(|_| ZipError::FileNameTooLarge)?,
+        mod_time: entry.last_modification_date().time,
+        mod_date: entry.last_modification_date().date,
+        version: entry.version_needed_to_extract(),
+        flags: GeneralPurposeFlag {
+            data_descriptor: false,
+            encrypted: false,
+            filename_unicode: entry.utf8_flag.unwrap_or(utf8_without_alternative),
+            compression_option_1: entry.has_eos_marker(),
+            compression_option_2: false,
+            strong_encryption: false,
+        },
+    };
+
+    let mut header = CentralDirectoryRecord {
+        v_made_by: crate::spec::version::as_made_by(entry.attribute_compatibility()),
+        v_needed: lf_header.version,
+        compressed_size: lf_header.compressed_size,
+        uncompressed_size: lf_header.uncompressed_size,
+        compression: lf_header.compression,
+        crc: lf_header.crc,
+        extra_field_length: lf_header.extra_field_length,
+        file_name_length: lf_header.file_name_length,
+        file_comment_length: comment_basic.len().try_into().map_err(|_| ZipError::CommentTooLarge)?,
+        mod_time: lf_header.mod_time,
+        mod_date: lf_header.mod_date,
+        flags: lf_header.flags,
+        disk_start: 0,
+        inter_attr: entry.internal_file_attribute(),
+        exter_attr: entry.external_file_attribute(),
+        lh_offset,
+    };
+
+    writer.writer.write_all(&crate::spec::consts::LFH_SIGNATURE.to_le_bytes()).await?;
+    writer.writer.write_all(&lf_header.as_slice()).await?;
+    writer.writer.write_all(filename_basic).await?;
+    writer.writer.write_all(&entry.extra_fields().as_bytes()).await?;
+    writer.writer.write_all(compressed_data).await?;
+
+    // The alignment padding is only meaningful within the local file header, so it's omitted from the central
+    // directory.
+    if alignment.is_some() {
+        strip_alignment_extra_field(&mut entry);
+    }
+    if let Some(builder) = zip64_extra_field_builder {
+        entry.extra_fields.push(ExtraField::Zip64ExtendedInformation(builder.build()?));
+    }
+    header.extra_field_length =
+        entry.extra_fields().count_bytes().try_into().map_err(|_| ZipError::ExtraFieldTooLarge)?;
+
+    writer.cd_entries.push(CentralDirectoryEntry { header, entry });
+    record_last_name(&mut writer.names, &writer.cd_entries);
+    // Ensure that we can fit this many files in this archive if forcing no zip64
+    if writer.cd_entries.len() > NON_ZIP64_MAX_NUM_FILES as usize {
+        if writer.force_no_zip64 {
+            return Err(ZipError::Zip64Needed(Zip64ErrorCase::TooManyFiles));
         }
+        if !writer.is_zip64 {
+            writer.is_zip64 = true;
+        }
+    }
+    Ok(())
+}
 
-        let utf8_without_alternative =
-            self.entry.filename().is_utf8_without_alternative() && self.entry.comment().is_utf8_without_alternative();
-        if !utf8_without_alternative {
-            if matches!(self.entry.filename().encoding(), StringEncoding::Utf8) {
-                let u_file_name = self.entry.filename().as_bytes().to_vec();
-                if !u_file_name.is_empty() {
-                    let basic_crc32 = crc32fast::hash(
-                        self.entry.filename().alternative().unwrap_or_else(|| self.entry.filename().as_bytes()),
-                    );
-                    let upath_field =
-                        get_or_put_info_zip_unicode_path_extra_field_mut(self.entry.extra_fields.as_mut());
-                    if let InfoZipUnicodePathExtraField::V1 { crc32, unicode } = upath_field {
-                        *crc32 = basic_crc32;
-                        *unicode = u_file_name;
-                    }
+/// Populates the Info-ZIP Unicode extra fields for a filename or comment which has a non-UTF-8 alternative.
+pub(crate) fn put_unicode_extra_fields(entry: &mut ZipEntry) {
+    let utf8_without_alternative =
+        entry.filename().is_utf8_without_alternative() && entry.comment().is_utf8_without_alternative();
+    if !utf8_without_alternative {
+        if matches!(entry.filename().encoding(), StringEncoding::Utf8) {
+            let u_file_name = entry.filename().as_bytes().to_vec();
+            if !u_file_name.is_empty() {
+                let basic_crc32 =
+                    crc32fast::hash(entry.filename().alternative().unwrap_or_else(|| entry.filename().as_bytes()));
+                let upath_field = get_or_put_info_zip_unicode_path_extra_field_mut(entry.extra_fields.as_mut());
+                if let InfoZipUnicodePathExtraField::V1 { crc32, unicode } = upath_field {
+                    *crc32 = basic_crc32;
+                    *unicode = u_file_name;
                 }
             }
-            if matches!(self.entry.comment().encoding(), StringEncoding::Utf8) {
-                let u_comment = self.entry.comment().as_bytes().to_vec();
-                if !u_comment.is_empty() {
-                    let basic_crc32 = crc32fast::hash(
-                        self.entry.comment().alternative().unwrap_or_else(|| self.entry.comment().as_bytes()),
-                    );
-                    let ucom_field =
-                        get_or_put_info_zip_unicode_comment_extra_field_mut(self.entry.extra_fields.as_mut());
-                    if let InfoZipUnicodeCommentExtraField::V1 { crc32, unicode } = ucom_field {
-                        *crc32 = basic_crc32;
-                        *unicode = u_comment;
-                    }
+        }
+        if matches!(entry.comment().encoding(), StringEncoding::Utf8) {
+            let u_comment = entry.comment().as_bytes().to_vec();
+            if !u_comment.is_empty() {
+                let basic_crc32 =
+                    crc32fast::hash(entry.comment().alternative().unwrap_or_else(|| entry.comment().as_bytes()));
+                let ucom_field = get_or_put_info_zip_unicode_comment_extra_field_mut(entry.extra_fields.as_mut());
+                if let InfoZipUnicodeCommentExtraField::V1 { crc32, unicode } = ucom_field {
+                    *crc32 = basic_crc32;
+                    *unicode = u_comment;
                 }
             }
         }
+    }
+}
 
-        let filename_basic = self.entry.filename().alternative().unwrap_or_else(|| self.entry.filename().as_bytes());
-        let comment_basic = self.entry.comment().alternative().unwrap_or_else(|| self.entry.comment().as_bytes());
-
-        let lf_header = LocalFileHeader {
-            compressed_size: lfh_compressed_size,
-            uncompressed_size: lfh_uncompressed_size,
-            compression: self.entry.compression().into(),
-            crc: crc32fast::hash(self.data),
-            extra_field_length: self
-                .entry
-                .extra_fields()
-                .count_bytes()
-                .try_into()
-                .map_err(|_| ZipError::ExtraFieldTooLarge)?,
-            file_name_length: filename_basic.len().try_into().map_err(|_| ZipError::FileNameTooLarge)?,
-            mod_time: self.entry.last_modification_date().time,
-            mod_date: self.entry.last_modification_date().date,
-            version: crate::spec::version::as_needed_to_extract(&self.entry),
-            flags: GeneralPurposeFlag {
-                data_descriptor: false,
-                encrypted: false,
-                filename_unicode: utf8_without_alternative,
-            },
-        };
+/// Pushes an alignment extra field which pads the entry's data to a multiple of `alignment` bytes, given the offset at
+/// which its local file header will be written.
+///
+/// The field follows Android's zipalign: a 2 byte alignment value followed by the null padding.
+pub(crate) fn push_alignment_extra_field(entry: &mut ZipEntry, lfh_offset: u64, alignment: u16) {
+    let filename_length = entry.filename().alternative().unwrap_or_else(|| entry.filename().as_bytes()).len();
+    let data_offset = lfh_offset + (SIGNATURE_LENGTH + LFH_LENGTH + filename_length) as u64;
+    let unpadded_offset = data_offset + entry.extra_fields().count_bytes() as u64 + 6;
 
-        let mut header = CentralDirectoryRecord {
-            v_made_by: crate::spec::version::as_made_by(),
-            v_needed: lf_header.version,
-            compressed_size: lf_header.compressed_size,
-            uncompressed_size: lf_header.uncompressed_size,
-            compression: lf_header.compression,
-            crc: lf_header.crc,
-            extra_field_length: lf_header.extra_field_length,
-            file_name_length: lf_header.file_name_length,
-            file_comment_length: comment_basic.len().try_into().map_err(|_| ZipError::CommentTooLarge)?,
-            mod_time: lf_header.mod_time,
-            mod_date: lf_header.mod_date,
-            flags: lf_header.flags,
-            disk_start: 0,
-            inter_attr: self.entry.internal_file_attribute(),
-            exter_attr: self.entry.external_file_attribute(),
-            lh_offset,
-        };
+    let alignment_u64 = alignment as u64;
+    let padding = (alignment_u64 - unpadded_offset % alignment_u64) % alignment_u64;
 
-        self.writer.writer.write_all(&crate::spec::consts::LFH_SIGNATURE.to_le_bytes()).await?;
-        self.writer.writer.write_all(&lf_header.as_slice()).await?;
-        self.writer.writer.write_all(filename_basic).await?;
-        self.writer.writer.write_all(&self.entry.extra_fields().as_bytes()).await?;
-        self.writer.writer.write_all(compressed_data).await?;
+    let mut content = alignment.to_le_bytes().to_vec();
+    content.resize(2 + padding as usize, 0);
 
-        if let Some(builder) = zip64_extra_field_builder {
-            self.entry.extra_fields.push(ExtraField::Zip64ExtendedInformation(builder.build()?));
-            header.extra_field_length =
-                self.entry.extra_fields().count_bytes().try_into().map_err(|_| ZipError::ExtraFieldTooLarge)?;
-        }
+    entry.extra_fields.push(ExtraField::Unknown(UnknownExtraField {
+        header_id: HeaderId::ALIGNMENT_EXTRA_FIELD,
+        data_size: content.len() as u16,
+        content,
+    }));
+}
 
-        self.writer.cd_entries.push(CentralDirectoryEntry { header, entry: self.entry });
-        // Ensure that we can fit this many files in this archive if forcing no zip64
-        if self.writer.cd_entries.len() > NON_ZIP64_MAX_NUM_FILES as usize {
-            if self.writer.force_no_zip64 {
-                return Err(ZipError::Zip64Needed(Zip64ErrorCase::TooManyFiles));
-            }
-            if !self.writer.is_zip64 {
-                self.writer.is_zip64 = true;
-            }
+/// Removes any alignment extra field from the entry.
+pub(crate) fn strip_alignment_extra_field(entry: &mut ZipEntry) {
+    entry.extra_fields.retain(|field| match field {
+        ExtraField::Unknown(field) => field.header_id != HeaderId::ALIGNMENT_EXTRA_FIELD,
+        _ => true,
+    });
+}
+
+/// Returns whether the data looks like text, using the same heuristic as Info-ZIP.
+///
+/// Data is considered text if it contains at least one tab, line feed, carriage return, or byte of 32 and above, and
+/// none of the control characters 0-6, 14-25, or 28-31. The remaining control characters (7, 8, 11, 12, 26 & 27) are
+/// allowed but don't count as text on their own, so empty data or data consisting only of them is considered binary.
+pub(crate) fn is_text(data: &[u8]) -> bool {
+    let mut text = false;
+
+    for byte in data {
+        match byte {
+            0..=6 | 14..=25 | 28..=31 => return false,
+            9 | 10 | 13 | 32.. => text = true,
+            _ => (),
         }
-        Ok(())
     }
+
+    text
 }
 
 #[cfg(any(
@@ -224,8 +369,6 @@ async fn compress(compression: Compression, data: &[u8], level: async_compressio
             writer.close().await.unwrap();
             writer.into_inner().into_inner()
         }
-        #[cfg(feature = "deflate64")]
-        Compression::Deflate64 => panic!("compressing deflate64 is not supported"),
         #[cfg(feature = "bzip2")]
         Compression::Bz => {
             let mut writer = write::BzEncoder::with_quality(Cursor::new(Vec::new()), level);
@@ -235,10 +378,11 @@ async fn compress(compression: Compression, data: &[u8], level: async_compressio
         }
         #[cfg(feature = "lzma")]
         Compression::Lzma => {
-            let mut writer = write::LzmaEncoder::with_quality(Cursor::new(Vec::new()), level);
+            let inner = crate::base::write::io::lzma::LzmaHeaderWriter::new(Cursor::new(Vec::new()));
+            let mut writer = write::LzmaEncoder::with_quality(inner, level);
             writer.write_all(data).await.unwrap();
             writer.close().await.unwrap();
-            writer.into_inner().into_inner()
+            writer.into_inner().into_inner().into_inner()
         }
         #[cfg(feature = "xz")]
         Compression::Xz => {
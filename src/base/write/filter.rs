@@ -0,0 +1,78 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+//! A module which supports rewriting an existing ZIP file with a subset of its entries.
+
+use crate::base::read::seek::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::entry::StoredZipEntry;
+use crate::error::Result;
+
+use futures_lite::io::{AsyncBufRead, AsyncReadExt, AsyncSeek, AsyncWrite};
+
+/// Copies the entries of a ZIP file which match the provided predicate into the provided writer, skipping the rest.
+///
+/// Each kept entry's compressed data is copied as-is alongside its CRC32 value and sizes, so no decompression or
+/// recompression takes place, though the data of a single entry is held in memory at once. The entries are written
+/// with fresh local file headers, so their offsets within the new central directory reflect any removed entries.
+/// Each entry's filename, comment, last modification date, attributes, and extra fields are preserved, as is the
+/// archive comment.
+///
+/// Encrypted entries can't be copied, so keeping one will return an error. The writer isn't closed by this function,
+/// so further entries may be written before doing so.
+///
+/// # Example
+/// ```no_run
+/// # use async_zip::base::read::seek::ZipFileReader;
+/// # use async_zip::base::write::{filter_archive, ZipFileWriter};
+/// # use async_zip::error::ZipError;
+/// # use futures_lite::io::Cursor;
+/// #
+/// # async fn run(data: Vec<u8>) -> Result<(), ZipError> {
+/// let mut reader = ZipFileReader::new(Cursor::new(data)).await?;
+/// let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+///
+/// // Remove any stale log files.
+/// filter_archive(&mut reader, &mut writer, |entry| !entry.filename().as_bytes().ends_with(b".log")).await?;
+/// let data = writer.close().await?;
+/// #   Ok(())
+/// # }
+/// ```
+pub async fn filter_archive<R, W, F>(
+    reader: &mut ZipFileReader<R>,
+    writer: &mut ZipFileWriter<W>,
+    keep: F,
+) -> Result<()>
+where
+    R: AsyncBufRead + AsyncSeek + Unpin,
+    W: AsyncWrite + Unpin,
+    F: Fn(&StoredZipEntry) -> bool,
+{
+    let comment = reader.file().comment();
+    writer.comment_bytes(comment.alternative().unwrap_or_else(|| comment.as_bytes()).to_vec());
+
+    let mut buffer = Vec::new();
+
+    for index in 0..reader.file().entries().len() {
+        let stored_entry = reader.file().entries()[index].clone();
+
+        if !keep(&stored_entry) {
+            continue;
+        }
+
+        stored_entry.seek_to_data_offset(reader.inner_mut()).await?;
+
+        buffer.clear();
+        let compressed_size = stored_entry.compressed_size();
+        (&mut reader.inner_mut()).take(compressed_size).read_to_end(&mut buffer).await?;
+
+        if (buffer.len() as u64) < compressed_size {
+            return Err(std::io::Error::from(std::io::ErrorKind::UnexpectedEof).into());
+        }
+
+        let (crc32, uncompressed_size) = (stored_entry.crc32(), stored_entry.uncompressed_size());
+        writer.write_entry_precompressed(stored_entry.to_entry(), &buffer, crc32, uncompressed_size).await?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,119 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+//! A module which supports writing archives containing only a central directory, whose entries' data lives elsewhere.
+
+use crate::base::write::entry_whole::put_unicode_extra_fields;
+use crate::base::write::{CentralDirectoryEntry, ZipFileWriter};
+use crate::entry::StoredZipEntry;
+use crate::error::{Result, Zip64ErrorCase, ZipError};
+use crate::spec::consts::{NON_ZIP64_MAX_NUM_FILES, NON_ZIP64_MAX_SIZE};
+use crate::spec::extra_field::{ExtraFieldAsBytes, Zip64ExtendedInformationExtraFieldBuilder};
+use crate::spec::header::{CentralDirectoryRecord, ExtraField, GeneralPurposeFlag};
+
+use futures_lite::io::AsyncWrite;
+
+impl<W: AsyncWrite + Unpin> ZipFileWriter<W> {
+    /// Consumes this ZIP writer and writes a central directory (and end of central directory record) listing the
+    /// provided entries, whose local file headers & data live outside of this archive.
+    ///
+    /// Each entry's header offset, CRC32 value, and sizes are written as-is, so the entries are typically those of an
+    /// archive holding the data (eg. as listed by a reader of it, or via [`ZipFileWriter::checkpoint()`]). This
+    /// supports two-file layouts where a small index is distributed separately from a large blob of entry data.
+    ///
+    /// The resulting archive isn't self-contained: its entries can only be read by combining its listing with a reader
+    /// over the data (eg. via [`seek::ZipFileReader::from_raw_parts()`]), and other tools will consider it corrupt.
+    /// Any entries already written to this writer are listed too, so this should usually be called on a new writer.
+    ///
+    /// [`seek::ZipFileReader::from_raw_parts()`]: crate::base::read::seek::ZipFileReader::from_raw_parts
+    pub async fn write_index_only<I>(mut self, entries: I) -> Result<W>
+    where
+        I: IntoIterator<Item = StoredZipEntry>,
+    {
+        for stored in entries {
+            let (version_made_by, offset) = (stored.version_made_by, stored.file_offset);
+            let mut entry = stored.entry;
+
+            self.apply_unicode_strategy(&mut entry);
+            self.apply_extended_timestamp(&mut entry);
+            put_unicode_extra_fields(&mut entry);
+            entry.extra_fields.retain(|field| !matches!(field, ExtraField::Zip64ExtendedInformation(_)));
+
+            let large_size = entry.compressed_size > NON_ZIP64_MAX_SIZE as u64
+                || entry.uncompressed_size > NON_ZIP64_MAX_SIZE as u64;
+            let large_offset = offset > NON_ZIP64_MAX_SIZE as u64;
+
+            if large_size || large_offset {
+                if self.force_no_zip64 {
+                    return Err(ZipError::Zip64Needed(Zip64ErrorCase::LargeFile));
+                }
+                self.is_zip64 = true;
+
+                let mut builder = Zip64ExtendedInformationExtraFieldBuilder::new();
+                if large_size {
+                    builder = builder.sizes(entry.compressed_size, entry.uncompressed_size);
+                }
+                if large_offset {
+                    builder = builder.relative_header_offset(offset);
+                }
+                entry.extra_fields.push(ExtraField::Zip64ExtendedInformation(builder.build()?));
+            }
+
+            let (compressed_size, uncompressed_size) = match large_size {
+                true => (NON_ZIP64_MAX_SIZE, NON_ZIP64_MAX_SIZE),
+                false => (entry.compressed_size as u32, entry.uncompressed_size as u32),
+            };
+
+            let utf8_without_alternative =
+                entry.filename().is_utf8_without_alternative() && entry.comment().is_utf8_without_alternative();
+            let filename_basic = entry.filename().alternative().unwrap_or_else(|| entry.filename().as_bytes());
+            let comment_basic = entry.comment().alternative().unwrap_or_else(|| entry.comment().as_bytes());
+
+            #[cfg(feature = "legacy")]
+            let (implode_8k_dictionary, implode_literal_tree) =
+                (entry.implode_options.large_dictionary, entry.implode_options.literal_tree);
+            #[cfg(not(feature = "legacy"))]
+            let (implode_8k_dictionary, implode_literal_tree) = (false, false);
+
+            let header = CentralDirectoryRecord {
+                v_made_by: version_made_by,
+                v_needed: entry.version_needed_to_extract(),
+                flags: GeneralPurposeFlag {
+                    data_descriptor: entry.data_descriptor,
+                    encrypted: entry.encrypted,
+                    filename_unicode: entry.utf8_flag.unwrap_or(utf8_without_alternative),
+                    compression_option_1: entry.has_eos_marker() || implode_8k_dictionary,
+                    compression_option_2: implode_literal_tree,
+                    strong_encryption: entry.strong_encryption,
+                },
+                compression: entry.raw_compression(),
+                mod_time: entry.last_modification_date().time,
+                mod_date: entry.last_modification_date().date,
+                crc: entry.crc32(),
+                compressed_size,
+                uncompressed_size,
+                file_name_length: filename_basic.len().try_into().map_err(|_| ZipError::FileNameTooLarge)?,
+                extra_field_length: entry
+                    .extra_fields()
+                    .count_bytes()
+                    .try_into()
+                    .map_err(|_| ZipError::ExtraFieldTooLarge)?,
+                file_comment_length: comment_basic.len().try_into().map_err(|_| ZipError::CommentTooLarge)?,
+                disk_start: 0,
+                inter_attr: entry.internal_file_attribute(),
+                exter_attr: entry.external_file_attribute(),
+                lh_offset: if large_offset { NON_ZIP64_MAX_SIZE } else { offset as u32 },
+            };
+
+            self.cd_entries.push(CentralDirectoryEntry { header, entry });
+            if self.cd_entries.len() > NON_ZIP64_MAX_NUM_FILES as usize {
+                if self.force_no_zip64 {
+                    return Err(ZipError::Zip64Needed(Zip64ErrorCase::TooManyFiles));
+                }
+                self.is_zip64 = true;
+            }
+        }
+
+        self.close().await
+    }
+}
@@ -0,0 +1,103 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use std::future::Future;
+use std::io::Error;
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use futures_lite::io::{AsyncWrite, AsyncWriteExt};
+
+/// A wrapper which buffers written data and passes each completed chunk of a fixed size to the provided closure.
+///
+/// The closure is passed the absolute offset at which the chunk starts, followed by its data, and the future it
+/// returns is awaited before any further data is buffered. Any remaining data is only passed on once
+/// [`ChunkWriter::finish()`] is called.
+pub struct ChunkWriter<F, Fut> {
+    buffer: Vec<u8>,
+    chunk_size: usize,
+    offset: u64,
+    chunk: F,
+    pending: Option<Pin<Box<Fut>>>,
+}
+
+impl<F, Fut> ChunkWriter<F, Fut>
+where
+    F: FnMut(u64, Vec<u8>) -> Fut + Unpin,
+    Fut: Future<Output = std::io::Result<()>>,
+{
+    /// Constructs a new wrapper which will pass chunks of `chunk_size` bytes to `chunk`.
+    pub fn new(chunk_size: usize, chunk: F) -> Self {
+        Self { buffer: Vec::with_capacity(chunk_size), chunk_size, offset: 0, chunk, pending: None }
+    }
+
+    /// Returns the size of each completed chunk.
+    pub fn chunk_size(&self) -> usize {
+        self.chunk_size
+    }
+
+    /// Returns the number of bytes which have been buffered but not yet passed on.
+    pub fn buffered(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Passes the buffered data on as the final chunk (if any), and returns the total number of bytes written.
+    pub async fn finish(&mut self) -> std::io::Result<u64> {
+        self.close().await?;
+        Ok(self.offset)
+    }
+
+    /// Passes the buffered data on as a chunk and starts a new one.
+    fn emit(&mut self) {
+        let data = std::mem::replace(&mut self.buffer, Vec::with_capacity(self.chunk_size));
+        let offset = self.offset;
+        self.offset += data.len() as u64;
+
+        self.pending = Some(Box::pin((self.chunk)(offset, data)));
+    }
+
+    /// Awaits the future returned for the most recently passed on chunk, if it hasn't yet completed.
+    fn poll_pending(&mut self, cx: &mut Context) -> Poll<Result<(), Error>> {
+        if let Some(pending) = self.pending.as_mut() {
+            let result = ready!(pending.as_mut().poll(cx));
+            self.pending = None;
+            result?;
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<F, Fut> AsyncWrite for ChunkWriter<F, Fut>
+where
+    F: FnMut(u64, Vec<u8>) -> Fut + Unpin,
+    Fut: Future<Output = std::io::Result<()>>,
+{
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context, buf: &[u8]) -> Poll<Result<usize, Error>> {
+        let this = self.get_mut();
+        ready!(this.poll_pending(cx))?;
+
+        let length = std::cmp::min(buf.len(), this.chunk_size - this.buffer.len());
+        this.buffer.extend_from_slice(&buf[..length]);
+
+        if this.buffer.len() == this.chunk_size {
+            this.emit();
+        }
+
+        Poll::Ready(Ok(length))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Error>> {
+        self.get_mut().poll_pending(cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Error>> {
+        let this = self.get_mut();
+        ready!(this.poll_pending(cx))?;
+
+        if !this.buffer.is_empty() {
+            this.emit();
+            ready!(this.poll_pending(cx))?;
+        }
+        Poll::Ready(Ok(()))
+    }
+}
@@ -0,0 +1,49 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::codec::Encoder;
+
+use std::io::Error;
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use futures_lite::io::AsyncWrite;
+
+/// A writer which encodes data via a custom [`Encoder`] before writing it to an inner [`AsyncWrite`] implementer.
+///
+/// Closing this writer finishes encoding but doesn't close the inner writer.
+pub struct CodecEncoder<W> {
+    writer: W,
+    encoder: Box<dyn Encoder>,
+}
+
+impl<W: AsyncWrite + Unpin> CodecEncoder<W> {
+    /// Constructs a new encoder which writes to the provided writer.
+    pub fn new(writer: W, encoder: Box<dyn Encoder>) -> Self {
+        Self { writer, encoder }
+    }
+
+    /// Consumes this encoder and returns the inner writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: AsyncWrite + Unpin> AsyncWrite for CodecEncoder<W> {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context, buf: &[u8]) -> Poll<Result<usize, Error>> {
+        let this = self.get_mut();
+        let writer = Pin::new(&mut this.writer);
+        this.encoder.poll_encode(cx, writer, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Error>> {
+        Pin::new(&mut self.get_mut().writer).poll_flush(cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Error>> {
+        let this = self.get_mut();
+        let writer = Pin::new(&mut this.writer);
+        ready!(this.encoder.poll_finish(cx, writer))?;
+        Pin::new(&mut this.writer).poll_flush(cx)
+    }
+}
@@ -0,0 +1,91 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::spec::consts::{LZMA_ALONE_HEADER_LENGTH, LZMA_PROPERTIES_LENGTH, LZMA_SDK_VERSION, LZMA_ZIP_HEADER_LENGTH};
+
+use std::io::Error;
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use futures_lite::io::AsyncWrite;
+
+/// A wrapping writer which converts the output of an LZMA encoder from the standalone `.lzma` format into the framing
+/// expected within ZIP entries.
+///
+/// The standalone format begins with the LZMA properties followed by an 8 byte uncompressed size, whereas ZIP entries
+/// instead begin with the LZMA SDK version and the length of the properties, followed by the properties themselves.
+/// All data after the header is passed through untouched.
+pub struct LzmaHeaderWriter<W> {
+    inner: W,
+    alone_header: Vec<u8>,
+    zip_header: [u8; LZMA_ZIP_HEADER_LENGTH],
+    zip_header_written: usize,
+}
+
+impl<W: AsyncWrite + Unpin> LzmaHeaderWriter<W> {
+    /// Constructs a new wrapping writer from an inner [`AsyncWrite`] writer.
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner,
+            alone_header: Vec::with_capacity(LZMA_ALONE_HEADER_LENGTH),
+            zip_header: [0; LZMA_ZIP_HEADER_LENGTH],
+            zip_header_written: LZMA_ZIP_HEADER_LENGTH,
+        }
+    }
+
+    /// Consumes this wrapper and returns the inner [`AsyncWrite`] writer.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    /// Writes any of the ZIP-specific header which hasn't yet been written to the inner writer.
+    fn poll_write_header(&mut self, cx: &mut Context) -> Poll<Result<(), Error>> {
+        while self.zip_header_written < LZMA_ZIP_HEADER_LENGTH {
+            let remaining = &self.zip_header[self.zip_header_written..];
+            let written = ready!(Pin::new(&mut self.inner).poll_write(cx, remaining))?;
+
+            if written == 0 {
+                return Poll::Ready(Err(Error::from(std::io::ErrorKind::WriteZero)));
+            }
+            self.zip_header_written += written;
+        }
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<W: AsyncWrite + Unpin> AsyncWrite for LzmaHeaderWriter<W> {
+    fn poll_write(mut self: Pin<&mut Self>, cx: &mut Context, buf: &[u8]) -> Poll<Result<usize, Error>> {
+        ready!(self.poll_write_header(cx))?;
+
+        if self.alone_header.len() < LZMA_ALONE_HEADER_LENGTH {
+            let length = std::cmp::min(LZMA_ALONE_HEADER_LENGTH - self.alone_header.len(), buf.len());
+            self.alone_header.extend_from_slice(&buf[..length]);
+
+            // Once the standalone header is complete, queue the equivalent ZIP header to be written.
+            if self.alone_header.len() == LZMA_ALONE_HEADER_LENGTH {
+                let mut zip_header = [0; LZMA_ZIP_HEADER_LENGTH];
+                zip_header[0..2].copy_from_slice(&LZMA_SDK_VERSION);
+                zip_header[2..4].copy_from_slice(&(LZMA_PROPERTIES_LENGTH as u16).to_le_bytes());
+                zip_header[4..].copy_from_slice(&self.alone_header[..LZMA_PROPERTIES_LENGTH]);
+
+                self.zip_header = zip_header;
+                self.zip_header_written = 0;
+            }
+
+            return Poll::Ready(Ok(length));
+        }
+
+        Pin::new(&mut self.inner).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Error>> {
+        ready!(self.poll_write_header(cx))?;
+        Pin::new(&mut self.inner).poll_flush(cx)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Error>> {
+        ready!(self.poll_write_header(cx))?;
+        Pin::new(&mut self.inner).poll_close(cx)
+    }
+}
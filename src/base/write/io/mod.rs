@@ -1,4 +1,9 @@
 // Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
+pub(crate) mod chunked;
+pub(crate) mod codec;
+#[cfg(feature = "lzma")]
+pub(crate) mod lzma;
 pub(crate) mod offset;
+pub(crate) mod split;
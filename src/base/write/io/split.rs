@@ -0,0 +1,128 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use std::future::Future;
+use std::io::Error;
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use futures_lite::io::AsyncWrite;
+
+/// A wrapper which spreads written data across multiple [`AsyncWrite`] volumes of a fixed maximum size.
+///
+/// A new volume is requested from the provided closure whenever the current one is full, with the previous volume
+/// being closed beforehand. The future returned by the closure is awaited before any further data is written.
+pub struct SplitWriter<W, F, Fut> {
+    volumes: Vec<W>,
+    starts: Vec<u64>,
+    volume_size: u64,
+    offset: u64,
+    next: F,
+    pending: Option<Pin<Box<Fut>>>,
+}
+
+impl<W, F, Fut> SplitWriter<W, F, Fut>
+where
+    W: AsyncWrite + Unpin,
+    F: FnMut(u32) -> Fut + Unpin,
+    Fut: Future<Output = std::io::Result<W>>,
+{
+    /// Constructs a new wrapper which will request volumes of at most `volume_size` bytes from `next`.
+    pub fn new(volume_size: u64, next: F) -> Self {
+        Self { volumes: Vec::new(), starts: Vec::new(), volume_size, offset: 0, next, pending: None }
+    }
+
+    /// Returns the maximum size of each volume.
+    pub fn volume_size(&self) -> u64 {
+        self.volume_size
+    }
+
+    /// Returns the number of volumes which have been requested so far.
+    pub fn num_volumes(&self) -> usize {
+        self.volumes.len()
+    }
+
+    /// Returns the number of bytes which can still be written to the current volume.
+    pub fn remaining(&self) -> u64 {
+        match self.starts.last() {
+            Some(start) => start + self.volume_size - self.offset,
+            None => 0,
+        }
+    }
+
+    /// Returns the volume number and relative offset at which the provided absolute offset was written.
+    pub fn position_of(&self, offset: u64) -> (u32, u64) {
+        let volume = self.starts.partition_point(|start| *start <= offset).saturating_sub(1);
+        let start = self.starts.get(volume).copied().unwrap_or_default();
+
+        (volume as u32, offset - start)
+    }
+
+    /// Returns the volume number and relative offset at which the next byte will be written.
+    pub fn position(&self) -> (u32, u64) {
+        if self.remaining() == 0 {
+            return (self.volumes.len() as u32, 0);
+        }
+        self.position_of(self.offset)
+    }
+
+    /// Closes the current volume (if any) and requests the next.
+    pub fn poll_next_volume(&mut self, cx: &mut Context) -> Poll<Result<(), Error>> {
+        if self.pending.is_none() {
+            if let Some(current) = self.volumes.last_mut() {
+                ready!(Pin::new(current).poll_close(cx))?;
+            }
+            self.pending = Some(Box::pin((self.next)(self.volumes.len() as u32)));
+        }
+
+        let pending = self.pending.as_mut().expect("volume requested above");
+        let result = ready!(pending.as_mut().poll(cx));
+        self.pending = None;
+
+        self.volumes.push(result?);
+        self.starts.push(self.offset);
+
+        Poll::Ready(Ok(()))
+    }
+
+    /// Consumes this wrapper and returns all volumes in order.
+    pub fn into_inner(self) -> Vec<W> {
+        self.volumes
+    }
+}
+
+impl<W, F, Fut> AsyncWrite for SplitWriter<W, F, Fut>
+where
+    W: AsyncWrite + Unpin,
+    F: FnMut(u32) -> Fut + Unpin,
+    Fut: Future<Output = std::io::Result<W>>,
+{
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context, buf: &[u8]) -> Poll<Result<usize, Error>> {
+        let this = self.get_mut();
+
+        if this.remaining() == 0 {
+            ready!(this.poll_next_volume(cx))?;
+        }
+
+        let length = std::cmp::min(buf.len() as u64, this.remaining()) as usize;
+        let current = this.volumes.last_mut().expect("volume requested above");
+        let written = ready!(Pin::new(current).poll_write(cx, &buf[..length]))?;
+        this.offset += written as u64;
+
+        Poll::Ready(Ok(written))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Error>> {
+        match self.get_mut().volumes.last_mut() {
+            Some(current) => Pin::new(current).poll_flush(cx),
+            None => Poll::Ready(Ok(())),
+        }
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Error>> {
+        match self.get_mut().volumes.last_mut() {
+            Some(current) => Pin::new(current).poll_close(cx),
+            None => Poll::Ready(Ok(())),
+        }
+    }
+}
@@ -49,23 +49,48 @@
 //! # }
 //! ```
 
+pub(crate) mod chunked;
 pub(crate) mod compressed_writer;
 pub(crate) mod entry_stream;
 pub(crate) mod entry_whole;
+pub(crate) mod filter;
+pub(crate) mod index;
 pub(crate) mod io;
+pub(crate) mod prepared;
+pub(crate) mod recompress;
+pub(crate) mod split;
 
+pub use chunked::{ChunkedZipFileWriter, MIN_CHUNK_SIZE};
+#[cfg(feature = "sha2")]
+pub use entry_stream::EntryDigest;
 pub use entry_stream::EntryStreamWriter;
+pub use filter::filter_archive;
+pub use io::chunked::ChunkWriter;
+pub use io::split::SplitWriter;
+pub use prepared::PreparedEntry;
+pub use recompress::recompress;
+pub use split::{SplitZipFileWriter, MAX_VOLUME_SIZE, MIN_VOLUME_SIZE};
 
 #[cfg(feature = "tokio")]
 use tokio_util::compat::{Compat, TokioAsyncWriteCompatExt};
 
 use crate::entry::ZipEntry;
-use crate::error::Result;
+use crate::error::{Result, Zip64ErrorCase, ZipError};
+use crate::file::ArchiveLayout;
+use crate::spec::attribute::AttributeCompatibility;
 use crate::spec::extra_field::ExtraFieldAsBytes;
+#[cfg(feature = "crc32c")]
+use crate::spec::header::Crc32cExtraField;
 use crate::spec::header::{
-    CentralDirectoryRecord, EndOfCentralDirectoryHeader, ExtraField, InfoZipUnicodeCommentExtraField,
-    InfoZipUnicodePathExtraField, Zip64EndOfCentralDirectoryLocator, Zip64EndOfCentralDirectoryRecord,
+    CentralDirectoryRecord, EndOfCentralDirectoryHeader, ExtendedTimestampExtraField, ExtraField,
+    InfoZipUnicodeCommentExtraField, InfoZipUnicodePathExtraField, Zip64EndOfCentralDirectoryLocator,
+    Zip64EndOfCentralDirectoryRecord,
 };
+use crate::spec::Compression;
+#[cfg(doc)]
+use crate::{base::read::ZipEntryReader, ZipEntryBuilder};
+use crate::{StringEncoding, ZipString};
+use crate::{ZipFile, ZipFileBuilder};
 
 #[cfg(feature = "tokio")]
 use crate::tokio::write::ZipFileWriter as TokioZipFileWriter;
@@ -73,14 +98,59 @@ use crate::tokio::write::ZipFileWriter as TokioZipFileWriter;
 use entry_whole::EntryWholeWriter;
 use io::offset::AsyncOffsetWriter;
 
-use crate::spec::consts::{NON_ZIP64_MAX_NUM_FILES, NON_ZIP64_MAX_SIZE};
-use futures_lite::io::{AsyncWrite, AsyncWriteExt};
+use crate::spec::consts::{
+    CDH_LENGTH, EOCDR_LENGTH, LFH_LENGTH, NON_ZIP64_MAX_NUM_FILES, NON_ZIP64_MAX_SIZE, SIGNATURE_LENGTH,
+    ZIP64_EOCDL_LENGTH, ZIP64_EOCDR_LENGTH,
+};
+use futures_lite::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use std::cmp::Ordering;
+#[cfg(feature = "sha2")]
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+/// The default size of the buffer used when copying data from a reader into a new entry.
+const COPY_BUFFER_SIZE: usize = 64 * 1024;
+
+/// The compression method of an entry alongside the SHA-256 digest of its uncompressed data.
+#[cfg(feature = "sha2")]
+type DedupeKey = (u16, [u8; 32]);
+
+/// How a ZIP file writer signals that an entry's filename or comment is encoded as UTF-8.
+///
+/// Extractors differ in which signals they honour. For example, Windows Explorer honours the UTF-8 general purpose
+/// flag but ignores the Info-ZIP Unicode extra fields, whereas some older Unix tools prefer the extra fields.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum UnicodeStrategy {
+    /// Always store UTF-8 filenames & comments as-is and set the UTF-8 flag, discarding any alternative encodings.
+    Utf8FlagOnly,
+    /// Never set the UTF-8 flag, instead storing non-ASCII UTF-8 filenames & comments within the Info-ZIP Unicode extra
+    /// fields. The basic filename or comment holds the alternative encoding if present, or the UTF-8 bytes otherwise.
+    ExtraFieldsOnly,
+    /// Set the UTF-8 flag for UTF-8 filenames & comments without an alternative encoding, and otherwise store the
+    /// alternative encoding alongside the Info-ZIP Unicode extra fields.
+    #[default]
+    Both,
+}
 
 pub(crate) struct CentralDirectoryEntry {
     pub header: CentralDirectoryRecord,
     pub entry: ZipEntry,
 }
 
+impl CentralDirectoryEntry {
+    /// Appends this entry's central directory header (incl. its signature, filename, extra fields, and comment).
+    fn extend_buffer(&self, buffer: &mut Vec<u8>) {
+        let filename_basic = self.entry.filename().alternative().unwrap_or_else(|| self.entry.filename().as_bytes());
+        let comment_basic = self.entry.comment().alternative().unwrap_or_else(|| self.entry.comment().as_bytes());
+
+        buffer.extend_from_slice(&crate::spec::consts::CDH_SIGNATURE.to_le_bytes());
+        buffer.extend_from_slice(&self.header.as_slice());
+        buffer.extend_from_slice(filename_basic);
+        buffer.extend_from_slice(&self.entry.extra_fields().as_bytes());
+        buffer.extend_from_slice(comment_basic);
+    }
+}
+
 /// A ZIP file writer which acts over AsyncWrite implementers.
 ///
 /// # Note
@@ -92,7 +162,172 @@ pub struct ZipFileWriter<W> {
     force_no_zip64: bool,
     /// Whether to write Zip64 end of directory structs.
     pub(crate) is_zip64: bool,
-    comment_opt: Option<String>,
+    /// Whether to set the text bit of whole entries' internal file attribute based on their data.
+    pub(crate) auto_text_detection: bool,
+    /// Whether to write a CRC-32C extra field for each entry.
+    #[cfg(feature = "crc32c")]
+    pub(crate) crc32c: bool,
+    /// The alignment which the total archive size should be padded to, if any.
+    pad_to: Option<u64>,
+    /// Whether to reject entries with filenames containing NUL bytes.
+    pub(crate) validate_filenames: bool,
+    /// Whether to only emit a ZIP64 extended information field for streamed entries when it's needed.
+    pub(crate) lazy_zip64_stream: bool,
+    /// The filenames of all entries written so far, if duplicate filenames should be rejected.
+    names: Option<HashSet<Vec<u8>>>,
+    /// The lowercase filename extensions of entries which should always be stored without compression.
+    store_extensions: Vec<String>,
+    /// The filename patterns of entries which should be stored and aligned for memory mapping, along with the
+    /// alignment.
+    mmap_ready: Option<(Vec<String>, u16)>,
+    /// How UTF-8 filenames & comments are signalled.
+    unicode_strategy: UnicodeStrategy,
+    /// Whether to convert backslashes within UTF-8 filenames to forward slashes.
+    normalize_separators: bool,
+    /// Whether to add an extended timestamp extra field derived from each entry's last modification date.
+    extended_timestamp: bool,
+    /// The compressed data of all entries written so far keyed by their compression method and the SHA-256 digest of
+    /// their uncompressed data, if the compression of identical contents should be deduplicated.
+    #[cfg(feature = "sha2")]
+    pub(crate) dedupe: Option<HashMap<DedupeKey, Vec<u8>>>,
+    /// Whether to build the central directory & end of central directory records in memory and write them at once.
+    atomic_close: bool,
+    /// The size of the buffer used when copying data from a reader into a new entry.
+    pub(crate) copy_buffer_size: usize,
+    comment_opt: Option<Vec<u8>>,
+}
+
+impl<W> ZipFileWriter<W> {
+    /// Returns an error if the entry's filename has already been written and duplicates are rejected.
+    ///
+    /// The filename is only recorded once the entry's central directory record has been pushed (see
+    /// [`record_last_name()`]), so an entry which fails to be written doesn't block a later retry under the same name.
+    pub(crate) fn check_duplicate_name(&self, entry: &ZipEntry) -> Result<()> {
+        match &self.names {
+            Some(names) if names.contains(entry.filename().as_bytes()) => {
+                Err(ZipError::DuplicateFileName(String::from_utf8_lossy(entry.filename().as_bytes()).into_owned()))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Converts backslashes within the entry's filename to forward slashes, if configured to do so.
+    pub(crate) fn apply_normalize_separators(&self, entry: &mut ZipEntry) {
+        if !self.normalize_separators || !entry.filename().as_bytes().contains(&b'\\') {
+            return;
+        }
+
+        if let Ok(filename) = entry.filename().as_str() {
+            let normalized = filename.replace('\\', "/");
+            entry.filename = match entry.filename().alternative() {
+                Some(alternative) => ZipString::new_with_alternative(normalized, alternative.to_vec()),
+                None => ZipString::new(normalized.into_bytes(), StringEncoding::Utf8),
+            };
+        }
+    }
+
+    /// Forces the entry's compression method to Stored if its filename has one of the configured extensions.
+    pub(crate) fn apply_store_extensions(&self, entry: &mut ZipEntry) {
+        if self.store_extensions.is_empty() {
+            return;
+        }
+
+        let filename = String::from_utf8_lossy(entry.filename().as_bytes());
+        let name = filename.rsplit('/').next().unwrap_or_default();
+        let Some((_, extension)) = name.rsplit_once('.') else {
+            return;
+        };
+
+        if self.store_extensions.iter().any(|stored| stored.eq_ignore_ascii_case(extension)) {
+            entry.compression = Compression::Stored;
+        }
+    }
+
+    /// Forces the entry's compression method to Stored if its filename matches one of the patterns configured via
+    /// [`ZipFileWriter::mmap_ready_entries()`].
+    pub(crate) fn apply_mmap_ready(&self, entry: &mut ZipEntry) {
+        if self.matches_mmap_ready(entry) {
+            entry.compression = Compression::Stored;
+        }
+    }
+
+    /// Returns the alignment which the entry's data should be written at, if it's stored and its filename matches one
+    /// of the patterns configured via [`ZipFileWriter::mmap_ready_entries()`].
+    pub(crate) fn mmap_alignment(&self, entry: &ZipEntry) -> Option<u16> {
+        let (_, alignment) = self.mmap_ready.as_ref()?;
+        let aligned = *alignment > 1 && entry.compression() == Compression::Stored;
+        (aligned && self.matches_mmap_ready(entry)).then_some(*alignment)
+    }
+
+    fn matches_mmap_ready(&self, entry: &ZipEntry) -> bool {
+        let Some((patterns, _)) = self.mmap_ready.as_ref() else {
+            return false;
+        };
+
+        let filename = String::from_utf8_lossy(entry.filename().as_bytes());
+        patterns.iter().any(|pattern| crate::utils::glob_match(pattern, &filename))
+    }
+
+    /// Adds an extended timestamp extra field derived from the entry's last modification date, if enabled.
+    ///
+    /// Only the modification time is stored, so the field is identical within the local file header and central
+    /// directory.
+    pub(crate) fn apply_extended_timestamp(&self, entry: &mut ZipEntry) {
+        if !self.extended_timestamp
+            || entry.extra_fields.iter().any(|field| matches!(field, ExtraField::ExtendedTimestamp(_)))
+        {
+            return;
+        }
+
+        // The field can only store times up to early 2106, whereas the MS-DOS date can go until the end of 2107, so
+        // later dates are left without a field rather than wrapping around.
+        let Some(time) = entry.last_modification_date.unix_timestamp().and_then(|time| u32::try_from(time).ok()) else {
+            return;
+        };
+
+        let field =
+            ExtendedTimestampExtraField { modification_time: Some(time), access_time: None, creation_time: None };
+        entry.extra_fields.push(ExtraField::ExtendedTimestamp(field));
+    }
+
+    /// Adjusts the entry's filename, comment, and UTF-8 flag to match the configured Unicode strategy.
+    pub(crate) fn apply_unicode_strategy(&self, entry: &mut ZipEntry) {
+        match self.unicode_strategy {
+            UnicodeStrategy::Both => (),
+            UnicodeStrategy::Utf8FlagOnly => {
+                for string in [&mut entry.filename, &mut entry.comment] {
+                    if string.encoding() == StringEncoding::Utf8 && string.alternative().is_some() {
+                        *string = ZipString::new(string.as_bytes().to_vec(), StringEncoding::Utf8);
+                    }
+                }
+            }
+            UnicodeStrategy::ExtraFieldsOnly => {
+                for string in [&mut entry.filename, &mut entry.comment] {
+                    if string.is_utf8_without_alternative() && !string.as_bytes().is_ascii() {
+                        let bytes = string.as_bytes().to_vec();
+                        *string = ZipString::new_with_alternative(String::from_utf8_lossy(&bytes).into_owned(), bytes);
+                    }
+                }
+                entry.utf8_flag.get_or_insert(false);
+            }
+        }
+    }
+
+    /// Returns the number of null bytes needed to pad the archive to the configured alignment, given the offset of the
+    /// EOCDR and the length of the comment.
+    fn comment_padding(&self, eocdr_offset: u64, comment_length: usize) -> Result<u64> {
+        let Some(alignment) = self.pad_to.filter(|alignment| *alignment > 1) else {
+            return Ok(0);
+        };
+
+        let end_offset = eocdr_offset + (SIGNATURE_LENGTH + EOCDR_LENGTH + comment_length) as u64;
+        let padding = (alignment - end_offset % alignment) % alignment;
+
+        if comment_length as u64 + padding > u16::MAX as u64 {
+            return Err(ZipError::CommentTooLarge);
+        }
+        Ok(padding)
+    }
 }
 
 impl<W: AsyncWrite + Unpin> ZipFileWriter<W> {
@@ -104,20 +339,234 @@ impl<W: AsyncWrite + Unpin> ZipFileWriter<W> {
             comment_opt: None,
             is_zip64: false,
             force_no_zip64: false,
+            auto_text_detection: false,
+            #[cfg(feature = "crc32c")]
+            crc32c: false,
+            pad_to: None,
+            validate_filenames: true,
+            lazy_zip64_stream: false,
+            names: None,
+            store_extensions: Vec::new(),
+            mmap_ready: None,
+            unicode_strategy: UnicodeStrategy::Both,
+            normalize_separators: false,
+            extended_timestamp: false,
+            #[cfg(feature = "sha2")]
+            dedupe: None,
+            atomic_close: false,
+            copy_buffer_size: COPY_BUFFER_SIZE,
         }
     }
 
     /// Force the ZIP writer to operate in non-ZIP64 mode.
     /// If any files would need ZIP64, an error will be raised.
+    ///
+    /// See [`ZipFileWriter::auto_zip64()`] for how this compares to the other ZIP64 modes.
     pub fn force_no_zip64(mut self) -> Self {
+        self.is_zip64 = false;
         self.force_no_zip64 = true;
         self
     }
 
     /// Force the ZIP writer to emit Zip64 structs at the end of the archive.
     /// Zip64 extended fields will only be written if needed.
+    ///
+    /// See [`ZipFileWriter::auto_zip64()`] for how this compares to the other ZIP64 modes.
     pub fn force_zip64(mut self) -> Self {
         self.is_zip64 = true;
+        self.force_no_zip64 = false;
+        self
+    }
+
+    /// Only emit ZIP64 structures when the archive actually needs them, undoing any previous call to
+    /// [`ZipFileWriter::force_zip64()`] or [`ZipFileWriter::force_no_zip64()`].
+    ///
+    /// The ZIP writer supports three ZIP64 modes:
+    /// - By default, ZIP64 structures are emitted when needed, but the local file header of each streamed entry also
+    ///   contains a ZIP64 extended information field (as its final size isn't yet known), which in turn causes the
+    ///   ZIP64 end of central directory record & locator to be written.
+    /// - With [`ZipFileWriter::force_zip64()`], the ZIP64 end of central directory record & locator are always
+    ///   written.
+    /// - With [`ZipFileWriter::force_no_zip64()`], ZIP64 structures are never written and an error is returned if
+    ///   they would be needed.
+    ///
+    /// These modes are exclusive, so whichever of the three methods is called last takes effect. The exception is that
+    /// the [`ZipFileWriter::lazy_zip64_stream()`] option enabled by this method stays enabled when another mode is
+    /// chosen afterwards.
+    ///
+    /// This option guarantees that no ZIP64 structures are written for archives whose sizes, offsets, and number of
+    /// entries all fit within their non-ZIP64 fields. As with [`ZipFileWriter::lazy_zip64_stream()`] (which this
+    /// option implies), closing a streamed entry whose sizes exceed 4 GiB will return [`ZipError::Zip64Needed`].
+    pub fn auto_zip64(mut self) -> Self {
+        self.is_zip64 = false;
+        self.force_no_zip64 = false;
+        self.lazy_zip64_stream = true;
+        self
+    }
+
+    /// Only emit a ZIP64 extended information field for entries written via streaming when it's needed.
+    ///
+    /// By default, the local file header of each streamed entry contains a ZIP64 extended information field as its
+    /// final size isn't yet known. This option omits that field to produce leaner headers, for when streamed entries
+    /// are known to be small. The field will still be added to an entry's central directory header if its local file
+    /// header is beyond the 4 GiB offset, but closing an entry whose sizes exceed 4 GiB will return
+    /// [`ZipError::Zip64Needed`].
+    pub fn lazy_zip64_stream(mut self) -> Self {
+        self.lazy_zip64_stream = true;
+        self
+    }
+
+    /// Automatically mark entries written via [`ZipFileWriter::write_entry_whole()`] as text when their data looks
+    /// like text.
+    ///
+    /// As with Info-ZIP, data looks like text if it contains at least one tab, line feed, carriage return, or byte of
+    /// 32 and above, and none of the control characters 0-6, 14-25, or 28-31. Entries explicitly marked as text or not
+    /// via [`ZipEntryBuilder::text()`] are left as-is.
+    pub fn auto_text_detection(mut self) -> Self {
+        self.auto_text_detection = true;
+        self
+    }
+
+    /// Write a CRC-32C digest of each entry's uncompressed data as an additional extra field.
+    ///
+    /// Other readers will ignore this non-standard field, but it can be verified by this crate via
+    /// [`ZipEntryReader::read_to_end_checked_crc32c()`]. For entries written via streaming, the field is only present
+    /// within the central directory.
+    ///
+    /// Note that this requires the `crc32c` feature.
+    #[cfg(feature = "crc32c")]
+    pub fn crc32c(mut self) -> Self {
+        self.crc32c = true;
+        self
+    }
+
+    /// Pad the total size of the archive to a multiple of `alignment` bytes when closing.
+    ///
+    /// Padding is written as null bytes appended to the archive comment, so the resulting archive remains valid and
+    /// the padding will be visible as part of the comment when read back. If the required padding wouldn't fit within
+    /// the maximum comment length, [`ZipFileWriter::close()`] will return [`ZipError::CommentTooLarge`].
+    pub fn pad_to(mut self, alignment: u64) -> Self {
+        self.pad_to = Some(alignment);
+        self
+    }
+
+    /// Allow entries with filenames containing NUL bytes to be written.
+    ///
+    /// By default, writing such an entry will return [`ZipError::InvalidFileName`], as NUL bytes are likely to break
+    /// extractors. See [`ZipEntryBuilder::validate()`] for stricter validation of filenames.
+    pub fn allow_invalid_filenames(mut self) -> Self {
+        self.validate_filenames = false;
+        self
+    }
+
+    /// Set the size of the buffer used when copying data from a reader into a new entry (64 KiB by default).
+    ///
+    /// This applies to [`ZipFileWriter::write_entry_from_reader()`] and [`recompress()`]. Larger buffers reduce the
+    /// number of reads & writes when working with high-bandwidth sources. A size of zero is treated as one byte.
+    pub fn with_copy_buffer_size(mut self, size: usize) -> Self {
+        self.copy_buffer_size = std::cmp::max(size, 1);
+        self
+    }
+
+    /// Reject entries whose filename matches that of an entry already written.
+    ///
+    /// The ZIP format allows duplicate filenames, but extractors handle them inconsistently (eg. by silently
+    /// overwriting the earlier entry). With this option, writing such an entry will return
+    /// [`ZipError::DuplicateFileName`]. Filenames are compared exactly, as raw bytes.
+    pub fn reject_duplicate_names(mut self) -> Self {
+        self.names = Some(HashSet::new());
+        self
+    }
+
+    /// Compress the data of entries written via [`ZipFileWriter::write_entry_whole()`] only once when their contents
+    /// are identical, reusing the compressed data of the first such entry for any duplicates.
+    ///
+    /// Contents are matched by their compression method and the SHA-256 digest of their uncompressed data. Each
+    /// duplicate is still written with its own local file header and copy of the compressed data, so that the archive
+    /// remains readable by streaming readers and extractors which expect a local file header per central directory
+    /// record. The compressed data of each distinct entry is held in memory until the writer is dropped, so this is
+    /// best suited to archives of many small, repetitive files. Stored entries are never deduplicated, as there's no
+    /// compression to save.
+    #[cfg(feature = "sha2")]
+    pub fn dedupe(mut self, enabled: bool) -> Self {
+        self.dedupe = enabled.then(HashMap::new);
+        self
+    }
+
+    /// Build the central directory and end of central directory records in memory when closing, and then write them via
+    /// a single call to [`AsyncWriteExt::write_all()`] rather than one record at a time.
+    ///
+    /// If the inner writer fails before accepting any of these records, the output is left exactly as it was after the
+    /// last entry was written, rather than ending partway through the central directory. The entries' data is then
+    /// intact, and can still be read via [`seek::ZipFileReader::new_with_recovery()`]. This is useful for
+    /// network-backed writers where partial writes are common, at the cost of holding the entire central directory in
+    /// memory. To retry closing after such a failure, use [`ZipFileWriter::try_close()`] which returns the writer
+    /// alongside the error.
+    ///
+    /// [`seek::ZipFileReader::new_with_recovery()`]: crate::base::read::seek::ZipFileReader::new_with_recovery
+    pub fn atomic_close(mut self, enabled: bool) -> Self {
+        self.atomic_close = enabled;
+        self
+    }
+
+    /// Store entries whose filename has one of the provided extensions without compression, regardless of the
+    /// compression method they were built with.
+    ///
+    /// This is useful when bulk-adding files, as already compressed formats (eg. `jpg`, `mp4`, `zip`, or `gz`) gain
+    /// little to nothing from being compressed again. Extensions are matched case-insensitively against the final
+    /// component of each filename, and may be provided with or without a leading dot.
+    pub fn store_extensions(mut self, extensions: &[&str]) -> Self {
+        let extensions = extensions.iter().map(|extension| extension.trim_start_matches('.').to_ascii_lowercase());
+        self.store_extensions.extend(extensions);
+        self
+    }
+
+    /// Store entries whose filename matches one of the provided patterns without compression, and pad their local file
+    /// headers so that their data starts at a multiple of `alignment` bytes (eg. 4096 for a page boundary).
+    ///
+    /// This allows consumers to memory map such entries directly from the archive, as done by some data formats which
+    /// embed large arrays within a ZIP file. Patterns are matched against the entire filename in the same way as
+    /// [`ZipFile::glob()`], where `*` matches any run of characters other than `/`, `?` matches any single character
+    /// other than `/`, and `**` matches any run of characters (including `/`). The padding is written as an extra field
+    /// in the same format as Android's zipalign, and is only present within the local file header.
+    ///
+    /// Entries written via [`ZipFileWriter::write_prepared()`] are only aligned if they were prepared as Stored.
+    ///
+    /// [`ZipFile::glob()`]: crate::ZipFile::glob
+    pub fn mmap_ready_entries(mut self, patterns: &[&str], alignment: u16) -> Self {
+        let patterns = patterns.iter().map(|pattern| pattern.to_string()).collect();
+        self.mmap_ready = Some((patterns, alignment));
+        self
+    }
+
+    /// Convert backslashes within entries' filenames to forward slashes as they're written.
+    ///
+    /// The specification requires forward slashes as path separators, but archives created from Windows paths often
+    /// contain backslashes instead, which Unix extractors treat as part of a single filename. Only UTF-8 filenames are
+    /// normalized, as a backslash byte may be part of a multibyte character in other encodings. For filenames with an
+    /// alternative encoding, the alternative is left as-is and the normalized filename is stored within the Info-ZIP
+    /// Unicode path extra field.
+    pub fn normalize_separators(mut self, enabled: bool) -> Self {
+        self.normalize_separators = enabled;
+        self
+    }
+
+    /// Set whether an extended timestamp extra field should be added to each entry, storing its last modification date
+    /// as seconds since the Unix epoch.
+    ///
+    /// Unix extractors prefer this field over the MS-DOS date & time, which has a granularity of two seconds and no
+    /// notion of a time zone. The entry's date is interpreted as UTC, and entries without a valid date or which already
+    /// have an extended timestamp extra field are left as-is.
+    pub fn emit_extended_timestamp(mut self, enabled: bool) -> Self {
+        self.extended_timestamp = enabled;
+        self
+    }
+
+    /// Set how UTF-8 filenames & comments are signalled to extractors (see [`UnicodeStrategy`]).
+    ///
+    /// By default, [`UnicodeStrategy::Both`] is used.
+    pub fn unicode_strategy(mut self, strategy: UnicodeStrategy) -> Self {
+        self.unicode_strategy = strategy;
         self
     }
 
@@ -126,6 +575,44 @@ impl<W: AsyncWrite + Unpin> ZipFileWriter<W> {
         EntryWholeWriter::from_raw(self, entry.into(), data).write().await
     }
 
+    /// Write an entry whose data was compressed ahead of time via [`ZipEntryBuilder::prepare()`].
+    ///
+    /// This allows many entries to be compressed concurrently and then written sequentially by a single writer. Options
+    /// which don't affect compression (eg. [`ZipFileWriter::auto_text_detection()`]) are still applied, but
+    /// [`ZipFileWriter::store_extensions()`] isn't, as the entry's data has already been compressed.
+    pub async fn write_prepared(&mut self, entry: PreparedEntry) -> Result<()> {
+        entry.write(self).await
+    }
+
+    /// Write an entry whose data the caller has already compressed as per the entry's compression method.
+    ///
+    /// The provided data is written as-is, with the provided CRC32 value and uncompressed size recorded in the entry's
+    /// headers. Neither is verified against the data, so an incorrect value will only be detected once the entry is
+    /// read. Options which require the uncompressed data (eg. [`ZipFileWriter::auto_text_detection()`]) aren't applied.
+    pub async fn write_entry_precompressed<E: Into<ZipEntry>>(
+        &mut self,
+        entry: E,
+        compressed_data: &[u8],
+        crc32: u32,
+        uncompressed_size: u64,
+    ) -> Result<()> {
+        let mut entry = entry.into();
+        entry.check_directory_size(uncompressed_size)?;
+
+        #[cfg(feature = "legacy")]
+        if matches!(entry.compression(), Compression::Implode) {
+            return Err(ZipError::FeatureNotSupported("writing implode compressed entries"));
+        }
+
+        self.apply_normalize_separators(&mut entry);
+        if self.validate_filenames {
+            entry.validate_filename(false)?;
+        }
+        self.check_duplicate_name(&entry)?;
+
+        entry_whole::write_compressed(self, entry, compressed_data, crc32, uncompressed_size).await
+    }
+
     /// Write an entry of unknown size and data via streaming (ie. using a data descriptor).
     /// The generated Local File Header will be invalid, with no compressed size, uncompressed size,
     /// and a null CRC. This might cause problems with the destination reader.
@@ -133,8 +620,180 @@ impl<W: AsyncWrite + Unpin> ZipFileWriter<W> {
         EntryStreamWriter::from_raw(self, entry.into()).await
     }
 
+    /// Write an entry via streaming within the provided closure, which is passed the entry's writer.
+    ///
+    /// The entry is always closed once the closure returns, including when it returns an error early (eg. via `?`), so
+    /// the archive is never left with an unclosed entry. The closure's error takes precedence over any error from
+    /// closing the entry. Note that this can't be guaranteed if the returned future is dropped before it completes.
+    ///
+    /// ```no_run
+    /// # use async_zip::base::write::ZipFileWriter;
+    /// # use async_zip::{Compression, ZipEntryBuilder};
+    /// # use futures_lite::io::AsyncWriteExt;
+    /// #
+    /// # async fn run() -> async_zip::error::Result<()> {
+    /// let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    /// let entry = ZipEntryBuilder::new("foo.txt".into(), Compression::Stored);
+    ///
+    /// writer.entry_scope(entry, async |entry_writer| {
+    ///     entry_writer.write_all(b"foo").await?;
+    ///     Ok(())
+    /// }).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn entry_scope<E, F, T>(&mut self, entry: E, f: F) -> Result<T>
+    where
+        E: Into<ZipEntry>,
+        F: AsyncFnOnce(&mut EntryStreamWriter<'_, W>) -> Result<T>,
+    {
+        let mut entry_writer = self.write_entry_stream(entry).await?;
+        let result = f(&mut entry_writer).await;
+        let closed = entry_writer.close().await;
+
+        let value = result?;
+        closed?;
+        Ok(value)
+    }
+
+    /// Write a new ZIP entry via streaming, copying all data from the provided reader until EOF is reached.
+    ///
+    /// This is equivalent to calling [`ZipFileWriter::write_entry_stream()`], copying the reader's data into the
+    /// returned writer, and then closing it. Data is copied in chunks of the size set via
+    /// [`ZipFileWriter::with_copy_buffer_size()`], which is 64 KiB by default.
+    pub async fn write_entry_from_reader<E, R>(&mut self, entry: E, mut reader: R) -> Result<()>
+    where
+        E: Into<ZipEntry>,
+        R: AsyncRead + Unpin,
+    {
+        let mut buffer = vec![0; self.copy_buffer_size];
+        let mut entry_writer = self.write_entry_stream(entry).await?;
+
+        loop {
+            let read = reader.read(&mut buffer).await?;
+
+            if read == 0 {
+                break;
+            }
+
+            entry_writer.write_all(&buffer[..read]).await?;
+        }
+
+        entry_writer.close().await
+    }
+
+    /// Computes the exact size of the archive that would be produced if each of the provided Stored entries were
+    /// written via [`ZipFileWriter::write_entry_whole()`], followed by a call to [`ZipFileWriter::close()`].
+    ///
+    /// Each entry is paired with the length of its data. Any entries already written to this writer, the archive
+    /// comment, and options such as [`ZipFileWriter::pad_to()`] are all taken into account. This is useful for setting
+    /// a `Content-Length` header before streaming an archive as an HTTP response.
+    ///
+    /// As the size of compressed data can't be known ahead of time, an error will be returned if any entry doesn't use
+    /// the Stored compression method.
+    pub fn stored_archive_size(&self, entries: &[(ZipEntry, u64)]) -> Result<u64> {
+        let mut offset = self.writer.offset();
+        let mut num_entries = self.cd_entries.len() as u64;
+        let mut is_zip64 = self.is_zip64;
+        let mut central_directory_size: u64 = self
+            .cd_entries
+            .iter()
+            .map(|entry| {
+                let header = &entry.header;
+                let lengths = header.file_name_length as u64
+                    + header.extra_field_length as u64
+                    + header.file_comment_length as u64;
+                (SIGNATURE_LENGTH + CDH_LENGTH) as u64 + lengths
+            })
+            .sum();
+
+        for (entry, size) in entries {
+            if entry.compression() != Compression::Stored {
+                return Err(ZipError::FeatureNotSupported("computing the size of entries which aren't Stored"));
+            }
+
+            let mut entry = entry.clone();
+
+            #[cfg(feature = "crc32c")]
+            if self.crc32c {
+                entry.extra_fields.retain(|field| !matches!(field, ExtraField::Crc32c(_)));
+                entry.extra_fields.push(ExtraField::Crc32c(Crc32cExtraField { crc32c: 0 }));
+            }
+
+            let large_size = *size > NON_ZIP64_MAX_SIZE as u64;
+            let large_offset = offset > NON_ZIP64_MAX_SIZE as u64;
+
+            if large_size || large_offset {
+                if self.force_no_zip64 {
+                    return Err(ZipError::Zip64Needed(Zip64ErrorCase::LargeFile));
+                }
+                is_zip64 = true;
+            }
+
+            // Mirrors the placement of the zip64 extended information field by the whole entry writer, where the
+            // field is only written to the local file header if it contains the entry's sizes.
+            let mut zip64_field_size = 0;
+            if large_size {
+                zip64_field_size = 4 + 16 + if large_offset { 8 } else { 0 };
+            }
+
+            self.apply_unicode_strategy(&mut entry);
+            self.apply_extended_timestamp(&mut entry);
+            entry_whole::put_unicode_extra_fields(&mut entry);
+
+            let filename_length = entry.filename().alternative().unwrap_or_else(|| entry.filename().as_bytes()).len();
+            let comment_length = entry.comment().alternative().unwrap_or_else(|| entry.comment().as_bytes()).len();
+            let mut lfh_extra_length = entry.extra_fields().count_bytes() + zip64_field_size;
+            let cd_extra_length = if !large_size && large_offset { lfh_extra_length + 4 + 8 } else { lfh_extra_length };
+
+            // The zip64 extended information field isn't part of the entry's extra fields here, so its size is folded
+            // into the offset used when computing the padding.
+            if let Some(alignment) = self.mmap_alignment(&entry) {
+                let extra_fields_length = entry.extra_fields().count_bytes();
+                entry_whole::push_alignment_extra_field(&mut entry, offset + zip64_field_size as u64, alignment);
+                lfh_extra_length += entry.extra_fields().count_bytes() - extra_fields_length;
+            }
+
+            offset += (SIGNATURE_LENGTH + LFH_LENGTH + filename_length + lfh_extra_length) as u64 + size;
+            central_directory_size +=
+                (SIGNATURE_LENGTH + CDH_LENGTH + filename_length + cd_extra_length + comment_length) as u64;
+
+            num_entries += 1;
+            if num_entries > NON_ZIP64_MAX_NUM_FILES as u64 {
+                if self.force_no_zip64 {
+                    return Err(ZipError::Zip64Needed(Zip64ErrorCase::TooManyFiles));
+                }
+                is_zip64 = true;
+            }
+        }
+
+        if offset > NON_ZIP64_MAX_SIZE as u64 {
+            if self.force_no_zip64 {
+                return Err(ZipError::Zip64Needed(Zip64ErrorCase::LargeFile));
+            }
+            is_zip64 = true;
+        }
+
+        offset += central_directory_size;
+        if is_zip64 {
+            offset += (SIGNATURE_LENGTH + ZIP64_EOCDR_LENGTH) as u64 + ZIP64_EOCDL_LENGTH;
+        }
+
+        let comment_length = self.comment_opt.as_ref().map(Vec::len).unwrap_or_default();
+        let padding = self.comment_padding(offset, comment_length)?;
+
+        Ok(offset + (SIGNATURE_LENGTH + EOCDR_LENGTH + comment_length) as u64 + padding)
+    }
+
     /// Set the ZIP file comment.
     pub fn comment(&mut self, comment: String) {
+        self.comment_opt = Some(comment.into_bytes());
+    }
+
+    /// Set the ZIP file comment from raw bytes, which needn't be valid UTF-8.
+    ///
+    /// This is useful for writing comments in legacy encodings (eg. CP437) or binary markers.
+    pub fn comment_bytes(&mut self, comment: Vec<u8>) {
         self.comment_opt = Some(comment);
     }
 
@@ -145,6 +804,45 @@ impl<W: AsyncWrite + Unpin> ZipFileWriter<W> {
         self.writer.inner_mut()
     }
 
+    /// Returns the number of bytes which have been written to the inner writer so far.
+    ///
+    /// This is the offset at which the next entry's local file header will be written. Closing the writer will add the
+    /// size of the central directory and end of central directory records.
+    pub fn bytes_written(&self) -> u64 {
+        self.writer.offset()
+    }
+
+    /// Flushes all data written so far and returns a [`ZipFile`] listing every entry which has been completely written.
+    ///
+    /// This supports reading entries whilst the archive is still being written (ie. tailing). After this function
+    /// returns, the local file header and data of each listed entry have been flushed to the inner writer, and their
+    /// offsets are final. A reader over the same underlying data (eg. a separate handle to the same file) can then read
+    /// them via [`seek::ZipFileReader::from_raw_parts()`], even though the central directory hasn't been written yet.
+    ///
+    /// As such, the writer publishes completed entries by calling this function (eg. after each entry) and passing the
+    /// returned listing to the reader, which must only read entries contained within the latest listing it received.
+    /// An entry which is currently being written via [`ZipFileWriter::write_entry_stream()`] is never listed, as its
+    /// writer must be closed before this function can be called.
+    ///
+    /// [`seek::ZipFileReader::from_raw_parts()`]: crate::base::read::seek::ZipFileReader::from_raw_parts
+    pub async fn checkpoint(&mut self) -> Result<ZipFile> {
+        self.writer.flush().await?;
+
+        // Parse the central directory as it would be written, so that entries are listed exactly as a reader of the
+        // finished archive would see them.
+        let mut buffer = Vec::new();
+        for entry in &self.cd_entries {
+            entry.extend_buffer(&mut buffer);
+        }
+        buffer.extend_from_slice(&crate::spec::consts::EOCDR_SIGNATURE.to_le_bytes());
+
+        let num_entries = self.cd_entries.len() as u64;
+        let entries = crate::base::read::cd(futures_lite::io::Cursor::new(buffer), num_entries, self.is_zip64).await?;
+
+        let builder = entries.into_iter().fold(ZipFileBuilder::new(), |builder, entry| builder.add_entry(entry));
+        Ok(builder.zip64(self.is_zip64).build())
+    }
+
     /// Consumes this ZIP writer and completes all closing tasks.
     ///
     /// This includes:
@@ -153,22 +851,85 @@ impl<W: AsyncWrite + Unpin> ZipFileWriter<W> {
     /// - Writing the file comment.
     ///
     /// Failure to call this function before going out of scope would result in a corrupted ZIP file.
-    pub async fn close(mut self) -> Result<W> {
+    pub async fn close(self) -> Result<W> {
+        Ok(self.close_with_layout().await?.0)
+    }
+
+    /// Consumes this ZIP writer and completes all closing tasks, writing the central directory in the order given by
+    /// the provided comparison function rather than the order in which entries were written.
+    ///
+    /// The order of the entries' data within the archive is unaffected. This is useful for placing certain entries
+    /// (eg. a manifest) first within the central directory, for readers which process it from the start. The sort is
+    /// stable, so entries which compare equal remain in the order they were written.
+    ///
+    /// See [`ZipFileWriter::close()`] for more information.
+    pub async fn close_sorted_by<F>(mut self, mut compare: F) -> Result<W>
+    where
+        F: FnMut(&ZipEntry, &ZipEntry) -> Ordering,
+    {
+        self.cd_entries.sort_by(|a, b| compare(&a.entry, &b.entry));
+        self.close().await
+    }
+
+    /// Consumes this ZIP writer and completes all closing tasks, returning the writer alongside the error if closing
+    /// fails so that it can be retried.
+    ///
+    /// The closing records are always built in memory and written via a single call, as with
+    /// [`ZipFileWriter::atomic_close()`]. If the inner writer fails without accepting any of these records, the output
+    /// is left exactly as it was after the last entry was written and closing can be retried (eg. once a network
+    /// connection has been re-established). Retrying isn't possible if the inner writer accepted only part of the
+    /// records before failing.
+    ///
+    /// See [`ZipFileWriter::close()`] for more information.
+    pub async fn try_close(mut self) -> std::result::Result<W, (ZipError, Self)> {
+        match self.write_closing_records(true).await {
+            Ok(_) => Ok(self.writer.into_inner()),
+            Err(err) => Err((err, self)),
+        }
+    }
+
+    /// Consumes this ZIP writer and completes all closing tasks, additionally returning the layout of the archive.
+    ///
+    /// See [`ZipFileWriter::close()`] for more information.
+    pub async fn close_with_layout(mut self) -> Result<(W, ArchiveLayout)> {
+        let layout = self.write_closing_records(self.atomic_close).await?;
+        Ok((self.writer.into_inner(), layout))
+    }
+
+    /// Writes the central directory and end of central directory records, either one record at a time or all at once.
+    ///
+    /// The writer's state is left untouched other than the written records, so this may be called again if the inner
+    /// writer failed without accepting any of them.
+    async fn write_closing_records(&mut self, atomic: bool) -> Result<ArchiveLayout> {
+        // The central directory may have been reordered, so the first entry isn't necessarily the first written.
+        let first_entry_offset = self
+            .cd_entries
+            .iter()
+            .map(|entry| {
+                match crate::base::read::get_zip64_extra_field(entry.entry.extra_fields())
+                    .filter(|_| entry.header.lh_offset == NON_ZIP64_MAX_SIZE)
+                {
+                    Some(zip64) => zip64.relative_header_offset.unwrap_or_default(),
+                    None => entry.header.lh_offset as u64,
+                }
+            })
+            .min();
         let cd_offset = self.writer.offset();
 
+        // The closing records are built within this buffer, which is either written (and cleared) after each central
+        // directory header, or only once all records have been built if closing atomically.
+        let mut buffer = Vec::new();
+
         for entry in &self.cd_entries {
-            let filename_basic =
-                entry.entry.filename().alternative().unwrap_or_else(|| entry.entry.filename().as_bytes());
-            let comment_basic = entry.entry.comment().alternative().unwrap_or_else(|| entry.entry.comment().as_bytes());
-
-            self.writer.write_all(&crate::spec::consts::CDH_SIGNATURE.to_le_bytes()).await?;
-            self.writer.write_all(&entry.header.as_slice()).await?;
-            self.writer.write_all(filename_basic).await?;
-            self.writer.write_all(&entry.entry.extra_fields().as_bytes()).await?;
-            self.writer.write_all(comment_basic).await?;
+            entry.extend_buffer(&mut buffer);
+
+            if !atomic {
+                self.writer.write_all(&buffer).await?;
+                buffer.clear();
+            }
         }
 
-        let central_directory_size = self.writer.offset() - cd_offset;
+        let central_directory_size = self.writer.offset() + buffer.len() as u64 - cd_offset;
         let central_directory_size_u32 = if central_directory_size > NON_ZIP64_MAX_SIZE as u64 {
             NON_ZIP64_MAX_SIZE
         } else {
@@ -192,12 +953,14 @@ impl<W: AsyncWrite + Unpin> ZipFileWriter<W> {
         };
 
         // Add the zip64 EOCDR and EOCDL if we are in zip64 mode.
+        let mut zip64_eocdr_offset = None;
         if self.is_zip64 {
-            let eocdr_offset = self.writer.offset();
+            let eocdr_offset = self.writer.offset() + buffer.len() as u64;
+            zip64_eocdr_offset = Some(eocdr_offset);
 
             let eocdr = Zip64EndOfCentralDirectoryRecord {
                 size_of_zip64_end_of_cd_record: 44,
-                version_made_by: crate::spec::version::as_made_by(),
+                version_made_by: crate::spec::version::as_made_by(AttributeCompatibility::Unix),
                 version_needed_to_extract: 46,
                 disk_number: 0,
                 disk_number_start_of_cd: 0,
@@ -206,18 +969,22 @@ impl<W: AsyncWrite + Unpin> ZipFileWriter<W> {
                 directory_size: central_directory_size,
                 offset_of_start_of_directory: cd_offset,
             };
-            self.writer.write_all(&crate::spec::consts::ZIP64_EOCDR_SIGNATURE.to_le_bytes()).await?;
-            self.writer.write_all(&eocdr.as_bytes()).await?;
+            buffer.extend_from_slice(&crate::spec::consts::ZIP64_EOCDR_SIGNATURE.to_le_bytes());
+            buffer.extend_from_slice(&eocdr.as_bytes());
 
             let eocdl = Zip64EndOfCentralDirectoryLocator {
                 number_of_disk_with_start_of_zip64_end_of_central_directory: 0,
                 relative_offset: eocdr_offset,
                 total_number_of_disks: 1,
             };
-            self.writer.write_all(&crate::spec::consts::ZIP64_EOCDL_SIGNATURE.to_le_bytes()).await?;
-            self.writer.write_all(&eocdl.as_bytes()).await?;
+            buffer.extend_from_slice(&crate::spec::consts::ZIP64_EOCDL_SIGNATURE.to_le_bytes());
+            buffer.extend_from_slice(&eocdl.as_bytes());
         }
 
+        let mut comment = self.comment_opt.clone().unwrap_or_default();
+        let padding = self.comment_padding(self.writer.offset() + buffer.len() as u64, comment.len())?;
+        comment.resize(comment.len() + padding as usize, 0);
+
         let header = EndOfCentralDirectoryHeader {
             disk_num: 0,
             start_cent_dir_disk: 0,
@@ -225,16 +992,50 @@ impl<W: AsyncWrite + Unpin> ZipFileWriter<W> {
             num_of_entries: num_entries_in_directory_u16,
             size_cent_dir: central_directory_size_u32,
             cent_dir_offset: cd_offset_u32,
-            file_comm_length: self.comment_opt.as_ref().map(|v| v.len() as u16).unwrap_or_default(),
+            file_comm_length: comment.len().try_into().map_err(|_| ZipError::CommentTooLarge)?,
         };
 
-        self.writer.write_all(&crate::spec::consts::EOCDR_SIGNATURE.to_le_bytes()).await?;
-        self.writer.write_all(&header.as_slice()).await?;
-        if let Some(comment) = self.comment_opt {
-            self.writer.write_all(comment.as_bytes()).await?;
-        }
+        let eocdr_offset = self.writer.offset() + buffer.len() as u64;
 
-        Ok(self.writer.into_inner())
+        buffer.extend_from_slice(&crate::spec::consts::EOCDR_SIGNATURE.to_le_bytes());
+        buffer.extend_from_slice(&header.as_slice());
+        buffer.extend_from_slice(&comment);
+
+        self.writer.write_all(&buffer).await?;
+
+        Ok(ArchiveLayout {
+            first_entry_offset,
+            central_directory_offset: cd_offset,
+            central_directory_size,
+            zip64_eocdr_offset,
+            eocdr_offset,
+            end_offset: self.writer.offset(),
+        })
+    }
+}
+
+impl ZipFileWriter<Vec<u8>> {
+    /// Construct a new in-memory ZIP file writer whose output buffer can hold at least `capacity` bytes without
+    /// reallocating.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::new(Vec::with_capacity(capacity))
+    }
+
+    /// Reserves capacity for at least `additional` more bytes to be written without reallocating the output buffer.
+    ///
+    /// Alongside [`ZipFileWriter::stored_archive_size()`], this allows the output buffer to be sized exactly before
+    /// any entries are written.
+    pub fn reserve(&mut self, additional: usize) {
+        self.writer.inner_mut().reserve(additional);
+    }
+
+    /// Consumes this in-memory ZIP writer, completes all closing tasks, and returns a reader over the written archive.
+    ///
+    /// The written bytes are moved into the reader rather than copied, which is useful for round-trip workflows (eg.
+    /// tests) that read an archive back immediately after writing it. See [`ZipFileWriter::close()`] for more
+    /// information.
+    pub async fn close_into_reader(self) -> Result<crate::base::read::mem::ZipFileReader> {
+        crate::base::read::mem::ZipFileReader::new(self.close().await?).await
     }
 }
 
@@ -251,10 +1052,33 @@ where
             comment_opt: None,
             is_zip64: false,
             force_no_zip64: false,
+            auto_text_detection: false,
+            #[cfg(feature = "crc32c")]
+            crc32c: false,
+            pad_to: None,
+            validate_filenames: true,
+            lazy_zip64_stream: false,
+            names: None,
+            store_extensions: Vec::new(),
+            mmap_ready: None,
+            unicode_strategy: UnicodeStrategy::Both,
+            normalize_separators: false,
+            extended_timestamp: false,
+            #[cfg(feature = "sha2")]
+            dedupe: None,
+            atomic_close: false,
+            copy_buffer_size: COPY_BUFFER_SIZE,
         }
     }
 }
 
+/// Records the filename of the most recently pushed central directory record, if duplicate filenames are rejected.
+pub(crate) fn record_last_name(names: &mut Option<HashSet<Vec<u8>>>, cd_entries: &[CentralDirectoryEntry]) {
+    if let (Some(names), Some(last)) = (names.as_mut(), cd_entries.last()) {
+        names.insert(last.entry.filename().as_bytes().to_vec());
+    }
+}
+
 pub(crate) fn get_or_put_info_zip_unicode_path_extra_field_mut(
     extra_fields: &mut Vec<ExtraField>,
 ) -> &mut InfoZipUnicodePathExtraField {
@@ -0,0 +1,110 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+//! A module which supports compressing entries ahead of time, independently of any ZIP writer.
+
+use crate::base::write::entry_whole::{compress_data, is_text, write_compressed};
+use crate::base::write::ZipFileWriter;
+use crate::entry::ZipEntry;
+use crate::error::Result;
+#[cfg(feature = "legacy")]
+use crate::error::ZipError;
+#[cfg(feature = "crc32c")]
+use crate::spec::header::{Crc32cExtraField, ExtraField};
+#[cfg(feature = "legacy")]
+use crate::spec::Compression;
+
+use futures_lite::io::AsyncWrite;
+
+/// An entry whose data has been compressed ahead of time, ready to be written via
+/// [`ZipFileWriter::write_prepared()`].
+///
+/// As preparing an entry doesn't require a ZIP writer, many entries can be compressed concurrently (eg. across
+/// multiple tasks) and then written sequentially by a single writer. See [`ZipEntryBuilder::prepare()`].
+///
+/// [`ZipEntryBuilder::prepare()`]: crate::ZipEntryBuilder::prepare
+#[derive(Clone, Debug)]
+pub struct PreparedEntry {
+    entry: ZipEntry,
+    data: Vec<u8>,
+    crc32: u32,
+    uncompressed_size: u64,
+    text: bool,
+    #[cfg(feature = "crc32c")]
+    crc32c: u32,
+}
+
+impl PreparedEntry {
+    /// Compresses the provided data as per the entry's compression method.
+    pub async fn new(mut entry: ZipEntry, data: &[u8]) -> Result<Self> {
+        entry.check_directory_size(data.len() as u64)?;
+        entry.check_skip_crc()?;
+
+        #[cfg(feature = "legacy")]
+        if matches!(entry.compression(), Compression::Implode) {
+            return Err(ZipError::FeatureNotSupported("writing implode compressed entries"));
+        }
+
+        let compressed = compress_data(&entry, data).await?.into_owned();
+        entry.set_eos_marker();
+
+        let crc32 = if entry.skip_crc { 0 } else { crc32fast::hash(data) };
+
+        Ok(Self {
+            entry,
+            data: compressed,
+            crc32,
+            uncompressed_size: data.len() as u64,
+            text: is_text(data),
+            #[cfg(feature = "crc32c")]
+            crc32c: crc32c::crc32c(data),
+        })
+    }
+
+    /// Returns the entry which was prepared.
+    pub fn entry(&self) -> &ZipEntry {
+        &self.entry
+    }
+
+    /// Returns the compressed data.
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Returns the CRC32 value of the uncompressed data.
+    pub fn crc32(&self) -> u32 {
+        self.crc32
+    }
+
+    /// Returns the size of the uncompressed data.
+    pub fn uncompressed_size(&self) -> u64 {
+        self.uncompressed_size
+    }
+
+    /// Returns the size of the compressed data.
+    pub fn compressed_size(&self) -> u64 {
+        self.data.len() as u64
+    }
+
+    /// Writes this entry to the provided ZIP writer, applying any of its options which don't affect compression.
+    pub(crate) async fn write<W: AsyncWrite + Unpin>(mut self, writer: &mut ZipFileWriter<W>) -> Result<()> {
+        writer.apply_normalize_separators(&mut self.entry);
+        if writer.validate_filenames {
+            self.entry.validate_filename(false)?;
+        }
+        writer.check_duplicate_name(&self.entry)?;
+
+        if writer.auto_text_detection && !self.entry.explicit_text && self.text {
+            self.entry.internal_file_attribute |= 0x1;
+        }
+
+        #[cfg(feature = "crc32c")]
+        if writer.crc32c {
+            self.entry.extra_fields.retain(|field| !matches!(field, ExtraField::Crc32c(_)));
+            let field = Crc32cExtraField { crc32c: self.crc32c };
+            self.entry.extra_fields.push(ExtraField::Crc32c(field));
+        }
+
+        write_compressed(writer, self.entry, &self.data, self.crc32, self.uncompressed_size).await
+    }
+}
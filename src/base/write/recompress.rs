@@ -0,0 +1,91 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+//! A module which supports repackaging an existing ZIP file with a different compression method.
+
+use crate::base::read::seek::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::entry::builder::ZipEntryBuilder;
+use crate::error::{Result, ZipError};
+use crate::spec::header::ExtraField;
+use crate::spec::Compression;
+
+use futures_lite::io::{AsyncBufRead, AsyncReadExt, AsyncSeek, AsyncWrite, AsyncWriteExt};
+
+/// Repackages every entry of a ZIP file into the provided writer using a different compression method.
+///
+/// Each entry is decompressed and then written via streaming, so only a single chunk of data is held in memory at
+/// once. Each entry's filename, comment, last modification date, attributes, and extra fields are preserved, as is
+/// the archive comment. Directories are always written using the Stored compression method.
+///
+/// The exception is any existing CRC-32C extra field, which is dropped rather than carried over unverified. A fresh
+/// field is still computed for each entry if enabled on the writer via `ZipFileWriter::crc32c()`.
+///
+/// The CRC32 value of each entry is checked as its data is read, returning [`ZipError::CRC32CheckError`] on a
+/// mismatch. The writer isn't closed by this function, so further entries may be written before doing so.
+///
+/// # Example
+/// ```no_run
+/// # use async_zip::base::read::seek::ZipFileReader;
+/// # use async_zip::base::write::{recompress, ZipFileWriter};
+/// # use async_zip::Compression;
+/// # use async_zip::error::ZipError;
+/// # use futures_lite::io::Cursor;
+/// #
+/// # async fn run(data: Vec<u8>) -> Result<(), ZipError> {
+/// let mut reader = ZipFileReader::new(Cursor::new(data)).await?;
+/// let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+///
+/// recompress(&mut reader, &mut writer, Compression::Stored).await?;
+/// let data = writer.close().await?;
+/// #   Ok(())
+/// # }
+/// ```
+pub async fn recompress<R, W>(
+    reader: &mut ZipFileReader<R>,
+    writer: &mut ZipFileWriter<W>,
+    compression: Compression,
+) -> Result<()>
+where
+    R: AsyncBufRead + AsyncSeek + Unpin,
+    W: AsyncWrite + Unpin,
+{
+    let comment = reader.file().comment();
+    writer.comment_bytes(comment.alternative().unwrap_or_else(|| comment.as_bytes()).to_vec());
+
+    let mut buffer = vec![0; writer.copy_buffer_size];
+
+    for index in 0..reader.file().entries().len() {
+        let stored_entry = &reader.file().entries()[index];
+        let mut entry = stored_entry.to_entry();
+        entry.extra_fields.retain(|field| !matches!(field, ExtraField::Crc32c(_)));
+
+        if stored_entry.dir()? {
+            let entry = ZipEntryBuilder::from(entry).compression(Compression::Stored);
+            writer.write_entry_whole(entry, &[]).await?;
+            continue;
+        }
+
+        let entry = ZipEntryBuilder::from(entry).compression(compression);
+        let mut entry_reader = reader.reader_with_entry(index).await?;
+        let mut entry_writer = writer.write_entry_stream(entry).await?;
+
+        loop {
+            let read = entry_reader.read(&mut buffer).await?;
+
+            if read == 0 {
+                break;
+            }
+
+            entry_writer.write_all(&buffer[..read]).await?;
+        }
+
+        if entry_reader.compute_hash() != entry_reader.entry().crc32() {
+            return Err(ZipError::CRC32CheckError);
+        }
+
+        entry_writer.close().await?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,276 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+//! A module which supports writing ZIP files split across multiple volumes.
+
+use crate::base::read::get_zip64_extra_field_mut;
+use crate::base::write::io::split::SplitWriter;
+use crate::base::write::{EntryStreamWriter, ZipFileWriter};
+use crate::entry::ZipEntry;
+use crate::error::{Result, Zip64ErrorCase, ZipError};
+use crate::spec::attribute::AttributeCompatibility;
+use crate::spec::consts::{
+    CDH_LENGTH, CDH_SIGNATURE, DATA_DESCRIPTOR_SIGNATURE, EOCDR_LENGTH, EOCDR_SIGNATURE, NON_ZIP64_MAX_NUM_FILES,
+    NON_ZIP64_MAX_SIZE, SIGNATURE_LENGTH, ZIP64_EOCDL_LENGTH, ZIP64_EOCDL_SIGNATURE, ZIP64_EOCDR_LENGTH,
+    ZIP64_EOCDR_SIGNATURE,
+};
+use crate::spec::extra_field::ExtraFieldAsBytes;
+use crate::spec::header::{
+    EndOfCentralDirectoryHeader, Zip64EndOfCentralDirectoryLocator, Zip64EndOfCentralDirectoryRecord,
+};
+
+use std::future::Future;
+
+use futures_lite::future::poll_fn;
+use futures_lite::io::{AsyncRead, AsyncWrite, AsyncWriteExt};
+
+/// The minimum size of each volume, as required by the specification.
+pub const MIN_VOLUME_SIZE: u64 = 64 * 1024;
+/// The maximum size of each volume, such that all offsets within a volume can be stored without ZIP64.
+pub const MAX_VOLUME_SIZE: u64 = NON_ZIP64_MAX_SIZE as u64;
+
+/// A ZIP file writer which splits a single logical archive across multiple volumes of a fixed maximum size.
+///
+/// Each volume is requested from the provided closure when needed, which is passed the zero-based volume number and
+/// returns a future resolving to the volume. The first volume begins with the split archive signature, and the disk
+/// numbers & offsets within the central directory and end of central directory record are relative to the volume they
+/// were written to. Neither central directory headers nor the end of central directory record are split across
+/// volumes, so a volume may end early in order for the next to contain them.
+///
+/// By convention, volumes are named `<name>.z01`, `<name>.z02`, and so on, with the final volume named `<name>.zip`.
+/// As the final volume isn't known until the archive is closed, it may need to be renamed afterwards.
+///
+/// # Note
+/// - [`SplitZipFileWriter::close()`] must be called before a stream writer goes out of scope.
+/// - Reading split archives is not currently supported by this crate.
+///
+/// # Example
+/// ```no_run
+/// # use async_zip::{Compression, ZipEntryBuilder, base::write::SplitZipFileWriter};
+/// # use async_zip::error::ZipError;
+/// #
+/// # async fn run() -> Result<(), ZipError> {
+/// let mut writer = SplitZipFileWriter::new(64 * 1024, |_| async { Ok(Vec::<u8>::new()) });
+///
+/// let opts = ZipEntryBuilder::new(String::from("foo.txt").into(), Compression::Stored);
+/// writer.write_entry_whole(opts, &[0; 100_000]).await?;
+///
+/// // Name the volumes foo.z01, foo.z02, ..., with the final volume named foo.zip.
+/// let volumes = writer.close().await?;
+/// #   Ok(())
+/// # }
+/// ```
+pub struct SplitZipFileWriter<W, F, Fut> {
+    writer: ZipFileWriter<SplitWriter<W, F, Fut>>,
+    started: bool,
+}
+
+impl<W, F, Fut> SplitZipFileWriter<W, F, Fut>
+where
+    W: AsyncWrite + Unpin,
+    F: FnMut(u32) -> Fut + Unpin,
+    Fut: Future<Output = std::io::Result<W>>,
+{
+    /// Construct a new split ZIP file writer which requests volumes of at most `volume_size` bytes from `next`.
+    ///
+    /// The volume size is clamped between [`MIN_VOLUME_SIZE`] and [`MAX_VOLUME_SIZE`].
+    pub fn new(volume_size: u64, next: F) -> Self {
+        let volume_size = volume_size.clamp(MIN_VOLUME_SIZE, MAX_VOLUME_SIZE);
+        Self { writer: ZipFileWriter::new(SplitWriter::new(volume_size, next)), started: false }
+    }
+
+    /// Write a new ZIP entry of known size and data.
+    pub async fn write_entry_whole<E: Into<ZipEntry>>(&mut self, entry: E, data: &[u8]) -> Result<()> {
+        self.start().await?;
+        self.writer.write_entry_whole(entry, data).await
+    }
+
+    /// Write an entry of unknown size and data via streaming (ie. using a data descriptor).
+    ///
+    /// See [`ZipFileWriter::write_entry_stream()`] for more information.
+    pub async fn write_entry_stream<E: Into<ZipEntry>>(
+        &mut self,
+        entry: E,
+    ) -> Result<EntryStreamWriter<'_, SplitWriter<W, F, Fut>>> {
+        self.start().await?;
+        self.writer.write_entry_stream(entry).await
+    }
+
+    /// Write a new ZIP entry via streaming, copying all data from the provided reader until EOF is reached.
+    pub async fn write_entry_from_reader<E, R>(&mut self, entry: E, reader: R) -> Result<()>
+    where
+        E: Into<ZipEntry>,
+        R: AsyncRead + Unpin,
+    {
+        self.start().await?;
+        self.writer.write_entry_from_reader(entry, reader).await
+    }
+
+    /// Set the ZIP file comment.
+    pub fn comment(&mut self, comment: String) {
+        self.writer.comment(comment);
+    }
+
+    /// Set the ZIP file comment from raw bytes, which needn't be valid UTF-8.
+    pub fn comment_bytes(&mut self, comment: Vec<u8>) {
+        self.writer.comment_bytes(comment);
+    }
+
+    /// Writes the split archive signature to the start of the first volume, if not already written.
+    async fn start(&mut self) -> Result<()> {
+        if !self.started {
+            self.writer.writer.write_all(&DATA_DESCRIPTOR_SIGNATURE.to_le_bytes()).await?;
+            self.started = true;
+        }
+        Ok(())
+    }
+
+    /// Consumes this ZIP writer and completes all closing tasks, returning the written volumes in order.
+    ///
+    /// This includes:
+    /// - Writing all central directory headers, with disk numbers and offsets relative to their volume.
+    /// - Writing the end of central directory header to the final volume.
+    /// - Writing the file comment.
+    ///
+    /// Failure to call this function before going out of scope would result in a corrupted ZIP file.
+    pub async fn close(mut self) -> Result<Vec<W>> {
+        self.start().await?;
+
+        let mut writer = self.writer;
+        let comment = writer.comment_opt.take().unwrap_or_default();
+
+        // Offsets were recorded relative to the start of the first volume, so convert them to be relative to the
+        // volume each local file header was written to.
+        for entry in writer.cd_entries.iter_mut() {
+            let split_writer = writer.writer.inner_mut();
+            let lh_offset = entry.header.lh_offset;
+
+            let disk = match get_zip64_extra_field_mut(&mut entry.entry.extra_fields)
+                .filter(|_| lh_offset == NON_ZIP64_MAX_SIZE)
+            {
+                Some(zip64) => {
+                    let (disk, offset) = split_writer.position_of(zip64.relative_header_offset.unwrap_or_default());
+                    zip64.relative_header_offset = Some(offset);
+                    disk
+                }
+                None => {
+                    let (disk, offset) = split_writer.position_of(lh_offset as u64);
+                    entry.header.lh_offset = offset as u32;
+                    disk
+                }
+            };
+
+            entry.header.disk_start =
+                disk.try_into().map_err(|_| ZipError::FeatureNotSupported("more than 65535 split volumes"))?;
+        }
+
+        let mut cd_position = None;
+        let cd_start = writer.writer.offset();
+        let mut entry_disks = Vec::with_capacity(writer.cd_entries.len());
+
+        for entry in &writer.cd_entries {
+            let filename_basic =
+                entry.entry.filename().alternative().unwrap_or_else(|| entry.entry.filename().as_bytes());
+            let comment_basic = entry.entry.comment().alternative().unwrap_or_else(|| entry.entry.comment().as_bytes());
+
+            // Start a new volume if this central directory header wouldn't fit in the current one.
+            let record_length = (SIGNATURE_LENGTH + CDH_LENGTH + filename_basic.len() + comment_basic.len()) as u64
+                + entry.entry.extra_fields().count_bytes() as u64;
+            let split_writer = writer.writer.inner_mut();
+            if record_length > split_writer.volume_size() {
+                return Err(ZipError::FeatureNotSupported("central directory headers larger than a split volume"));
+            }
+            if split_writer.remaining() < record_length {
+                poll_fn(|cx| split_writer.poll_next_volume(cx)).await?;
+            }
+
+            let position = writer.writer.inner_mut().position();
+            cd_position.get_or_insert(position);
+            entry_disks.push(position.0);
+            writer.writer.write_all(&CDH_SIGNATURE.to_le_bytes()).await?;
+            writer.writer.write_all(&entry.header.as_slice()).await?;
+            writer.writer.write_all(filename_basic).await?;
+            writer.writer.write_all(&entry.entry.extra_fields().as_bytes()).await?;
+            writer.writer.write_all(comment_basic).await?;
+        }
+
+        let (cd_disk, cd_offset) = cd_position.unwrap_or_else(|| writer.writer.inner_mut().position());
+        let central_directory_size = writer.writer.offset() - cd_start;
+        let num_entries_in_directory = writer.cd_entries.len() as u64;
+
+        if num_entries_in_directory > NON_ZIP64_MAX_NUM_FILES as u64 {
+            if writer.force_no_zip64 {
+                return Err(ZipError::Zip64Needed(Zip64ErrorCase::TooManyFiles));
+            }
+            writer.is_zip64 = true;
+        }
+        if central_directory_size > NON_ZIP64_MAX_SIZE as u64 {
+            if writer.force_no_zip64 {
+                return Err(ZipError::Zip64Needed(Zip64ErrorCase::LargeFile));
+            }
+            writer.is_zip64 = true;
+        }
+
+        // Start a new volume if the end of central directory record (and ZIP64 structs) wouldn't fit in this one.
+        let mut tail_length = (SIGNATURE_LENGTH + EOCDR_LENGTH + comment.len()) as u64;
+        if writer.is_zip64 {
+            tail_length += (SIGNATURE_LENGTH + ZIP64_EOCDR_LENGTH) as u64 + ZIP64_EOCDL_LENGTH;
+        }
+        if tail_length > writer.writer.inner_mut().volume_size() {
+            return Err(ZipError::CommentTooLarge);
+        }
+        if writer.writer.inner_mut().remaining() < tail_length {
+            let split_writer = writer.writer.inner_mut();
+            poll_fn(|cx| split_writer.poll_next_volume(cx)).await?;
+        }
+
+        let (disk, _) = writer.writer.inner_mut().position();
+        let num_entries_on_disk = entry_disks.iter().filter(|entry_disk| **entry_disk == disk).count() as u64;
+        let num_disks = disk + 1;
+        let disk_u16: u16 =
+            disk.try_into().map_err(|_| ZipError::FeatureNotSupported("more than 65535 split volumes"))?;
+
+        if writer.is_zip64 {
+            let (_, eocdr_offset) = writer.writer.inner_mut().position();
+
+            let eocdr = Zip64EndOfCentralDirectoryRecord {
+                size_of_zip64_end_of_cd_record: 44,
+                version_made_by: crate::spec::version::as_made_by(AttributeCompatibility::Unix),
+                version_needed_to_extract: 46,
+                disk_number: disk,
+                disk_number_start_of_cd: cd_disk,
+                num_entries_in_directory_on_disk: num_entries_on_disk,
+                num_entries_in_directory,
+                directory_size: central_directory_size,
+                offset_of_start_of_directory: cd_offset,
+            };
+            writer.writer.write_all(&ZIP64_EOCDR_SIGNATURE.to_le_bytes()).await?;
+            writer.writer.write_all(&eocdr.as_bytes()).await?;
+
+            let eocdl = Zip64EndOfCentralDirectoryLocator {
+                number_of_disk_with_start_of_zip64_end_of_central_directory: disk,
+                relative_offset: eocdr_offset,
+                total_number_of_disks: num_disks,
+            };
+            writer.writer.write_all(&ZIP64_EOCDL_SIGNATURE.to_le_bytes()).await?;
+            writer.writer.write_all(&eocdl.as_bytes()).await?;
+        }
+
+        let saturate_entries = |value: u64| std::cmp::min(value, NON_ZIP64_MAX_NUM_FILES as u64) as u16;
+        let header = EndOfCentralDirectoryHeader {
+            disk_num: disk_u16,
+            start_cent_dir_disk: cd_disk as u16,
+            num_of_entries_disk: saturate_entries(num_entries_on_disk),
+            num_of_entries: saturate_entries(num_entries_in_directory),
+            size_cent_dir: std::cmp::min(central_directory_size, NON_ZIP64_MAX_SIZE as u64) as u32,
+            cent_dir_offset: cd_offset as u32,
+            file_comm_length: comment.len().try_into().map_err(|_| ZipError::CommentTooLarge)?,
+        };
+
+        writer.writer.write_all(&EOCDR_SIGNATURE.to_le_bytes()).await?;
+        writer.writer.write_all(&header.as_slice()).await?;
+        writer.writer.write_all(&comment).await?;
+
+        Ok(writer.writer.into_inner().into_inner())
+    }
+}
@@ -0,0 +1,160 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+//! Pluggable codecs for compression methods which aren't supported by this crate directly.
+//!
+//! Entries can be written with a custom codec via [`ZipEntryBuilder::custom_compression()`], which emits the given
+//! method id and uses the codec to compress the entry's data. For reading, a codec is registered with an individual
+//! reader via its `register_codec()` method (eg. [`mem::ZipFileReader::register_codec()`]). Entries using a method
+//! which isn't supported by this crate can still be listed, but reading their data fails with
+//! [`ZipError::CompressionNotSupported`] unless a codec has been registered for the method id.
+//!
+//! The ids of methods implemented by this crate (0, 6, 8, 9, 12, 14, 93, 95, & 99) are reserved regardless of which
+//! features are enabled, and can't be used for a custom codec.
+//!
+//! Codecs compress & decompress data incrementally via an [`Encoder`] or [`Decoder`] which is created per entry, so
+//! an entry's data doesn't need to be buffered in full whilst it's written or read.
+//!
+//! ### Example
+//! ```
+//! # use async_zip::codec::{Codec, Decoder, Encoder};
+//! # use futures_lite::io::{AsyncBufRead, AsyncRead, AsyncWrite};
+//! # use std::pin::Pin;
+//! # use std::sync::Arc;
+//! # use std::task::{ready, Context, Poll};
+//! #
+//! #[derive(Debug)]
+//! struct Xor(u8);
+//!
+//! impl Codec for Xor {
+//!     fn encoder(&self) -> Box<dyn Encoder> {
+//!         Box::new(Xor(self.0))
+//!     }
+//!
+//!     fn decoder(&self) -> Box<dyn Decoder> {
+//!         Box::new(Xor(self.0))
+//!     }
+//! }
+//!
+//! impl Encoder for Xor {
+//!     fn poll_encode(
+//!         &mut self,
+//!         cx: &mut Context<'_>,
+//!         output: Pin<&mut dyn AsyncWrite>,
+//!         input: &[u8],
+//!     ) -> Poll<std::io::Result<usize>> {
+//!         let encoded: Vec<u8> = input.iter().map(|byte| byte ^ self.0).collect();
+//!         output.poll_write(cx, &encoded)
+//!     }
+//!
+//!     fn poll_finish(&mut self, _: &mut Context<'_>, _: Pin<&mut dyn AsyncWrite>) -> Poll<std::io::Result<()>> {
+//!         Poll::Ready(Ok(()))
+//!     }
+//! }
+//!
+//! impl Decoder for Xor {
+//!     fn poll_decode(
+//!         &mut self,
+//!         cx: &mut Context<'_>,
+//!         input: Pin<&mut dyn AsyncBufRead>,
+//!         output: &mut [u8],
+//!     ) -> Poll<std::io::Result<usize>> {
+//!         let read = ready!(input.poll_read(cx, output))?;
+//!         output[..read].iter_mut().for_each(|byte| *byte ^= self.0);
+//!         Poll::Ready(Ok(read))
+//!     }
+//! }
+//!
+//! # async fn run(mut reader: async_zip::base::read::mem::ZipFileReader) -> async_zip::error::Result<()> {
+//! reader.register_codec(0xABCD, Arc::new(Xor(0x5A)))?;
+//! #     Ok(())
+//! # }
+//! ```
+//!
+//! [`ZipEntryBuilder::custom_compression()`]: crate::ZipEntryBuilder::custom_compression
+//! [`mem::ZipFileReader::register_codec()`]: crate::base::read::mem::ZipFileReader::register_codec
+//! [`ZipError::CompressionNotSupported`]: crate::error::ZipError::CompressionNotSupported
+
+use crate::entry::ZipEntry;
+use crate::error::{Result, ZipError};
+use crate::spec::Compression;
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use futures_lite::io::{AsyncBufRead, AsyncWrite};
+
+/// A compression method implemented outside of this crate.
+pub trait Codec: Debug + Send + Sync {
+    /// Returns a new encoder which compresses the data of a single entry.
+    fn encoder(&self) -> Box<dyn Encoder>;
+
+    /// Returns a new decoder which decompresses the data of a single entry.
+    fn decoder(&self) -> Box<dyn Decoder>;
+}
+
+/// Incrementally compresses an entry's data as it's written.
+pub trait Encoder: Send {
+    /// Compresses a prefix of the input, writing any output to the provided writer, and returns the number of input
+    /// bytes which were consumed.
+    fn poll_encode(
+        &mut self,
+        cx: &mut Context<'_>,
+        output: Pin<&mut dyn AsyncWrite>,
+        input: &[u8],
+    ) -> Poll<std::io::Result<usize>>;
+
+    /// Writes any remaining output to the provided writer once all of the entry's data has been encoded.
+    ///
+    /// This may be called again after returning [`Poll::Pending`], until it returns [`Poll::Ready`].
+    fn poll_finish(&mut self, cx: &mut Context<'_>, output: Pin<&mut dyn AsyncWrite>) -> Poll<std::io::Result<()>>;
+}
+
+/// Incrementally decompresses an entry's data as it's read.
+pub trait Decoder: Send {
+    /// Reads compressed data from the provided reader & decompresses it into the output buffer, returning the number
+    /// of bytes which were decompressed.
+    ///
+    /// The provided reader only yields the entry's compressed data, and zero should be returned once the entry's data
+    /// has been decompressed in full.
+    fn poll_decode(
+        &mut self,
+        cx: &mut Context<'_>,
+        input: Pin<&mut dyn AsyncBufRead>,
+        output: &mut [u8],
+    ) -> Poll<std::io::Result<usize>>;
+}
+
+/// The codecs registered with a reader, keyed by their method id.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct CodecRegistry(HashMap<u16, Arc<dyn Codec>>);
+
+impl CodecRegistry {
+    /// Registers a codec for the provided method id, replacing any codec previously registered for it.
+    ///
+    /// Returns [`ZipError::CompressionMethodReserved`] if the method id belongs to a method implemented by this crate.
+    pub(crate) fn register(&mut self, method: u16, codec: Arc<dyn Codec>) -> Result<()> {
+        if Compression::is_reserved(method) {
+            return Err(ZipError::CompressionMethodReserved(method));
+        }
+
+        self.0.insert(method, codec);
+        Ok(())
+    }
+
+    /// Returns the codec registered for the entry's compression method if it's custom.
+    ///
+    /// Returns [`ZipError::CompressionNotSupported`] if the method is custom but no codec has been registered for it.
+    pub(crate) fn resolve(&self, entry: &ZipEntry) -> Result<Option<Arc<dyn Codec>>> {
+        match entry.compression() {
+            Compression::Custom(method) => match self.0.get(&method) {
+                Some(codec) => Ok(Some(codec.clone())),
+                None => Err(ZipError::CompressionNotSupported(method)),
+            },
+            _ => Ok(None),
+        }
+    }
+}
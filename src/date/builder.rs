@@ -4,6 +4,7 @@
 use crate::ZipDateTime;
 
 /// A builder for [`ZipDateTime`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct ZipDateTimeBuilder(pub(crate) ZipDateTime);
 
 impl From<ZipDateTime> for ZipDateTimeBuilder {
@@ -8,6 +8,8 @@ use chrono::{DateTime, Datelike, LocalResult, TimeZone, Timelike, Utc};
 
 use self::builder::ZipDateTimeBuilder;
 
+use std::time::{SystemTime, UNIX_EPOCH};
+
 // https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md#446
 // https://learn.microsoft.com/en-us/windows/win32/api/oleauto/nf-oleauto-dosdatetimetovarianttime
 
@@ -51,6 +53,51 @@ impl ZipDateTime {
         ((self.time & 0x1F) << 1).into()
     }
 
+    /// Constructs this date & time from a [`SystemTime`], interpreted as UTC.
+    ///
+    /// Times which can't be represented (ie. before 1980 or after 2107) are clamped to the nearest representable time.
+    pub fn from_system_time(time: SystemTime) -> Self {
+        let Ok(elapsed) = time.duration_since(UNIX_EPOCH) else {
+            return Self::from_civil(1980, 1, 1, 0, 0, 0);
+        };
+
+        let seconds = elapsed.as_secs();
+        let (year, month, day) = civil_from_days(seconds / 86400);
+        let seconds = (seconds % 86400) as u32;
+
+        Self::from_civil(year, month, day, seconds / 3600, seconds / 60 % 60, seconds % 60)
+    }
+
+    /// Returns the number of seconds since the Unix epoch of this date & time, interpreted as UTC.
+    ///
+    /// Returns `None` if the date is invalid (eg. a zero month or day, as written by some tools when no date is set).
+    pub(crate) fn unix_timestamp(&self) -> Option<i64> {
+        let (month, day) = (self.month(), self.day());
+        if !(1..=12).contains(&month) || day == 0 {
+            return None;
+        }
+
+        let days = days_from_civil(self.year() as i64, month, day);
+        Some(days * 86400 + (self.hour() * 3600 + self.minute() * 60 + self.second()) as i64)
+    }
+
+    /// Constructs this date & time from its components, clamping those outside of the representable range.
+    fn from_civil(year: i64, month: u32, day: u32, hour: u32, minute: u32, second: u32) -> Self {
+        let builder = match year {
+            ..1980 => ZipDateTimeBuilder::new().year(1980).month(1).day(1),
+            2108.. => ZipDateTimeBuilder::new().year(2107).month(12).day(31).hour(23).minute(59).second(58),
+            year => ZipDateTimeBuilder::new()
+                .year(year as i32)
+                .month(month)
+                .day(day)
+                .hour(hour)
+                .minute(minute)
+                .second(second),
+        };
+
+        builder.build()
+    }
+
     /// Constructs chrono's [`DateTime`] representation of this date & time.
     ///
     /// Note that this requires the `chrono` feature.
@@ -68,6 +115,44 @@ impl ZipDateTime {
     }
 }
 
+/// Converts a number of days since the Unix epoch into a (year, month, day) date in the proleptic Gregorian calendar.
+///
+/// https://howardhinnant.github.io/date_algorithms.html#civil_from_days
+fn civil_from_days(days: u64) -> (i64, u32, u32) {
+    let days = days as i64 + 719468;
+    let era = days / 146097;
+    let day_of_era = days - era * 146097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+
+    let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u32;
+    let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    (year, month, day)
+}
+
+/// Converts a (year, month, day) date in the proleptic Gregorian calendar into a number of days since the Unix epoch.
+///
+/// https://howardhinnant.github.io/date_algorithms.html#days_from_civil
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = year - i64::from(month <= 2);
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let shifted_month = if month > 2 { month - 3 } else { month + 9 } as i64;
+    let day_of_year = (153 * shifted_month + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    era * 146097 + day_of_era - 719468
+}
+
+impl From<SystemTime> for ZipDateTime {
+    fn from(value: SystemTime) -> Self {
+        ZipDateTime::from_system_time(value)
+    }
+}
+
 impl From<ZipDateTimeBuilder> for ZipDateTime {
     fn from(builder: ZipDateTimeBuilder) -> Self {
         builder.0
@@ -77,16 +162,8 @@ impl From<ZipDateTimeBuilder> for ZipDateTime {
 #[cfg(feature = "chrono")]
 impl From<&DateTime<Utc>> for ZipDateTime {
     fn from(value: &DateTime<Utc>) -> Self {
-        let mut builder = ZipDateTimeBuilder::new();
-
-        builder = builder.year(value.date_naive().year());
-        builder = builder.month(value.date_naive().month());
-        builder = builder.day(value.date_naive().day());
-        builder = builder.hour(value.time().hour());
-        builder = builder.minute(value.time().minute());
-        builder = builder.second(value.time().second());
-
-        builder.build()
+        let (date, time) = (value.date_naive(), value.time());
+        ZipDateTime::from_civil(date.year().into(), date.month(), date.day(), time.hour(), time.minute(), time.second())
     }
 }
 
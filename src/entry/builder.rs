@@ -1,11 +1,30 @@
 // Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
+use crate::base::write::PreparedEntry;
+use crate::codec::Codec;
 use crate::entry::ZipEntry;
-use crate::spec::{attribute::AttributeCompatibility, header::ExtraField, Compression};
+use crate::error::Result;
+use crate::spec::{
+    attribute::{AttributeCompatibility, DosAttributes},
+    header::{ExtraField, MetadataExtraField},
+    Compression,
+};
+#[cfg(doc)]
+use crate::{base::write::ZipFileWriter, error::ZipError};
 use crate::{date::ZipDateTime, string::ZipString};
 
+use std::sync::Arc;
+use std::time::SystemTime;
+
+/// The Unix file type bits (`S_IFDIR`) marking an entry's mode as that of a directory.
+const UNIX_DIRECTORY_MODE: u16 = 0o040000;
+
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, Utc};
+
 /// A builder for [`ZipEntry`].
+#[derive(Clone, Debug)]
 pub struct ZipEntryBuilder(pub(crate) ZipEntry);
 
 impl From<ZipEntry> for ZipEntryBuilder {
@@ -24,13 +43,29 @@ impl ZipEntryBuilder {
 
     /// Sets the entry's filename.
     pub fn filename(mut self, filename: ZipString) -> Self {
-        self.0.filename = filename;
+        self.0.filename = if self.0.directory { with_trailing_slash(filename) } else { filename };
+        self.0.utf8_flag = None;
         self
     }
 
     /// Sets the entry's compression method.
+    ///
+    /// Entries marked as a directory via [`ZipEntryBuilder::directory()`] always use the Stored compression method, so
+    /// the provided method is ignored for them.
     pub fn compression(mut self, compression: Compression) -> Self {
-        self.0.compression = compression;
+        self.0.compression = if self.0.directory { Compression::Stored } else { compression };
+        self.0.aes_marker = false;
+        self
+    }
+
+    /// Sets the entry's compression method to a custom method id, whose data is compressed via the provided codec.
+    ///
+    /// Writing the entry fails if the method id belongs to a method implemented by this crate. Readers must register
+    /// a codec for the method id in order to read the entry. See the [`codec`](crate::codec) module for more
+    /// information.
+    pub fn custom_compression(mut self, method: u16, codec: Box<dyn Codec>) -> Self {
+        self.0.compression = Compression::Custom(method);
+        self.0.codec = Some(Arc::from(codec));
         self
     }
 
@@ -64,12 +99,43 @@ impl ZipEntryBuilder {
         self
     }
 
+    /// Sets the entry's last modification date from a [`SystemTime`], interpreted as UTC.
+    ///
+    /// See [`ZipDateTime::from_system_time()`] for how unrepresentable times are handled.
+    pub fn last_modified_system_time(self, time: SystemTime) -> Self {
+        self.last_modification_date(ZipDateTime::from_system_time(time))
+    }
+
+    /// Sets the entry's last modification date from chrono's [`DateTime`] representation.
+    ///
+    /// Note that this requires the `chrono` feature.
+    #[cfg(feature = "chrono")]
+    pub fn last_modified_chrono(self, date: DateTime<Utc>) -> Self {
+        self.last_modification_date(ZipDateTime::from_chrono(&date))
+    }
+
     /// Sets the entry's internal file attribute.
     pub fn internal_file_attribute(mut self, attribute: u16) -> Self {
         self.0.internal_file_attribute = attribute;
         self
     }
 
+    /// Sets whether the entry is marked as containing text data (the low bit of the internal file attribute).
+    ///
+    /// Some extraction tools use this bit to decide whether to apply line-ending conversion. The bit set here is kept
+    /// as-is, even when [`ZipFileWriter::auto_text_detection()`] is enabled.
+    ///
+    /// [`ZipFileWriter::auto_text_detection()`]: crate::base::write::ZipFileWriter::auto_text_detection
+    pub fn text(mut self, text: bool) -> Self {
+        self.0.explicit_text = true;
+        if text {
+            self.0.internal_file_attribute |= 0x1;
+        } else {
+            self.0.internal_file_attribute &= !0x1;
+        }
+        self
+    }
+
     /// Sets the entry's external file attribute.
     pub fn external_file_attribute(mut self, attribute: u32) -> Self {
         self.0.external_file_attribute = attribute;
@@ -82,9 +148,43 @@ impl ZipEntryBuilder {
         self
     }
 
+    /// Sets a key-value metadata pair on the entry, replacing any existing value for the same key.
+    ///
+    /// Pairs are stored within a non-standard extra field which other readers will ignore, and can be read back via
+    /// [`ZipEntry::metadata()`].
+    pub fn metadata(mut self, key: &str, value: &str) -> Self {
+        let fields = &mut self.0.extra_fields;
+        let index = match fields.iter().position(|field| matches!(field, ExtraField::Metadata(_))) {
+            Some(index) => index,
+            None => {
+                fields.push(ExtraField::Metadata(MetadataExtraField { pairs: Vec::new() }));
+                fields.len() - 1
+            }
+        };
+
+        if let ExtraField::Metadata(field) = &mut fields[index] {
+            match field.pairs.iter_mut().find(|(existing, _)| existing == key) {
+                Some((_, existing)) => *existing = value.to_owned(),
+                None => field.pairs.push((key.to_owned(), value.to_owned())),
+            }
+        }
+        self
+    }
+
     /// Sets the entry's file comment.
     pub fn comment(mut self, comment: ZipString) -> Self {
         self.0.comment = comment;
+        self.0.utf8_flag = None;
+        self
+    }
+
+    /// Sets the entry's MS-DOS file attributes (eg. read-only or hidden).
+    ///
+    /// This also sets the attribute host compatibility to DOS so that extraction tools on Windows will honour them.
+    pub fn dos_attributes(mut self, attributes: DosAttributes) -> Self {
+        let attributes = if self.0.directory { attributes | DosAttributes::DIRECTORY } else { attributes };
+        self.0.attribute_compatibility = AttributeCompatibility::Dos;
+        self.0.external_file_attribute = (self.0.external_file_attribute & !0xFF) | attributes.bits() as u32;
         self
     }
 
@@ -93,11 +193,98 @@ impl ZipEntryBuilder {
     /// If the attribute host compatibility isn't set to Unix, this will have no effect.
     pub fn unix_permissions(mut self, mode: u16) -> Self {
         if matches!(self.0.attribute_compatibility, AttributeCompatibility::Unix) {
+            let mode = if self.0.directory { mode | UNIX_DIRECTORY_MODE } else { mode };
             self.0.external_file_attribute = (self.0.external_file_attribute & 0xFFFF) | (mode as u32) << 16;
         }
         self
     }
 
+    /// Marks the entry as a directory, rather than relying on the caller to follow the trailing slash convention.
+    ///
+    /// This appends a trailing slash to the entry's filename if it doesn't already end in one (including any filename
+    /// set afterwards), forces the Stored compression method, and sets the directory bit of the MS-DOS attributes. If
+    /// the attribute host compatibility is Unix, the directory file type is also set within the Unix mode, alongside
+    /// `0o755` permissions if none have been set. Writing any data to the entry will then return
+    /// [`ZipError::DirectoryWithData`], so that [`ZipEntry::dir()`] holds for it when read back.
+    ///
+    /// Passing `false` only removes the marker, leaving the entry's filename, compression method, and attributes as-is.
+    pub fn directory(mut self, enabled: bool) -> Self {
+        self.0.directory = enabled;
+
+        if enabled {
+            self.0.filename = with_trailing_slash(std::mem::replace(&mut self.0.filename, String::new().into()));
+            self.0.compression = Compression::Stored;
+            self.0.external_file_attribute |= DosAttributes::DIRECTORY.bits() as u32;
+
+            if matches!(self.0.attribute_compatibility, AttributeCompatibility::Unix) {
+                let mut mode = (self.0.external_file_attribute >> 16) as u16;
+                if mode & 0o7777 == 0 {
+                    mode |= 0o755;
+                }
+                mode = (mode & 0o7777) | UNIX_DIRECTORY_MODE;
+                self.0.external_file_attribute = (self.0.external_file_attribute & 0xFFFF) | (mode as u32) << 16;
+            }
+        }
+        self
+    }
+
+    /// Forces the UTF-8 bit of the general purpose flag on or off, rather than deriving it from the encoding of the
+    /// entry's filename & comment.
+    ///
+    /// Some extractors misbehave when the bit is set for ASCII filenames, or unset for UTF-8 filenames. Note that
+    /// setting the filename or comment afterwards clears this override.
+    pub fn utf8_flag(mut self, enabled: bool) -> Self {
+        self.0.utf8_flag = Some(enabled);
+        self
+    }
+
+    /// Skips computing the CRC32 value of the entry's data when it's written, writing a value of zero instead.
+    ///
+    /// This avoids the cost of hashing large payloads which are already known to be intact, and is intended for stored
+    /// entries within archives only ever read by trusted consumers. Writing an entry with any other compression method
+    /// returns [`ZipError::FeatureNotSupported`](crate::error::ZipError::FeatureNotSupported), as the CRC32 value is
+    /// then the only check that decompression produced the original data.
+    ///
+    /// Note that the resulting archive doesn't conform to the specification, which has no flag marking a CRC32 value as
+    /// absent (the data descriptor flag only defers the value until after the data, where it would still be zero), so
+    /// none is set. Most extraction tools will report a CRC mismatch for any non-empty entry, as will this crate's
+    /// checked reads (eg. [`read_to_end_checked()`](crate::base::read::ZipEntryReader::read_to_end_checked)).
+    pub fn skip_crc(mut self, enabled: bool) -> Self {
+        self.0.skip_crc = enabled;
+        self
+    }
+
+    /// Overrides the "version needed to extract" written for the entry, which is otherwise derived from its compression
+    /// method and whether it's a directory.
+    ///
+    /// This is an advanced option for matching the exact output expected by certain legacy tools. Advertising a version
+    /// lower than that of the features the entry actually uses may cause extractors to mishandle it.
+    pub fn version_needed(mut self, version: u16) -> Self {
+        self.0.version_needed = Some(version);
+        self
+    }
+
+    /// Checks that the entry's filename is well-formed, returning [`ZipError::InvalidFileName`] if not.
+    ///
+    /// Filenames containing NUL bytes, control characters, or backslashes are rejected, as they may break extractors
+    /// or lead to path confusion (the specification requires forward slashes as path separators). Note that
+    /// [`ZipFileWriter`] only rejects NUL bytes by default, unless [`ZipFileWriter::allow_invalid_filenames()`] is set.
+    ///
+    /// [`ZipFileWriter`]: crate::base::write::ZipFileWriter
+    /// [`ZipFileWriter::allow_invalid_filenames()`]: crate::base::write::ZipFileWriter::allow_invalid_filenames
+    pub fn validate(&self) -> Result<()> {
+        self.0.validate_filename(true)
+    }
+
+    /// Consumes this builder and compresses the provided data ahead of time, returning an entry which can later be
+    /// written via [`ZipFileWriter::write_prepared()`].
+    ///
+    /// As no ZIP writer is required, this can be used to compress many entries concurrently (eg. across multiple
+    /// tasks), with the results then written sequentially by a single writer.
+    pub async fn prepare(self, data: &[u8]) -> Result<PreparedEntry> {
+        PreparedEntry::new(self.build(), data).await
+    }
+
     /// Consumes this builder and returns a final [`ZipEntry`].
     ///
     /// This is equivalent to:
@@ -111,3 +298,18 @@ impl ZipEntryBuilder {
         self.into()
     }
 }
+
+/// Appends a trailing slash to the filename (and its alternative encoding, if any) if it doesn't already end in one.
+fn with_trailing_slash(filename: ZipString) -> ZipString {
+    if filename.as_bytes().ends_with(b"/") {
+        return filename;
+    }
+
+    match filename.alternative() {
+        Some(alternative) => ZipString::new_with_alternative(
+            format!("{}/", String::from_utf8_lossy(filename.as_bytes())),
+            [alternative, b"/"].concat(),
+        ),
+        None => ZipString::new([filename.as_bytes(), b"/"].concat(), filename.encoding()),
+    }
+}
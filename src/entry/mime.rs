@@ -0,0 +1,53 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+//! Content type guessing for entries, based on their filename extension or the magic bytes at the start of their data.
+
+use mime_guess::Mime;
+
+/// The number of leading bytes of an entry's data needed to recognise all supported magic numbers.
+pub(crate) const MAGIC_LENGTH: usize = 16;
+
+/// Known magic numbers, each as an offset, the bytes at that offset, and the matching content type.
+const MAGIC_NUMBERS: &[(usize, &[u8], &str)] = &[
+    (0, b"\x89PNG\r\n\x1a\n", "image/png"),
+    (0, b"\xFF\xD8\xFF", "image/jpeg"),
+    (0, b"GIF87a", "image/gif"),
+    (0, b"GIF89a", "image/gif"),
+    (8, b"WEBP", "image/webp"),
+    (0, b"%PDF-", "application/pdf"),
+    (0, b"PK\x03\x04", "application/zip"),
+    (0, b"\x1F\x8B", "application/gzip"),
+    (0, b"BZh", "application/x-bzip2"),
+    (0, b"\xFD7zXZ\x00", "application/x-xz"),
+    (0, b"\x28\xB5\x2F\xFD", "application/zstd"),
+    (0, b"7z\xBC\xAF\x27\x1C", "application/x-7z-compressed"),
+    (0, b"\x00asm", "application/wasm"),
+    (0, b"\x7FELF", "application/x-elf"),
+    (0, b"OggS", "audio/ogg"),
+    (0, b"fLaC", "audio/flac"),
+    (4, b"ftyp", "video/mp4"),
+    (0, b"wOFF", "font/woff"),
+    (0, b"wOF2", "font/woff2"),
+];
+
+/// Magic numbers short enough to commonly begin unrelated data (eg. text), which are only consulted when the filename's
+/// extension isn't recognised.
+const WEAK_MAGIC_NUMBERS: &[(usize, &[u8], &str)] = &[(0, b"BM", "image/bmp"), (0, b"ID3", "audio/mpeg")];
+
+/// Guesses a content type from the provided filename's extension.
+pub(crate) fn from_filename(filename: &str) -> Option<Mime> {
+    mime_guess::from_path(filename).first()
+}
+
+/// Guesses a content type from the magic number at the start of the provided data, including weak magic numbers only
+/// if requested.
+pub(crate) fn from_magic(data: &[u8], weak: bool) -> Option<Mime> {
+    let weak_magic_numbers = if weak { WEAK_MAGIC_NUMBERS } else { &[] };
+
+    MAGIC_NUMBERS
+        .iter()
+        .chain(weak_magic_numbers)
+        .find(|(offset, magic, _)| data.get(*offset..*offset + magic.len()) == Some(*magic))
+        .and_then(|(_, _, mime)| mime.parse().ok())
+}
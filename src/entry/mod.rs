@@ -2,21 +2,35 @@
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
 pub mod builder;
+#[cfg(feature = "mime")]
+pub(crate) mod mime;
 
-use std::ops::Deref;
+use std::collections::HashMap;
+use std::ops::{Deref, Range};
+use std::sync::{Arc, OnceLock};
 
-use futures_lite::io::{AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt, SeekFrom};
+use futures_lite::io::{AsyncRead, AsyncSeek, AsyncSeekExt, SeekFrom};
 
+#[cfg(feature = "legacy")]
+use crate::base::read::io::implode::ImplodeOptions;
+use crate::base::read::io::read_bytes;
+use crate::base::read::{detect_filename, get_zip64_extra_field};
+use crate::codec::Codec;
 use crate::entry::builder::ZipEntryBuilder;
 use crate::error::{Result, ZipError};
 use crate::spec::{
-    attribute::AttributeCompatibility,
-    consts::LFH_SIGNATURE,
-    header::{ExtraField, LocalFileHeader},
+    attribute::{AttributeCompatibility, DosAttributes},
+    consts::{AES_COMPRESSION_METHOD, LFH_LENGTH, LFH_SIGNATURE, SIGNATURE_LENGTH},
+    extra_field::ExtraFieldAsBytes,
+    header::{AeVersion, ExtraField, LocalFileHeader},
+    parse::parse_extra_fields,
     Compression,
 };
 use crate::{string::ZipString, ZipDateTime};
 
+#[cfg(doc)]
+use crate::{base::write::ZipFileWriter, ZipFile};
+
 /// An immutable store of data about a ZIP entry.
 ///
 /// This type cannot be directly constructed so instead, the [`ZipEntryBuilder`] must be used. Internally this builder
@@ -26,6 +40,9 @@ use crate::{string::ZipString, ZipDateTime};
 pub struct ZipEntry {
     pub(crate) filename: ZipString,
     pub(crate) compression: Compression,
+    /// Whether the entry's headers store the AES marker method (99) in place of `compression`, as is the case for
+    /// entries encrypted via WinZip's AES encryption (whose actual method is resolved from the AES extra field).
+    pub(crate) aes_marker: bool,
     #[cfg(any(
         feature = "deflate",
         feature = "bzip2",
@@ -45,6 +62,18 @@ pub struct ZipEntry {
     pub(crate) extra_fields: Vec<ExtraField>,
     pub(crate) comment: ZipString,
     pub(crate) data_descriptor: bool,
+    pub(crate) encrypted: bool,
+    pub(crate) strong_encryption: bool,
+    pub(crate) version_needed: Option<u16>,
+    pub(crate) utf8_flag: Option<bool>,
+    pub(crate) skip_crc: bool,
+    pub(crate) explicit_text: bool,
+    pub(crate) directory: bool,
+    pub(crate) codec: Option<Arc<dyn Codec>>,
+    #[cfg(feature = "legacy")]
+    pub(crate) implode_options: ImplodeOptions,
+    #[cfg(feature = "lzma")]
+    pub(crate) lzma_eos_marker: bool,
 }
 
 impl From<ZipEntryBuilder> for ZipEntry {
@@ -58,6 +87,7 @@ impl ZipEntry {
         ZipEntry {
             filename,
             compression,
+            aes_marker: false,
             #[cfg(any(
                 feature = "deflate",
                 feature = "bzip2",
@@ -77,7 +107,40 @@ impl ZipEntry {
             extra_fields: Vec::new(),
             comment: String::new().into(),
             data_descriptor: false,
+            encrypted: false,
+            strong_encryption: false,
+            version_needed: None,
+            utf8_flag: None,
+            skip_crc: false,
+            explicit_text: false,
+            directory: false,
+            codec: None,
+            #[cfg(feature = "legacy")]
+            implode_options: ImplodeOptions::default(),
+            #[cfg(feature = "lzma")]
+            lzma_eos_marker: true,
+        }
+    }
+
+    /// Checks the entry's filename (and its alternative, if any) for characters which may break extractors.
+    ///
+    /// NUL bytes are always rejected. When `strict`, control characters and backslashes are also rejected.
+    pub(crate) fn validate_filename(&self, strict: bool) -> Result<()> {
+        let filename = self.filename.as_bytes();
+        let alternative = self.filename.alternative().unwrap_or_default();
+
+        for byte in filename.iter().chain(alternative) {
+            match byte {
+                0 => return Err(ZipError::InvalidFileName("filename contains a NUL byte")),
+                0x01..=0x1F | 0x7F if strict => {
+                    return Err(ZipError::InvalidFileName("filename contains a control character"))
+                }
+                b'\\' if strict => return Err(ZipError::InvalidFileName("filename contains a backslash")),
+                _ => {}
+            }
         }
+
+        Ok(())
     }
 
     /// Returns the entry's filename.
@@ -95,6 +158,54 @@ impl ZipEntry {
         self.compression
     }
 
+    /// Returns the compression method to be stored within the entry's headers, preserving the AES marker method (99)
+    /// for entries read from an archive.
+    pub(crate) fn raw_compression(&self) -> u16 {
+        match self.aes_marker {
+            true => AES_COMPRESSION_METHOD,
+            false => self.compression.into(),
+        }
+    }
+
+    /// Returns whether the entry's data is terminated by an end-of-stream marker which must be indicated via bit 1 of
+    /// the general purpose flag (ie. LZMA data which was compressed by this crate, or read with the bit set).
+    pub(crate) fn has_eos_marker(&self) -> bool {
+        #[cfg(feature = "lzma")]
+        {
+            self.compression.has_eos_marker() && self.lzma_eos_marker
+        }
+        #[cfg(not(feature = "lzma"))]
+        {
+            self.compression.has_eos_marker()
+        }
+    }
+
+    /// Marks the entry's data as having been compressed by this crate, and so terminated by an end-of-stream marker if
+    /// its compression method requires one.
+    pub(crate) fn set_eos_marker(&mut self) {
+        #[cfg(feature = "lzma")]
+        {
+            self.lzma_eos_marker = true;
+        }
+    }
+
+    /// Returns the codec used for the entry's custom compression method, if any.
+    ///
+    /// Returns [`ZipError::CompressionNotSupported`] if the method is custom but the entry has no codec, or
+    /// [`ZipError::CompressionMethodReserved`] if the method id belongs to a method implemented by this crate.
+    pub(crate) fn custom_codec(&self) -> Result<Option<Arc<dyn Codec>>> {
+        match self.compression {
+            Compression::Custom(method) if Compression::is_reserved(method) => {
+                Err(ZipError::CompressionMethodReserved(method))
+            }
+            Compression::Custom(method) => match self.codec.clone() {
+                Some(codec) => Ok(Some(codec)),
+                None => Err(ZipError::CompressionNotSupported(method)),
+            },
+            _ => Ok(None),
+        }
+    }
+
     /// Returns the entry's CRC32 value.
     pub fn crc32(&self) -> u32 {
         self.crc32
@@ -125,6 +236,11 @@ impl ZipEntry {
         self.internal_file_attribute
     }
 
+    /// Returns whether or not the entry is marked as containing text data (the low bit of the internal file attribute).
+    pub fn text(&self) -> bool {
+        self.internal_file_attribute & 0x1 != 0
+    }
+
     /// Returns the entry's external file attribute
     pub fn external_file_attribute(&self) -> u32 {
         self.external_file_attribute
@@ -135,6 +251,94 @@ impl ZipEntry {
         &self.extra_fields
     }
 
+    /// Returns the entry's CRC-32C value, if it was written with a CRC-32C extra field.
+    pub fn crc32c(&self) -> Option<u32> {
+        self.extra_fields.iter().find_map(|field| match field {
+            ExtraField::Crc32c(field) => Some(field.crc32c),
+            _ => None,
+        })
+    }
+
+    /// Returns the entry's key-value metadata pairs, as set via [`ZipEntryBuilder::metadata()`].
+    ///
+    /// If the entry has no metadata extra field, an empty map is returned.
+    pub fn metadata(&self) -> HashMap<String, String> {
+        self.extra_fields
+            .iter()
+            .filter_map(|field| match field {
+                ExtraField::Metadata(field) => Some(field.pairs.iter().cloned()),
+                _ => None,
+            })
+            .flatten()
+            .collect()
+    }
+
+    /// Returns whether or not the entry's data is encrypted.
+    ///
+    /// Encrypted entries can still be listed as their metadata isn't encrypted, but attempting to read their data will
+    /// result in an error as encryption isn't currently supported.
+    pub fn encrypted(&self) -> bool {
+        self.encrypted
+    }
+
+    /// Returns the entry's modification time as seconds since the Unix epoch, if it has an extended timestamp extra
+    /// field which stores one.
+    ///
+    /// Unlike [`ZipEntry::last_modification_date()`], this has a granularity of one second and isn't subject to the
+    /// local time zone of the writer.
+    pub fn extended_modification_time(&self) -> Option<u32> {
+        self.extra_fields.iter().find_map(|field| match field {
+            ExtraField::ExtendedTimestamp(field) => field.modification_time,
+            _ => None,
+        })
+    }
+
+    /// Returns the vendor version of the entry's AES encryption, if it was encrypted via WinZip's AES encryption.
+    ///
+    /// AE-2 entries store a zero CRC-32, so [`ZipEntry::crc32()`] can't be used to verify their data.
+    pub fn aes_version(&self) -> Option<AeVersion> {
+        self.extra_fields.iter().find_map(|field| match field {
+            ExtraField::Aes(field) => Some(field.version),
+            _ => None,
+        })
+    }
+
+    /// Returns whether or not the entry's data is protected by PKWARE's strong encryption.
+    ///
+    /// Strong encryption isn't supported, and attempting to read the data of such an entry will result in a
+    /// [`ZipError::StrongEncryptionUnsupported`] error.
+    ///
+    /// [`ZipError::StrongEncryptionUnsupported`]: crate::error::ZipError::StrongEncryptionUnsupported
+    pub fn strong_encryption(&self) -> bool {
+        self.strong_encryption
+    }
+
+    /// Returns the minimum ZIP specification version needed to extract the entry.
+    ///
+    /// For entries read from an archive, this is the value stored within its header. Otherwise, it's the value which
+    /// would be written for the entry: either that set via [`ZipEntryBuilder::version_needed()`], or one based on its
+    /// compression method and whether it's a directory.
+    pub fn version_needed_to_extract(&self) -> u16 {
+        self.version_needed.unwrap_or_else(|| crate::spec::version::as_needed_to_extract(self))
+    }
+
+    /// Returns the state of the UTF-8 bit of the general purpose flag, if the entry was read from an archive or the bit
+    /// was set via [`ZipEntryBuilder::utf8_flag()`].
+    ///
+    /// When such an entry is written, this bit is preserved rather than being derived from the encoding of the entry's
+    /// filename & comment. Setting either via [`ZipEntryBuilder`] clears the preserved bit.
+    pub fn utf8_flag(&self) -> Option<bool> {
+        self.utf8_flag
+    }
+
+    /// Returns whether CRC32 computation is skipped when writing the entry, as set via
+    /// [`ZipEntryBuilder::skip_crc()`].
+    ///
+    /// This is always false for entries read from an archive.
+    pub fn skip_crc(&self) -> bool {
+        self.skip_crc
+    }
+
     /// Returns the entry's file comment.
     pub fn comment(&self) -> &ZipString {
         &self.comment
@@ -152,25 +356,112 @@ impl ZipEntry {
         Some(((self.external_file_attribute) >> 16) as u16)
     }
 
+    /// Returns the entry's MS-DOS file attributes, stored within the low byte of the external file attribute.
+    ///
+    /// Most tools populate these bits regardless of the attribute host compatibility.
+    pub fn dos_attributes(&self) -> DosAttributes {
+        DosAttributes::from_bits(self.external_file_attribute as u8)
+    }
+
     /// Returns whether or not the entry represents a directory.
     pub fn dir(&self) -> Result<bool> {
         Ok(self.filename.as_str()?.ends_with('/'))
     }
+
+    /// Returns an error if the entry was marked as a directory via [`ZipEntryBuilder::directory()`] but is being
+    /// written with data.
+    pub(crate) fn check_directory_size(&self, uncompressed_size: u64) -> Result<()> {
+        if self.directory && uncompressed_size > 0 {
+            let filename = String::from_utf8_lossy(self.filename.as_bytes()).into_owned();
+            return Err(crate::error::ZipError::DirectoryWithData(filename));
+        }
+        Ok(())
+    }
+
+    /// Returns an error if CRC32 computation is skipped for an entry which isn't stored, as the CRC32 value is then the
+    /// only check that decompression produced the original data.
+    pub(crate) fn check_skip_crc(&self) -> Result<()> {
+        if self.skip_crc && self.compression != Compression::Stored {
+            return Err(crate::error::ZipError::FeatureNotSupported("skipping the CRC32 of compressed entries"));
+        }
+        Ok(())
+    }
+
+    /// Returns a guess of the entry's content type based on its filename extension, or `None` if the extension isn't
+    /// recognised or the entry represents a directory.
+    ///
+    /// See [`ZipEntryReader::guessed_content_type()`] for a guess which also considers the entry's data.
+    ///
+    /// [`ZipEntryReader::guessed_content_type()`]: crate::base::read::ZipEntryReader::guessed_content_type
+    #[cfg(feature = "mime")]
+    pub fn guessed_content_type(&self) -> Option<mime_guess::Mime> {
+        let filename = String::from_utf8_lossy(self.filename.as_bytes());
+
+        if filename.ends_with('/') {
+            return None;
+        }
+        mime::from_filename(&filename)
+    }
 }
 
 /// An immutable store of data about how a ZIP entry is stored within a specific archive.
 ///
 /// Besides storing archive independent information like the size and timestamp it can also be used to query
 /// information about how the entry is stored in an archive.
-#[derive(Clone)]
+#[derive(Clone, Debug)]
 pub struct StoredZipEntry {
     pub(crate) entry: ZipEntry,
     // pub(crate) general_purpose_flag: GeneralPurposeFlag,
     pub(crate) file_offset: u64,
     pub(crate) header_size: u64,
+    pub(crate) version_made_by: u16,
+    /// The combined size of the local file header, filename, and extra fields, once it has been read.
+    pub(crate) local_header_size: OnceLock<u64>,
+}
+
+impl From<&StoredZipEntry> for ZipEntry {
+    fn from(entry: &StoredZipEntry) -> Self {
+        entry.to_entry()
+    }
 }
 
 impl StoredZipEntry {
+    /// Constructs a new stored entry whose local file header is located at the provided offset.
+    ///
+    /// The header size is derived from the entry's filename and extra fields, as they would be written by a
+    /// [`ZipFileWriter`]. This is useful alongside [`ZipFileBuilder`](crate::ZipFileBuilder) for synthesising archive
+    /// listings.
+    pub fn new(entry: ZipEntry, header_offset: u64) -> Self {
+        let filename = entry.filename.alternative().unwrap_or_else(|| entry.filename.as_bytes());
+        let trailing_size = filename.len() + entry.extra_fields.as_slice().count_bytes();
+        let version_made_by = crate::spec::version::as_made_by(entry.attribute_compatibility);
+
+        StoredZipEntry {
+            entry,
+            file_offset: header_offset,
+            header_size: (SIGNATURE_LENGTH + LFH_LENGTH + trailing_size) as u64,
+            version_made_by,
+            local_header_size: OnceLock::new(),
+        }
+    }
+
+    /// Returns the host system and ZIP specification version which the entry was made by, as stored within the
+    /// central directory.
+    ///
+    /// The host system determines how the entry's external file attribute should be interpreted, and is returned as
+    /// its raw value since archives may be made by hosts which aren't supported by this crate (see
+    /// [`StoredZipEntry::made_by_compatibility()`]). The specification version is stored as `10 * major + minor` (eg.
+    /// 63 for version 6.3). For entries recovered without a central directory, this reflects the defaults used for
+    /// the entry's attribute host compatibility.
+    pub fn made_by(&self) -> (u8, u8) {
+        ((self.version_made_by >> 8) as u8, self.version_made_by as u8)
+    }
+
+    /// Returns the host system which the entry was made by, or [`None`] if it isn't supported by this crate.
+    pub fn made_by_compatibility(&self) -> Option<AttributeCompatibility> {
+        AttributeCompatibility::try_from(self.version_made_by >> 8).ok()
+    }
+
     /// Returns the offset in bytes to where the header of the entry starts.
     pub fn header_offset(&self) -> u64 {
         self.file_offset
@@ -184,27 +475,106 @@ impl StoredZipEntry {
         self.header_size
     }
 
-    /// Seek to the offset in bytes where the data of the entry starts.
-    pub(crate) async fn seek_to_data_offset<R: AsyncRead + AsyncSeek + Unpin>(&self, mut reader: &mut R) -> Result<()> {
-        // Seek to the header
+    /// Returns whether or not this entry has a ZIP64 extended information extra field.
+    ///
+    /// This is independent of whether the archive as a whole is ZIP64 (see [`ZipFile::zip64()`]), as a ZIP64 archive
+    /// may contain entries which don't require the extended field.
+    pub fn is_zip64(&self) -> bool {
+        get_zip64_extra_field(&self.entry.extra_fields).is_some()
+    }
+
+    /// Returns the compression method of the entry's underlying data.
+    ///
+    /// For entries encrypted via WinZip's AES encryption (stored with method 99), this is the method held within the
+    /// AES extra field, which is applied to the data before encryption. Otherwise, this is the entry's stored method.
+    /// Entries read by this crate already resolve method 99 in [`ZipEntry::compression()`], so the two only differ
+    /// for entries constructed with an AES extra field which disagrees with their stated method.
+    pub fn effective_compression(&self) -> Compression {
+        let aes_compression = self.entry.extra_fields.iter().find_map(|field| match field {
+            ExtraField::Aes(field) => Compression::try_from(field.compression).ok(),
+            _ => None,
+        });
+
+        aes_compression.unwrap_or(self.entry.compression)
+    }
+
+    /// Returns a writable copy of this entry, preserving its filename, timestamps, attributes, extra fields & comment.
+    ///
+    /// Fields that are managed by the writer (the ZIP64 extended information extra field, the data descriptor flag, and
+    /// the version needed to extract) are removed, so the returned entry can be passed directly to a [`ZipFileWriter`]
+    /// alongside its data. As that data is written unencrypted, any encryption flags & AES extra field are also
+    /// removed, leaving the entry's actual compression method in place of the AES marker method.
+    pub fn to_entry(&self) -> ZipEntry {
+        let mut entry = self.entry.clone();
+        entry
+            .extra_fields
+            .retain(|field| !matches!(field, ExtraField::Zip64ExtendedInformation(_) | ExtraField::Aes(_)));
+        entry.aes_marker = false;
+        entry.data_descriptor = false;
+        entry.encrypted = false;
+        entry.strong_encryption = false;
+        entry.version_needed = None;
+        entry
+    }
+
+    /// Reads and returns the filename stored within the entry's local file header.
+    ///
+    /// Readers within this crate trust the filename stored within the central directory, but some tools produce (or
+    /// tamper with) archives where the two differ. Comparing the returned filename against [`ZipEntry::filename()`]
+    /// allows such discrepancies to be detected.
+    ///
+    /// Providing a reader over a different source to the one this entry was read from may lead to inaccurate parsing.
+    pub async fn local_filename<R: AsyncRead + AsyncSeek + Unpin>(&self, mut reader: &mut R) -> Result<ZipString> {
         reader.seek(SeekFrom::Start(self.file_offset)).await?;
+        crate::utils::assert_signature(&mut reader, LFH_SIGNATURE).await?;
+
+        let header = LocalFileHeader::from_reader(&mut reader).await?;
+        let filename_basic = read_bytes(&mut reader, header.file_name_length.into()).await?;
+        let extra_field = read_bytes(&mut reader, header.extra_field_length.into()).await?;
+        let extra_fields = parse_extra_fields(extra_field, header.uncompressed_size, header.compressed_size)?;
+
+        Ok(detect_filename(filename_basic, header.flags.filename_unicode, &extra_fields))
+    }
 
-        // Check the signature
-        let signature = {
-            let mut buffer = [0; 4];
-            reader.read_exact(&mut buffer).await?;
-            u32::from_le_bytes(buffer)
-        };
+    /// Returns the absolute byte range of the entry's compressed data within the provided reader.
+    ///
+    /// The local file header is read to locate the start of the data, as the lengths of its filename and extra fields
+    /// may differ from those stored within the central directory. Its size is then cached, so repeated calls (as well
+    /// as subsequent reads of this entry's data) don't need to read it again.
+    ///
+    /// Providing a reader over a different source to the one this entry was read from may lead to inaccurate parsing.
+    pub async fn compressed_data_range<R: AsyncRead + AsyncSeek + Unpin>(&self, reader: &mut R) -> Result<Range<u64>> {
+        let start = self.file_offset + self.local_header_size(reader).await?;
+        Ok(start..start + self.entry.compressed_size)
+    }
 
-        match signature {
-            LFH_SIGNATURE => (),
-            actual => return Err(ZipError::UnexpectedHeaderError(actual, LFH_SIGNATURE)),
-        };
+    /// Returns the combined size of the local file header, filename, and extra fields, reading it if not yet cached.
+    async fn local_header_size<R: AsyncRead + AsyncSeek + Unpin>(&self, mut reader: &mut R) -> Result<u64> {
+        if let Some(size) = self.local_header_size.get() {
+            return Ok(*size);
+        }
+
+        reader.seek(SeekFrom::Start(self.file_offset)).await?;
+        crate::utils::assert_signature(&mut reader, LFH_SIGNATURE).await?;
 
-        // Skip the local file header and trailing data
         let header = LocalFileHeader::from_reader(&mut reader).await?;
-        let trailing_size = (header.file_name_length as i64) + (header.extra_field_length as i64);
-        reader.seek(SeekFrom::Current(trailing_size)).await?;
+        let size =
+            (SIGNATURE_LENGTH + LFH_LENGTH) as u64 + header.file_name_length as u64 + header.extra_field_length as u64;
+
+        Ok(*self.local_header_size.get_or_init(|| size))
+    }
+
+    /// Seek to the offset in bytes where the data of the entry starts.
+    pub(crate) async fn seek_to_data_offset<R: AsyncRead + AsyncSeek + Unpin>(&self, reader: &mut R) -> Result<()> {
+        if self.entry.strong_encryption {
+            return Err(ZipError::StrongEncryptionUnsupported);
+        }
+        if self.entry.encrypted {
+            return Err(ZipError::FeatureNotSupported("encryption"));
+        }
+
+        let start = self.file_offset + self.local_header_size(reader).await?;
+        reader.seek(SeekFrom::Start(start)).await?;
 
         Ok(())
     }
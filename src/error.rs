@@ -3,6 +3,8 @@
 
 //! A module which holds relevant error reporting structures/types.
 
+use crate::spec::Compression;
+
 use std::fmt::{Display, Formatter};
 use thiserror::Error;
 
@@ -30,8 +32,14 @@ impl Display for Zip64ErrorCase {
 pub enum ZipError {
     #[error("feature not supported: '{0}'")]
     FeatureNotSupported(&'static str),
+    #[error("entry is protected by PKWARE strong encryption, which isn't supported")]
+    StrongEncryptionUnsupported,
     #[error("compression not supported: {0}")]
     CompressionNotSupported(u16),
+    #[error("compression method {0} is implemented by this crate and can't be used for a custom codec")]
+    CompressionMethodReserved(u16),
+    #[error("compression method '{0}' not supported (supported methods: {1})")]
+    CompressionNameNotSupported(String, String),
     #[error("host attribute compatibility not supported: {0}")]
     AttributeCompatibilityNotSupported(u16),
     #[error("attempted to read a ZIP64 file whilst on a 32-bit target")]
@@ -46,27 +54,60 @@ pub enum ZipError {
     CommentTooLarge,
     #[error("filename exceeded maximum size")]
     FileNameTooLarge,
+    #[error("filename was invalid: {0}")]
+    InvalidFileName(&'static str),
+    #[error("an entry with the filename '{0}' has already been written")]
+    DuplicateFileName(String),
+    #[error("the directory entry '{0}' was written with data")]
+    DirectoryWithData(String),
     #[error("attempted to convert non-UTF8 bytes to a string/str")]
     StringNotUtf8,
 
     #[error("unable to locate the end of central directory record")]
     UnableToLocateEOCDR,
+    #[error("the archive contained no data (a valid empty archive still contains an end of central directory record)")]
+    EmptyArchive,
     #[error("extra field size was indicated to be {0} but only {1} bytes remain")]
     InvalidExtraFieldHeader(u16, usize),
     #[error("zip64 extended information field was incomplete")]
     Zip64ExtendedFieldIncomplete,
 
     #[error("an upstream reader returned an error: {0}")]
-    UpstreamReadError(#[from] std::io::Error),
+    UpstreamReadError(#[source] std::io::Error),
+    #[error("the {method} compressed data of an entry was invalid: {source}")]
+    DecompressionError { method: Compression, source: std::io::Error },
     #[error("a computed CRC32 value did not match the expected value")]
     CRC32CheckError,
+    #[error("an entry's data descriptor did not match its central directory record")]
+    DataDescriptorMismatch,
+    #[error("the central directory was estimated to need {0} bytes of memory, exceeding the budget of {1} bytes")]
+    MemoryBudgetExceeded(usize, usize),
     #[error("entry index was out of bounds")]
     EntryIndexOutOfBounds,
     #[error("Encountered an unexpected header (actual: {0:#x}, expected: {1:#x}).")]
     UnexpectedHeaderError(u32, u32),
+    #[error("the central directory did not match the {0} entries declared by the end of central directory record")]
+    CentralDirectoryMismatch(u64),
 
     #[error("Info-ZIP Unicode Comment Extra Field was incomplete")]
     InfoZipUnicodeCommentFieldIncomplete,
     #[error("Info-ZIP Unicode Path Extra Field was incomplete")]
     InfoZipUnicodePathFieldIncomplete,
+    #[error("a computed CRC-32C value did not match the expected value")]
+    CRC32CCheckError,
+}
+
+impl From<std::io::Error> for ZipError {
+    /// Converts an IO error into a [`ZipError::UpstreamReadError`], unless it carries a [`ZipError`] (eg. a
+    /// [`ZipError::DecompressionError`] returned via [`AsyncRead`]), in which case that error is unwrapped.
+    ///
+    /// [`AsyncRead`]: futures_lite::io::AsyncRead
+    fn from(err: std::io::Error) -> Self {
+        if !err.get_ref().is_some_and(|inner| inner.is::<ZipError>()) {
+            return ZipError::UpstreamReadError(err);
+        }
+
+        let inner = err.into_inner().expect("error has an inner value");
+        *inner.downcast::<ZipError>().expect("inner value is a zip error")
+    }
 }
@@ -1,9 +1,10 @@
 // Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
-use crate::{file::ZipFile, string::ZipString};
+use crate::{entry::StoredZipEntry, file::ZipFile, string::ZipString};
 
 /// A builder for [`ZipFile`].
+#[derive(Clone, Debug)]
 pub struct ZipFileBuilder(pub(crate) ZipFile);
 
 impl From<ZipFile> for ZipFileBuilder {
@@ -14,7 +15,18 @@ impl From<ZipFile> for ZipFileBuilder {
 
 impl Default for ZipFileBuilder {
     fn default() -> Self {
-        ZipFileBuilder(ZipFile { entries: Vec::new(), zip64: false, comment: String::new().into() })
+        ZipFileBuilder(ZipFile {
+            entries: Vec::new(),
+            zip64: false,
+            comment: String::new().into(),
+            recovered: false,
+            comment_length_mismatch: false,
+            zip64_extensible_data: Vec::new(),
+            disk_number: 0,
+            total_disks: 1,
+            #[cfg(feature = "indexmap")]
+            entry_map: std::sync::OnceLock::new(),
+        })
     }
 }
 
@@ -23,12 +35,26 @@ impl ZipFileBuilder {
         Self::default()
     }
 
+    /// Appends an entry to the file's list of entries.
+    pub fn add_entry(mut self, entry: StoredZipEntry) -> Self {
+        self.0.entries.push(entry);
+        #[cfg(feature = "indexmap")]
+        self.0.entry_map.take();
+        self
+    }
+
     /// Sets the file's comment.
     pub fn comment(mut self, comment: ZipString) -> Self {
         self.0.comment = comment;
         self
     }
 
+    /// Sets whether or not the file is zip64.
+    pub fn zip64(mut self, zip64: bool) -> Self {
+        self.0.zip64 = zip64;
+        self
+    }
+
     /// Consumes this builder and returns a final [`ZipFile`].
     ///
     /// This is equivalent to:
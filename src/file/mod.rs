@@ -6,12 +6,22 @@ pub(crate) mod builder;
 use crate::{entry::StoredZipEntry, string::ZipString};
 use builder::ZipFileBuilder;
 
+use std::collections::HashMap;
+use std::ops::Range;
+
 /// An immutable store of data about a ZIP file.
-#[derive(Clone)]
+#[derive(Clone, Debug)]
 pub struct ZipFile {
     pub(crate) entries: Vec<StoredZipEntry>,
     pub(crate) zip64: bool,
     pub(crate) comment: ZipString,
+    pub(crate) recovered: bool,
+    pub(crate) comment_length_mismatch: bool,
+    pub(crate) zip64_extensible_data: Vec<u8>,
+    pub(crate) disk_number: u32,
+    pub(crate) total_disks: u32,
+    #[cfg(feature = "indexmap")]
+    pub(crate) entry_map: std::sync::OnceLock<indexmap::IndexMap<String, usize>>,
 }
 
 impl From<ZipFileBuilder> for ZipFile {
@@ -26,6 +36,25 @@ impl ZipFile {
         &self.entries
     }
 
+    /// Returns the indices of this ZIP file's entries which match the provided predicate.
+    ///
+    /// As the predicate only has access to each entry's metadata, this allows selecting a subset of entries (eg. by
+    /// filename extension or size) before any of their data is read or decompressed.
+    pub fn entries_filtered<'a, P>(&'a self, predicate: P) -> impl Iterator<Item = usize> + 'a
+    where
+        P: Fn(&StoredZipEntry) -> bool + 'a,
+    {
+        self.entries.iter().enumerate().filter(move |(_, entry)| predicate(entry)).map(|(index, _)| index)
+    }
+
+    /// Returns whether or not this ZIP file contains no entries.
+    ///
+    /// Such a ZIP file is still valid, as opposed to entirely empty input which will fail to be read with
+    /// [`ZipError::EmptyArchive`](crate::error::ZipError::EmptyArchive).
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
     /// Returns this ZIP file's trailing comment.
     pub fn comment(&self) -> &ZipString {
         &self.comment
@@ -35,4 +64,212 @@ impl ZipFile {
     pub fn zip64(&self) -> bool {
         self.zip64
     }
+
+    /// Returns the raw zip64 extensible data sector of this ZIP file's ZIP64 end of central directory record.
+    ///
+    /// This is empty if the ZIP file isn't zip64 or the record has no such sector. The sector is reserved for use by
+    /// PKWARE, so it's provided as-is for advanced consumers.
+    pub fn zip64_extensible_data(&self) -> &[u8] {
+        &self.zip64_extensible_data
+    }
+
+    /// Returns the number of the disk containing the end of central directory record (ie. the final disk), starting
+    /// from zero.
+    ///
+    /// This is always zero for single-file archives, though some tools write a spurious non-zero value.
+    pub fn disk_number(&self) -> u32 {
+        self.disk_number
+    }
+
+    /// Returns the total number of disks the ZIP file spans, as stored within the ZIP64 end of central directory
+    /// locator or otherwise implied by [`ZipFile::disk_number()`].
+    pub fn total_disks(&self) -> u32 {
+        self.total_disks
+    }
+
+    /// Returns the indices of this ZIP file's entries whose filenames match the provided shell-style glob pattern.
+    ///
+    /// Within the pattern, `*` matches any run of characters other than `/`, `?` matches any single character other
+    /// than `/`, and `**` matches any run of characters (including `/`). For example, `logs/*.txt` matches
+    /// `logs/a.txt` but not `logs/2024/a.txt`, whereas `logs/**/*.txt` matches both. Only `/` is treated as a path
+    /// separator (as per the specification), matching is case-sensitive, and non-UTF-8 filenames are converted
+    /// lossily before being matched.
+    pub fn glob(&self, pattern: &str) -> Vec<usize> {
+        let matches = |entry: &StoredZipEntry| {
+            let filename = String::from_utf8_lossy(entry.filename().as_bytes());
+            crate::utils::glob_match(pattern, &filename)
+        };
+
+        self.entries_filtered(matches).collect()
+    }
+
+    /// Returns the indices of this ZIP file's entries, grouped by the first component of their filenames.
+    ///
+    /// An entry directly within the root of the archive forms its own group, alongside any entries nested under it
+    /// when it's a directory. Directories needn't have an explicit entry of their own, so `foo/` and `foo/bar.txt`
+    /// are grouped under `foo` whether or not the former exists. Empty and `.` components (eg. from leading or repeated
+    /// slashes) are ignored, and entries with no other components are grouped under an empty string. Non-UTF-8
+    /// filenames are converted lossily.
+    pub fn top_level_groups(&self) -> HashMap<String, Vec<usize>> {
+        let mut groups: HashMap<String, Vec<usize>> = HashMap::new();
+
+        for (index, entry) in self.entries.iter().enumerate() {
+            let filename = String::from_utf8_lossy(entry.filename().as_bytes());
+            let component = filename.split('/').find(|component| !component.is_empty() && *component != ".");
+
+            groups.entry(component.unwrap_or_default().to_string()).or_default().push(index);
+        }
+
+        groups
+    }
+
+    /// Returns a map of entry filenames to their indices, in the order the entries appear within the ZIP file.
+    ///
+    /// Filenames are converted lossily to UTF-8. Where multiple entries share a filename, the index of the first is
+    /// kept. The map is built on first use and cached for subsequent calls.
+    #[cfg(feature = "indexmap")]
+    pub fn entry_map(&self) -> &indexmap::IndexMap<String, usize> {
+        self.entry_map.get_or_init(|| {
+            let mut map = indexmap::IndexMap::with_capacity(self.entries.len());
+
+            for (index, entry) in self.entries.iter().enumerate() {
+                let filename = String::from_utf8_lossy(entry.filename().as_bytes()).into_owned();
+                map.entry(filename).or_insert(index);
+            }
+
+            map
+        })
+    }
+
+    /// Returns whether or not this ZIP file's entries were recovered by scanning for local file headers, rather than
+    /// being read from the central directory.
+    pub fn recovered(&self) -> bool {
+        self.recovered
+    }
+
+    /// Returns whether or not the comment length declared within this ZIP file's end of central directory record
+    /// differed from the number of bytes actually following it.
+    ///
+    /// Such archives are still read, with the comment being clamped to the bytes available and any bytes beyond the
+    /// declared length being ignored, but this may indicate that the archive was truncated or had data appended to it.
+    pub fn comment_length_mismatch(&self) -> bool {
+        self.comment_length_mismatch
+    }
+}
+
+/// A summary of a ZIP file, read from only its end of central directory records.
+///
+/// See [`read_summary()`](crate::base::read::read_summary).
+#[derive(Clone, Debug)]
+pub struct ArchiveSummary {
+    pub(crate) num_entries: u64,
+    pub(crate) central_directory_size: u64,
+    pub(crate) central_directory_offset: u64,
+    pub(crate) comment: ZipString,
+    pub(crate) zip64: bool,
+    pub(crate) disk_number: u32,
+    pub(crate) total_disks: u32,
+}
+
+impl ArchiveSummary {
+    /// Returns the number of entries within this ZIP file's central directory.
+    pub fn num_entries(&self) -> u64 {
+        self.num_entries
+    }
+
+    /// Returns the size of this ZIP file's central directory in bytes.
+    pub fn central_directory_size(&self) -> u64 {
+        self.central_directory_size
+    }
+
+    /// Returns the offset of this ZIP file's central directory from the start of the archive.
+    pub fn central_directory_offset(&self) -> u64 {
+        self.central_directory_offset
+    }
+
+    /// Returns this ZIP file's trailing comment.
+    pub fn comment(&self) -> &ZipString {
+        &self.comment
+    }
+
+    /// Returns whether or not this ZIP file is zip64
+    pub fn zip64(&self) -> bool {
+        self.zip64
+    }
+
+    /// Returns the number of the disk containing the end of central directory record, starting from zero.
+    ///
+    /// As summaries can be read from archives which span multiple disks, this (alongside
+    /// [`ArchiveSummary::total_disks()`]) is useful for diagnosing why reading the full [`ZipFile`] failed.
+    pub fn disk_number(&self) -> u32 {
+        self.disk_number
+    }
+
+    /// Returns the total number of disks the ZIP file spans.
+    ///
+    /// See [`ZipFile::total_disks()`] for more information.
+    pub fn total_disks(&self) -> u32 {
+        self.total_disks
+    }
+}
+
+/// The offsets of the structures within a ZIP file, as written by a ZIP file writer.
+///
+/// All offsets are relative to the start of the data written by the writer. This is useful when prepending data (such
+/// as a self-extractor stub) to an archive, where these offsets would need to be adjusted by the length of that data.
+///
+/// See [`ZipFileWriter::close_with_layout()`](crate::base::write::ZipFileWriter::close_with_layout).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ArchiveLayout {
+    pub(crate) first_entry_offset: Option<u64>,
+    pub(crate) central_directory_offset: u64,
+    pub(crate) central_directory_size: u64,
+    pub(crate) zip64_eocdr_offset: Option<u64>,
+    pub(crate) eocdr_offset: u64,
+    pub(crate) end_offset: u64,
+}
+
+impl ArchiveLayout {
+    /// Returns the offset of the first entry's local file header, or `None` if no entries were written.
+    pub fn first_entry_offset(&self) -> Option<u64> {
+        self.first_entry_offset
+    }
+
+    /// Returns the offset of the central directory.
+    pub fn central_directory_offset(&self) -> u64 {
+        self.central_directory_offset
+    }
+
+    /// Returns the size of the central directory in bytes.
+    pub fn central_directory_size(&self) -> u64 {
+        self.central_directory_size
+    }
+
+    /// Returns the offset of the ZIP64 end of central directory record, or `None` if one wasn't written.
+    pub fn zip64_eocdr_offset(&self) -> Option<u64> {
+        self.zip64_eocdr_offset
+    }
+
+    /// Returns the offset of the end of central directory record.
+    pub fn eocdr_offset(&self) -> u64 {
+        self.eocdr_offset
+    }
+
+    /// Returns the offset directly after the end of central directory record & comment (ie. the total length written).
+    pub fn end_offset(&self) -> u64 {
+        self.end_offset
+    }
+
+    /// Returns the byte range of the central directory.
+    ///
+    /// Along with [`ArchiveLayout::eocdr_range()`], this is useful for signing schemes which compute a digest over the
+    /// exact bytes of these structures.
+    pub fn central_directory_range(&self) -> Range<u64> {
+        self.central_directory_offset..self.central_directory_offset + self.central_directory_size
+    }
+
+    /// Returns the byte range of the end of central directory record, including the comment.
+    pub fn eocdr_range(&self) -> Range<u64> {
+        self.eocdr_offset..self.end_offset
+    }
 }
@@ -9,8 +9,9 @@
 //! ## Features
 //! - A base implementation atop `futures`'s IO traits.
 //! - An extended implementation atop `tokio`'s IO traits.
-//! - Support for Stored, Deflate, bzip2, LZMA, zstd, and xz compression methods.
-//! - Various different reading approaches (seek, stream, filesystem, in-memory buffer).
+//! - Support for Stored, Deflate, bzip2, LZMA, zstd, and xz compression methods, as well as custom methods via
+//!   pluggable codecs.
+//! - Various different reading approaches (seek, stream, filesystem, in-memory buffer, range requests).
 //! - Support for writing complete data (u8 slices) or stream writing using data descriptors.
 //! - Initial support for ZIP64 reading and writing.
 //! - Aims for reasonable [specification](https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md) compliance.
@@ -28,20 +29,33 @@
 //! - `chrono` - Enables support for parsing dates via `chrono`.
 //! - `tokio` - Enables support for the `tokio` implementation module.
 //! - `tokio-fs` - Enables support for the `tokio::fs` reading module.
+//! - `async-std` - Enables support for the `async_std` implementation module.
 //! - `deflate` - Enables support for the Deflate compression method.
 //! - `bzip2` - Enables support for the bzip2 compression method.
 //! - `lzma` - Enables support for the LZMA compression method.
 //! - `zstd` - Enables support for the zstd compression method.
 //! - `xz` - Enables support for the xz compression method.
+//! - `legacy` - Enables support for reading the legacy Implode compression method.
+//! - `crc32c` - Enables support for writing & verifying CRC-32C extra fields.
+//! - `sha2` - Enables support for computing SHA-256 digests of written entries & deduplicating their compression.
+//! - `encoding_rs` - Enables support for decoding entry data in legacy text encodings via `encoding_rs`.
+//! - `mime` - Enables support for guessing the content type of entries via `mime_guess`.
+//! - `memmap2` - Enables support for memory-mapping Stored entries via the `tokio::fs` reading module.
+//! - `indexmap` - Enables support for building an order-preserving map of entry names via `indexmap`.
+//! - `bytes` - Enables support for reading ZIP files held within `bytes::Bytes` buffers via the `mem` reading module.
 //!
 //! [Read more.](https://github.com/Majored/rs-async-zip)
 
 pub mod base;
+pub mod codec;
 pub mod error;
 
 #[cfg(feature = "tokio")]
 pub mod tokio;
 
+#[cfg(feature = "async-std")]
+pub mod async_std;
+
 pub(crate) mod date;
 pub(crate) mod entry;
 pub(crate) mod file;
@@ -52,11 +66,15 @@ pub(crate) mod utils;
 #[cfg(test)]
 pub(crate) mod tests;
 
-pub use crate::spec::attribute::AttributeCompatibility;
+pub use crate::spec::attribute::{AttributeCompatibility, DosAttributes};
 pub use crate::spec::compression::{Compression, DeflateOption};
+pub use crate::spec::header::AeVersion;
 
 pub use crate::date::{builder::ZipDateTimeBuilder, ZipDateTime};
 pub use crate::entry::{builder::ZipEntryBuilder, StoredZipEntry, ZipEntry};
-pub use crate::file::{builder::ZipFileBuilder, ZipFile};
+pub use crate::file::{builder::ZipFileBuilder, ArchiveLayout, ArchiveSummary, ZipFile};
 
 pub use crate::string::{StringEncoding, ZipString};
+
+#[cfg(feature = "mime")]
+pub use mime_guess::Mime;
@@ -3,10 +3,13 @@
 
 use crate::error::{Result, ZipError};
 
+use std::ops::{BitOr, BitOrAssign};
+
 /// An attribute host compatibility supported by this crate.
 #[non_exhaustive]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum AttributeCompatibility {
+    Dos,
     Unix,
 }
 
@@ -17,6 +20,7 @@ impl TryFrom<u16> for AttributeCompatibility {
     // https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md#4422
     fn try_from(value: u16) -> Result<Self> {
         match value {
+            0 => Ok(AttributeCompatibility::Dos),
             3 => Ok(AttributeCompatibility::Unix),
             _ => Err(ZipError::AttributeCompatibilityNotSupported(value)),
         }
@@ -28,6 +32,7 @@ impl From<&AttributeCompatibility> for u16 {
     // https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md#4422
     fn from(compatibility: &AttributeCompatibility) -> Self {
         match compatibility {
+            AttributeCompatibility::Dos => 0,
             AttributeCompatibility::Unix => 3,
         }
     }
@@ -39,3 +44,57 @@ impl From<AttributeCompatibility> for u16 {
         (&compatibility).into()
     }
 }
+
+/// A set of MS-DOS (FAT) file attribute bits, stored within the low byte of an entry's external file attribute.
+///
+/// Flags can be combined via the `|` operator:
+/// ```
+/// # use async_zip::DosAttributes;
+/// #
+/// let attributes = DosAttributes::READ_ONLY | DosAttributes::HIDDEN;
+/// assert!(attributes.contains(DosAttributes::HIDDEN));
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DosAttributes(u8);
+
+impl DosAttributes {
+    pub const READ_ONLY: DosAttributes = DosAttributes(0x01);
+    pub const HIDDEN: DosAttributes = DosAttributes(0x02);
+    pub const SYSTEM: DosAttributes = DosAttributes(0x04);
+    pub const DIRECTORY: DosAttributes = DosAttributes(0x10);
+    pub const ARCHIVE: DosAttributes = DosAttributes(0x20);
+
+    /// Returns an empty set of attributes.
+    pub const fn empty() -> Self {
+        DosAttributes(0)
+    }
+
+    /// Constructs a set of attributes from their raw bits.
+    pub const fn from_bits(bits: u8) -> Self {
+        DosAttributes(bits)
+    }
+
+    /// Returns the raw bits of this set of attributes.
+    pub const fn bits(&self) -> u8 {
+        self.0
+    }
+
+    /// Returns whether all of the provided attributes are set.
+    pub const fn contains(&self, other: DosAttributes) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl BitOr for DosAttributes {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        DosAttributes(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for DosAttributes {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
@@ -23,6 +23,11 @@ pub enum Compression {
     Zstd,
     #[cfg(feature = "xz")]
     Xz,
+    /// The legacy Implode method used by PKZIP 1.x, which is only supported for reading.
+    #[cfg(feature = "legacy")]
+    Implode,
+    /// A method with the given id which is handled by a user-provided [`Codec`](crate::codec::Codec).
+    Custom(u16),
 }
 
 impl TryFrom<u16> for Compression {
@@ -45,6 +50,8 @@ impl TryFrom<u16> for Compression {
             93 => Ok(Compression::Zstd),
             #[cfg(feature = "xz")]
             95 => Ok(Compression::Xz),
+            #[cfg(feature = "legacy")]
+            6 => Ok(Compression::Implode),
             _ => Err(ZipError::CompressionNotSupported(value)),
         }
     }
@@ -68,6 +75,64 @@ impl From<&Compression> for u16 {
             Compression::Zstd => 93,
             #[cfg(feature = "xz")]
             Compression::Xz => 95,
+            #[cfg(feature = "legacy")]
+            Compression::Implode => 6,
+            Compression::Custom(method) => *method,
+        }
+    }
+}
+
+/// The names of all compression methods supported by this crate (given the enabled features).
+const NAMES: &[(Compression, &str)] = &[
+    (Compression::Stored, "stored"),
+    #[cfg(feature = "deflate")]
+    (Compression::Deflate, "deflate"),
+    #[cfg(feature = "deflate64")]
+    (Compression::Deflate64, "deflate64"),
+    #[cfg(feature = "bzip2")]
+    (Compression::Bz, "bzip2"),
+    #[cfg(feature = "lzma")]
+    (Compression::Lzma, "lzma"),
+    #[cfg(feature = "zstd")]
+    (Compression::Zstd, "zstd"),
+    #[cfg(feature = "xz")]
+    (Compression::Xz, "xz"),
+    #[cfg(feature = "legacy")]
+    (Compression::Implode, "implode"),
+];
+
+impl Compression {
+    /// Returns whether the method id belongs to a method implemented by this crate (incl. the AES encryption marker),
+    /// regardless of whether its relevant feature is enabled.
+    pub(crate) fn is_reserved(method: u16) -> bool {
+        matches!(method, 0 | 6 | 8 | 9 | 12 | 14 | 93 | 95 | 99)
+    }
+
+    /// Converts a case-insensitive name (eg. `"deflate"` or `"zstd"`) into a supported compression method.
+    ///
+    /// The accepted names are those returned by this type's [`Display`](std::fmt::Display) implementation. If the name
+    /// is unknown or the relevant feature isn't enabled, the returned error will list all supported names.
+    pub fn from_name(name: &str) -> Result<Self> {
+        NAMES
+            .iter()
+            .find(|(_, candidate)| candidate.eq_ignore_ascii_case(name))
+            .map(|(compression, _)| *compression)
+            .ok_or_else(|| {
+                let supported = NAMES.iter().map(|(_, name)| *name).collect::<Vec<_>>().join(", ");
+                ZipError::CompressionNameNotSupported(name.to_string(), supported)
+            })
+    }
+
+    /// Returns whether data written with this compression method is terminated by an end-of-stream marker which must
+    /// be indicated via the general purpose flag (ie. LZMA).
+    pub(crate) fn has_eos_marker(&self) -> bool {
+        #[cfg(feature = "lzma")]
+        {
+            matches!(self, Compression::Lzma)
+        }
+        #[cfg(not(feature = "lzma"))]
+        {
+            false
         }
     }
 }
@@ -78,6 +143,24 @@ impl From<Compression> for u16 {
     }
 }
 
+impl std::fmt::Display for Compression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Compression::Custom(method) = self {
+            return write!(f, "custom ({method})");
+        }
+        let name = NAMES.iter().find(|(compression, _)| compression == self).map(|(_, name)| *name).unwrap_or_default();
+        f.write_str(name)
+    }
+}
+
+impl std::str::FromStr for Compression {
+    type Err = ZipError;
+
+    fn from_str(name: &str) -> Result<Self> {
+        Compression::from_name(name)
+    }
+}
+
 /// Level of compression data should be compressed with for deflate.
 #[derive(Debug, Clone, Copy)]
 pub enum DeflateOption {
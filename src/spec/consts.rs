@@ -27,6 +27,8 @@ pub const EOCDR_LENGTH: usize = 18;
 /// The signature for the zip64 end of central directory record.
 /// Ref: https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md#4314
 pub const ZIP64_EOCDR_SIGNATURE: u32 = 0x06064b50;
+/// The length of the ZIP64 EOCDR, excluding the signature and the extensible data sector.
+pub const ZIP64_EOCDR_LENGTH: usize = 52;
 /// The signature for the zip64 end of central directory locator.
 /// Ref: https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md#4315
 pub const ZIP64_EOCDL_SIGNATURE: u32 = 0x07064b50;
@@ -34,6 +36,10 @@ pub const ZIP64_EOCDL_SIGNATURE: u32 = 0x07064b50;
 /// The EOCDL has a fixed size, thankfully.
 pub const ZIP64_EOCDL_LENGTH: u64 = 20;
 
+/// The signature for the digital signature which may follow the central directory.
+/// Ref: https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md#4313
+pub const DIGITAL_SIGNATURE_SIGNATURE: u32 = 0x05054b50;
+
 /// The contents of a header field when one must reference the zip64 version instead.
 pub const NON_ZIP64_MAX_SIZE: u32 = 0xFFFFFFFF;
 /// The maximum number of files or disks in a ZIP file before it requires ZIP64.
@@ -42,3 +48,22 @@ pub const NON_ZIP64_MAX_NUM_FILES: u16 = 0xFFFF;
 // https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md#439
 pub const DATA_DESCRIPTOR_SIGNATURE: u32 = 0x8074b50;
 pub const DATA_DESCRIPTOR_LENGTH: usize = 12;
+
+/// The compression method stored within the headers of AES encrypted entries, in place of their actual method.
+pub const AES_COMPRESSION_METHOD: u16 = 99;
+
+// LZMA compression method constants
+//
+// https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md#58
+/// The version of the LZMA SDK written to the ZIP-specific LZMA header (9.20).
+#[cfg(feature = "lzma")]
+pub const LZMA_SDK_VERSION: [u8; 2] = [9, 20];
+/// The length of the LZMA properties (the lc/lp/pb byte followed by the dictionary size).
+#[cfg(feature = "lzma")]
+pub const LZMA_PROPERTIES_LENGTH: usize = 5;
+/// The length of the ZIP-specific LZMA header, including the properties.
+#[cfg(feature = "lzma")]
+pub const LZMA_ZIP_HEADER_LENGTH: usize = 4 + LZMA_PROPERTIES_LENGTH;
+/// The length of the header used by the standalone `.lzma` format, including the properties.
+#[cfg(feature = "lzma")]
+pub const LZMA_ALONE_HEADER_LENGTH: usize = LZMA_PROPERTIES_LENGTH + 8;
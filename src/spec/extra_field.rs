@@ -2,8 +2,9 @@
 
 use crate::error::{Result as ZipResult, ZipError};
 use crate::spec::header::{
-    ExtraField, HeaderId, InfoZipUnicodeCommentExtraField, InfoZipUnicodePathExtraField, UnknownExtraField,
-    Zip64ExtendedInformationExtraField,
+    AeVersion, AesExtraField, Crc32cExtraField, ExtendedTimestampExtraField, ExtraField, HeaderId,
+    InfoZipUnicodeCommentExtraField, InfoZipUnicodePathExtraField, InfoZipUnixOldExtraField, MetadataExtraField,
+    UnknownExtraField, Zip64ExtendedInformationExtraField,
 };
 
 use super::consts::NON_ZIP64_MAX_SIZE;
@@ -34,6 +35,11 @@ impl ExtraFieldAsBytes for ExtraField {
             ExtraField::Zip64ExtendedInformation(field) => field.as_bytes(),
             ExtraField::InfoZipUnicodeComment(field) => field.as_bytes(),
             ExtraField::InfoZipUnicodePath(field) => field.as_bytes(),
+            ExtraField::InfoZipUnixOld(field) => field.as_bytes(),
+            ExtraField::ExtendedTimestamp(field) => field.as_bytes(),
+            ExtraField::Aes(field) => field.as_bytes(),
+            ExtraField::Crc32c(field) => field.as_bytes(),
+            ExtraField::Metadata(field) => field.as_bytes(),
             ExtraField::Unknown(field) => field.as_bytes(),
         }
     }
@@ -43,6 +49,11 @@ impl ExtraFieldAsBytes for ExtraField {
             ExtraField::Zip64ExtendedInformation(field) => field.count_bytes(),
             ExtraField::InfoZipUnicodeComment(field) => field.count_bytes(),
             ExtraField::InfoZipUnicodePath(field) => field.count_bytes(),
+            ExtraField::InfoZipUnixOld(field) => field.count_bytes(),
+            ExtraField::ExtendedTimestamp(field) => field.count_bytes(),
+            ExtraField::Aes(field) => field.count_bytes(),
+            ExtraField::Crc32c(field) => field.count_bytes(),
+            ExtraField::Metadata(field) => field.count_bytes(),
             ExtraField::Unknown(field) => field.count_bytes(),
         }
     }
@@ -153,6 +164,116 @@ impl ExtraFieldAsBytes for InfoZipUnicodePathExtraField {
     }
 }
 
+impl ExtraFieldAsBytes for AesExtraField {
+    fn as_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        let header_id: u16 = HeaderId::AES_EXTRA_FIELD.into();
+        let version: u16 = match self.version {
+            AeVersion::Ae1 => 1,
+            AeVersion::Ae2 => 2,
+        };
+        bytes.append(&mut header_id.to_le_bytes().to_vec());
+        bytes.append(&mut 7u16.to_le_bytes().to_vec());
+        bytes.append(&mut version.to_le_bytes().to_vec());
+        bytes.extend_from_slice(&self.vendor_id);
+        bytes.push(self.strength);
+        bytes.append(&mut self.compression.to_le_bytes().to_vec());
+
+        bytes
+    }
+
+    fn count_bytes(&self) -> usize {
+        11
+    }
+}
+
+impl ExtraFieldAsBytes for ExtendedTimestampExtraField {
+    fn as_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        let header_id: u16 = HeaderId::EXTENDED_TIMESTAMP_EXTRA_FIELD.into();
+        bytes.append(&mut header_id.to_le_bytes().to_vec());
+        bytes.append(&mut ((self.count_bytes() - 4) as u16).to_le_bytes().to_vec());
+
+        let times = [self.modification_time, self.access_time, self.creation_time];
+        let flags =
+            times.iter().enumerate().filter(|(_, time)| time.is_some()).fold(0u8, |flags, (bit, _)| flags | 1 << bit);
+        bytes.push(flags);
+        for time in times.into_iter().flatten() {
+            bytes.append(&mut time.to_le_bytes().to_vec());
+        }
+
+        bytes
+    }
+
+    fn count_bytes(&self) -> usize {
+        let times = [self.modification_time, self.access_time, self.creation_time];
+        5 + times.iter().flatten().count() * 4
+    }
+}
+
+impl ExtraFieldAsBytes for InfoZipUnixOldExtraField {
+    fn as_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        let header_id: u16 = HeaderId::INFO_ZIP_UNIX_OLD_EXTRA_FIELD.into();
+        bytes.append(&mut header_id.to_le_bytes().to_vec());
+        bytes.append(&mut ((self.count_bytes() - 4) as u16).to_le_bytes().to_vec());
+        bytes.append(&mut self.access_time.to_le_bytes().to_vec());
+        bytes.append(&mut self.modification_time.to_le_bytes().to_vec());
+        if let (Some(uid), Some(gid)) = (self.uid, self.gid) {
+            bytes.append(&mut uid.to_le_bytes().to_vec());
+            bytes.append(&mut gid.to_le_bytes().to_vec());
+        }
+
+        bytes
+    }
+
+    fn count_bytes(&self) -> usize {
+        match (self.uid, self.gid) {
+            (Some(_), Some(_)) => 16,
+            _ => 12,
+        }
+    }
+}
+
+impl ExtraFieldAsBytes for Crc32cExtraField {
+    fn as_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        let header_id: u16 = HeaderId::CRC32C_EXTRA_FIELD.into();
+        bytes.append(&mut header_id.to_le_bytes().to_vec());
+        bytes.append(&mut 4u16.to_le_bytes().to_vec());
+        bytes.append(&mut self.crc32c.to_le_bytes().to_vec());
+
+        bytes
+    }
+
+    fn count_bytes(&self) -> usize {
+        8
+    }
+}
+
+impl ExtraFieldAsBytes for MetadataExtraField {
+    // The lengths can't overflow as writers reject extra fields whose combined size (via count_bytes()) exceeds
+    // u16::MAX with ZipError::ExtraFieldTooLarge before serialising them.
+    fn as_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        let header_id: u16 = HeaderId::METADATA_EXTRA_FIELD.into();
+        bytes.append(&mut header_id.to_le_bytes().to_vec());
+        bytes.append(&mut ((self.count_bytes() - 4) as u16).to_le_bytes().to_vec());
+        for (key, value) in self.pairs.iter() {
+            bytes.append(&mut (key.len() as u16).to_le_bytes().to_vec());
+            bytes.extend_from_slice(key.as_bytes());
+            bytes.append(&mut (value.len() as u16).to_le_bytes().to_vec());
+            bytes.extend_from_slice(value.as_bytes());
+        }
+
+        bytes
+    }
+
+    fn count_bytes(&self) -> usize {
+        4 + self.pairs.iter().map(|(key, value)| 4 + key.len() + value.len()).sum::<usize>()
+    }
+}
+
 /// Parse a zip64 extra field from bytes.
 /// The content of "data" should exclude the header.
 fn zip64_extended_information_field_from_bytes(
@@ -249,6 +370,106 @@ fn info_zip_unicode_path_extra_field_from_bytes(
     }
 }
 
+/// Parse an old Info-ZIP Unix extra field from bytes, returning None if it's too short to contain both times.
+///
+/// The local file header's variant additionally stores the user & group ids, so they're only parsed if present.
+fn info_zip_unix_old_extra_field_from_bytes(_header_id: HeaderId, data: &[u8]) -> Option<InfoZipUnixOldExtraField> {
+    if data.len() < 8 {
+        return None;
+    }
+
+    let (uid, gid) = match data.len() >= 12 {
+        true => (
+            Some(u16::from_le_bytes(data[8..10].try_into().unwrap())),
+            Some(u16::from_le_bytes(data[10..12].try_into().unwrap())),
+        ),
+        false => (None, None),
+    };
+
+    Some(InfoZipUnixOldExtraField {
+        access_time: u32::from_le_bytes(data[0..4].try_into().unwrap()),
+        modification_time: u32::from_le_bytes(data[4..8].try_into().unwrap()),
+        uid,
+        gid,
+    })
+}
+
+/// Parse an extended timestamp extra field from bytes, returning None if it's empty or has a truncated time.
+///
+/// The flags byte indicates which times are present in the local file header, but the central directory only stores
+/// the modification time alongside the same flags, so each time is only parsed if its data is present.
+fn extended_timestamp_extra_field_from_bytes(_header_id: HeaderId, data: &[u8]) -> Option<ExtendedTimestampExtraField> {
+    let (flags, mut data) = data.split_first()?;
+    let mut times = [None; 3];
+
+    for (bit, time) in times.iter_mut().enumerate() {
+        if flags & (1 << bit) == 0 || data.len() < 4 {
+            continue;
+        }
+        *time = Some(u32::from_le_bytes(data[0..4].try_into().unwrap()));
+        data = &data[4..];
+    }
+
+    if !data.is_empty() {
+        return None;
+    }
+
+    let [modification_time, access_time, creation_time] = times;
+    Some(ExtendedTimestampExtraField { modification_time, access_time, creation_time })
+}
+
+/// Parse an AES extra field from bytes.
+/// Parse an AES extra field from bytes, returning None if it's incomplete or of an unknown vendor version.
+fn aes_extra_field_from_bytes(_header_id: HeaderId, data: &[u8]) -> Option<AesExtraField> {
+    if data.len() < 7 {
+        return None;
+    }
+
+    let version = match u16::from_le_bytes(data[0..2].try_into().unwrap()) {
+        1 => AeVersion::Ae1,
+        2 => AeVersion::Ae2,
+        _ => return None,
+    };
+
+    Some(AesExtraField {
+        version,
+        vendor_id: [data[2], data[3]],
+        strength: data[4],
+        compression: u16::from_le_bytes(data[5..7].try_into().unwrap()),
+    })
+}
+
+/// Parse a CRC-32C extra field from bytes, returning None if it isn't exactly four bytes long.
+fn crc32c_extra_field_from_bytes(_header_id: HeaderId, data: &[u8]) -> Option<Crc32cExtraField> {
+    let crc32c = data.try_into().ok()?;
+    Some(Crc32cExtraField { crc32c: u32::from_le_bytes(crc32c) })
+}
+
+/// Parse a metadata extra field from bytes, returning None if a string is truncated or isn't valid UTF-8.
+fn metadata_extra_field_from_bytes(_header_id: HeaderId, data: &[u8]) -> Option<MetadataExtraField> {
+    fn read_string(data: &[u8], current_idx: &mut usize) -> Option<String> {
+        let length_bytes = data.get(*current_idx..*current_idx + 2)?;
+        let length = u16::from_le_bytes(length_bytes.try_into().unwrap()) as usize;
+        *current_idx += 2;
+
+        let string_bytes = data.get(*current_idx..*current_idx + length)?;
+        *current_idx += length;
+
+        String::from_utf8(string_bytes.to_vec()).ok()
+    }
+
+    let mut pairs = Vec::new();
+    let mut current_idx = 0;
+
+    while current_idx < data.len() {
+        let key = read_string(data, &mut current_idx)?;
+        let value = read_string(data, &mut current_idx)?;
+        pairs.push((key, value));
+    }
+
+    Some(MetadataExtraField { pairs })
+}
+
 pub(crate) fn extra_field_from_bytes(
     header_id: HeaderId,
     data_size: u16,
@@ -266,6 +487,27 @@ pub(crate) fn extra_field_from_bytes(
         HeaderId::INFO_ZIP_UNICODE_PATH_EXTRA_FIELD => Ok(ExtraField::InfoZipUnicodePath(
             info_zip_unicode_path_extra_field_from_bytes(header_id, data_size, data)?,
         )),
+        // Fields which can't be interpreted are kept as-is, so the archive can still be read & rewritten faithfully.
+        HeaderId::INFO_ZIP_UNIX_OLD_EXTRA_FIELD => match info_zip_unix_old_extra_field_from_bytes(header_id, data) {
+            Some(field) => Ok(ExtraField::InfoZipUnixOld(field)),
+            None => Ok(ExtraField::Unknown(UnknownExtraField { header_id, data_size, content: data.to_vec() })),
+        },
+        HeaderId::EXTENDED_TIMESTAMP_EXTRA_FIELD => match extended_timestamp_extra_field_from_bytes(header_id, data) {
+            Some(field) => Ok(ExtraField::ExtendedTimestamp(field)),
+            None => Ok(ExtraField::Unknown(UnknownExtraField { header_id, data_size, content: data.to_vec() })),
+        },
+        HeaderId::AES_EXTRA_FIELD => match aes_extra_field_from_bytes(header_id, data) {
+            Some(field) => Ok(ExtraField::Aes(field)),
+            None => Ok(ExtraField::Unknown(UnknownExtraField { header_id, data_size, content: data.to_vec() })),
+        },
+        HeaderId::CRC32C_EXTRA_FIELD => match crc32c_extra_field_from_bytes(header_id, data) {
+            Some(field) => Ok(ExtraField::Crc32c(field)),
+            None => Ok(ExtraField::Unknown(UnknownExtraField { header_id, data_size, content: data.to_vec() })),
+        },
+        HeaderId::METADATA_EXTRA_FIELD => match metadata_extra_field_from_bytes(header_id, data) {
+            Some(field) => Ok(ExtraField::Metadata(field)),
+            None => Ok(ExtraField::Unknown(UnknownExtraField { header_id, data_size, content: data.to_vec() })),
+        },
         _ => Ok(ExtraField::Unknown(UnknownExtraField { header_id, data_size, content: data.to_vec() })),
     }
 }
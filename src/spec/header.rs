@@ -17,10 +17,19 @@ pub struct LocalFileHeader {
 
 // https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md#444
 #[derive(Copy, Clone)]
+#[non_exhaustive]
 pub struct GeneralPurposeFlag {
     pub encrypted: bool,
     pub data_descriptor: bool,
     pub filename_unicode: bool,
+    /// Bit 1, whose meaning depends on the compression method. For LZMA entries it indicates that an end-of-stream
+    /// marker terminates the data, and for Implode entries that an 8K (rather than 4K) sliding dictionary is used.
+    pub compression_option_1: bool,
+    /// Bit 2, whose meaning depends on the compression method. For Implode entries it indicates that literals are
+    /// encoded via a third Shannon-Fano tree.
+    pub compression_option_2: bool,
+    /// Bit 6, which indicates that the entry's data is protected by PKWARE's strong encryption.
+    pub strong_encryption: bool,
 }
 
 /// 2 byte header ids
@@ -32,6 +41,15 @@ impl HeaderId {
     pub const ZIP64_EXTENDED_INFORMATION_EXTRA_FIELD: HeaderId = HeaderId(0x0001);
     pub const INFO_ZIP_UNICODE_COMMENT_EXTRA_FIELD: HeaderId = HeaderId(0x6375);
     pub const INFO_ZIP_UNICODE_PATH_EXTRA_FIELD: HeaderId = HeaderId(0x7075);
+    pub const INFO_ZIP_UNIX_OLD_EXTRA_FIELD: HeaderId = HeaderId(0x5855);
+    pub const EXTENDED_TIMESTAMP_EXTRA_FIELD: HeaderId = HeaderId(0x5455);
+    pub const AES_EXTRA_FIELD: HeaderId = HeaderId(0x9901);
+    /// The header id used by Android's zipalign for padding the local file header so that an entry's data is aligned.
+    pub const ALIGNMENT_EXTRA_FIELD: HeaderId = HeaderId(0xD935);
+    /// A non-standard header id used by this crate for storing a CRC-32C digest.
+    pub const CRC32C_EXTRA_FIELD: HeaderId = HeaderId(0x6332);
+    /// A non-standard header id used by this crate for storing key-value metadata.
+    pub const METADATA_EXTRA_FIELD: HeaderId = HeaderId(0x6D64);
 }
 
 impl From<u16> for HeaderId {
@@ -54,6 +72,11 @@ pub enum ExtraField {
     Zip64ExtendedInformation(Zip64ExtendedInformationExtraField),
     InfoZipUnicodeComment(InfoZipUnicodeCommentExtraField),
     InfoZipUnicodePath(InfoZipUnicodePathExtraField),
+    InfoZipUnixOld(InfoZipUnixOldExtraField),
+    ExtendedTimestamp(ExtendedTimestampExtraField),
+    Aes(AesExtraField),
+    Crc32c(Crc32cExtraField),
+    Metadata(MetadataExtraField),
     Unknown(UnknownExtraField),
 }
 
@@ -95,6 +118,76 @@ pub enum InfoZipUnicodePathExtraField {
     Unknown { version: u8, data: Vec<u8> },
 }
 
+/// Stores the Unix access & modification times (and optionally, the user & group ids) of the entry, as written by
+/// older versions of Info-ZIP.
+///
+/// The ids are only present within the local file header, so they will be `None` when parsed from the central
+/// directory. The times are stored as seconds since the Unix epoch.
+#[derive(Clone, Debug)]
+pub struct InfoZipUnixOldExtraField {
+    pub access_time: u32,
+    pub modification_time: u32,
+    pub uid: Option<u16>,
+    pub gid: Option<u16>,
+}
+
+/// Stores the times of an entry as seconds since the Unix epoch, as per the extended timestamp extra field.
+///
+/// The local file header's variant may store any of the three times, whereas the central directory's variant only
+/// stores the modification time (if any), so the others will be `None` when parsed from the central directory.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExtendedTimestampExtraField {
+    pub modification_time: Option<u32>,
+    pub access_time: Option<u32>,
+    pub creation_time: Option<u32>,
+}
+
+/// The vendor version of an AES encrypted entry, as stored within its AES extra field.
+///
+/// AE-1 entries store the CRC-32 of their plaintext as normal, whereas AE-2 entries store a zero CRC-32 (relying
+/// solely on the authentication code) so that the CRC-32 can't leak information about small files.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AeVersion {
+    Ae1,
+    Ae2,
+}
+
+impl AeVersion {
+    /// Returns whether or not entries of this vendor version store the CRC-32 of their plaintext.
+    pub fn stores_crc32(&self) -> bool {
+        matches!(self, AeVersion::Ae1)
+    }
+}
+
+/// Stores the parameters of an entry encrypted via WinZip's AES encryption.
+///
+/// The entry's compression method is stored as 99 within its headers, with the actual method being stored here.
+#[derive(Clone, Debug)]
+pub struct AesExtraField {
+    pub version: AeVersion,
+    pub vendor_id: [u8; 2],
+    pub strength: u8,
+    pub compression: u16,
+}
+
+/// Stores a CRC-32C digest of the entry's uncompressed data.
+///
+/// This field isn't part of the specification and is only written by this crate when requested, so it will be ignored
+/// by other readers.
+#[derive(Clone, Debug)]
+pub struct Crc32cExtraField {
+    pub crc32c: u32,
+}
+
+/// Stores application-specific key-value metadata pairs in insertion order.
+///
+/// Each key and value is stored as a 2 byte length followed by its UTF-8 bytes. This field isn't part of the
+/// specification, so it will be ignored by other readers.
+#[derive(Clone, Debug)]
+pub struct MetadataExtraField {
+    pub pairs: Vec<(String, String)>,
+}
+
 /// Represents any unparsed extra field.
 #[derive(Clone, Debug)]
 pub struct UnknownExtraField {
@@ -43,8 +43,26 @@ impl GeneralPurposeFlag {
             false => 0x0,
             true => 0x800,
         };
+        let compression_option_1: u16 = match self.compression_option_1 {
+            false => 0x0,
+            true => 0x2,
+        };
+        let compression_option_2: u16 = match self.compression_option_2 {
+            false => 0x0,
+            true => 0x4,
+        };
+        let strong_encryption: u16 = match self.strong_encryption {
+            false => 0x0,
+            true => 0x40,
+        };
 
-        (encrypted | data_descriptor | filename_unicode).to_le_bytes()
+        (encrypted
+            | data_descriptor
+            | filename_unicode
+            | compression_option_1
+            | compression_option_2
+            | strong_encryption)
+            .to_le_bytes()
     }
 }
 
@@ -113,8 +131,18 @@ impl From<u16> for GeneralPurposeFlag {
         let encrypted = !matches!(value & 0x1, 0);
         let data_descriptor = !matches!((value & 0x8) >> 3, 0);
         let filename_unicode = !matches!((value & 0x800) >> 11, 0);
-
-        GeneralPurposeFlag { encrypted, data_descriptor, filename_unicode }
+        let compression_option_1 = !matches!((value & 0x2) >> 1, 0);
+        let compression_option_2 = !matches!((value & 0x4) >> 2, 0);
+        let strong_encryption = !matches!((value & 0x40) >> 6, 0);
+
+        GeneralPurposeFlag {
+            encrypted,
+            data_descriptor,
+            filename_unicode,
+            compression_option_1,
+            compression_option_2,
+            strong_encryption,
+        }
     }
 }
 
@@ -342,4 +370,83 @@ mod tests {
             }
         )
     }
+
+    #[test]
+    fn test_parse_info_zip_unix_old() {
+        use crate::spec::extra_field::ExtraFieldAsBytes;
+
+        // The local file header's variant, which includes the user & group ids.
+        let local: [u8; 16] =
+            [0x55, 0x58, 0x0C, 0x00, 0x10, 0x00, 0x00, 0x00, 0x20, 0x00, 0x00, 0x00, 0xE8, 0x03, 0xE9, 0x03];
+        let fields = parse_extra_fields(local.to_vec(), 0, 0).unwrap();
+        let ExtraField::InfoZipUnixOld(field) = &fields[0] else { panic!("expected an Info-ZIP Unix (old) field") };
+        assert_eq!((field.access_time, field.modification_time), (16, 32));
+        assert_eq!((field.uid, field.gid), (Some(1000), Some(1001)));
+        assert_eq!(fields[0].as_bytes(), local);
+
+        // The central directory's variant, which only includes the times.
+        let central: [u8; 12] = [0x55, 0x58, 0x08, 0x00, 0x10, 0x00, 0x00, 0x00, 0x20, 0x00, 0x00, 0x00];
+        let fields = parse_extra_fields(central.to_vec(), 0, 0).unwrap();
+        let ExtraField::InfoZipUnixOld(field) = &fields[0] else { panic!("expected an Info-ZIP Unix (old) field") };
+        assert_eq!((field.access_time, field.modification_time), (16, 32));
+        assert_eq!((field.uid, field.gid), (None, None));
+        assert_eq!(fields[0].as_bytes(), central);
+
+        // A truncated field is kept as an unknown field.
+        let truncated: [u8; 8] = [0x55, 0x58, 0x04, 0x00, 0x10, 0x00, 0x00, 0x00];
+        let fields = parse_extra_fields(truncated.to_vec(), 0, 0).unwrap();
+        let ExtraField::Unknown(field) = &fields[0] else { panic!("expected an unknown field") };
+        assert_eq!(field.header_id, HeaderId::INFO_ZIP_UNIX_OLD_EXTRA_FIELD);
+        assert_eq!(fields[0].as_bytes(), truncated);
+    }
+
+    #[test]
+    fn test_parse_aes() {
+        use crate::spec::extra_field::ExtraFieldAsBytes;
+        use crate::spec::header::AeVersion;
+
+        // An AE-2 field with 256-bit strength, wrapping deflate compressed data.
+        let data: [u8; 11] = [0x01, 0x99, 0x07, 0x00, 0x02, 0x00, 0x41, 0x45, 0x03, 0x08, 0x00];
+        let fields = parse_extra_fields(data.to_vec(), 0, 0).unwrap();
+        let ExtraField::Aes(field) = &fields[0] else { panic!("expected an AES field") };
+        assert_eq!(field.version, AeVersion::Ae2);
+        assert!(!field.version.stores_crc32());
+        assert_eq!((&field.vendor_id, field.strength, field.compression), (b"AE", 3, 8));
+        assert_eq!(fields[0].as_bytes(), data);
+
+        let unknown_version: [u8; 11] = [0x01, 0x99, 0x07, 0x00, 0x03, 0x00, 0x41, 0x45, 0x03, 0x08, 0x00];
+        let fields = parse_extra_fields(unknown_version.to_vec(), 0, 0).unwrap();
+        assert!(matches!(&fields[0], ExtraField::Unknown(field) if field.header_id == HeaderId::AES_EXTRA_FIELD));
+        assert_eq!(fields[0].as_bytes(), unknown_version);
+    }
+
+    #[test]
+    fn test_parse_extended_timestamp() {
+        use crate::spec::extra_field::ExtraFieldAsBytes;
+        use crate::spec::header::ExtendedTimestampExtraField;
+
+        // A local file header's field with the modification & access times.
+        let data: [u8; 13] = [0x55, 0x54, 0x09, 0x00, 0x03, 0x01, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00];
+        let fields = parse_extra_fields(data.to_vec(), 0, 0).unwrap();
+        let ExtraField::ExtendedTimestamp(field) = &fields[0] else { panic!("expected an extended timestamp field") };
+        let expected =
+            ExtendedTimestampExtraField { modification_time: Some(1), access_time: Some(2), creation_time: None };
+        assert_eq!(*field, expected);
+        assert_eq!(fields[0].as_bytes(), data);
+
+        // The central directory's field only stores the modification time, despite the flags.
+        let data: [u8; 9] = [0x55, 0x54, 0x05, 0x00, 0x03, 0x01, 0x00, 0x00, 0x00];
+        let fields = parse_extra_fields(data.to_vec(), 0, 0).unwrap();
+        let ExtraField::ExtendedTimestamp(field) = &fields[0] else { panic!("expected an extended timestamp field") };
+        assert_eq!((field.modification_time, field.access_time), (Some(1), None));
+
+        // Empty or truncated fields are kept as unknown fields, whereas a field with only flags is still valid.
+        let malformed: [u8; 16] =
+            [0x55, 0x54, 0x00, 0x00, 0x55, 0x54, 0x03, 0x00, 0x01, 0x00, 0x00, 0x55, 0x54, 0x01, 0x00, 0x00];
+        let fields = parse_extra_fields(malformed.to_vec(), 0, 0).unwrap();
+        assert!(matches!(&fields[0], ExtraField::Unknown(field) if field.content.is_empty()));
+        assert!(matches!(&fields[1], ExtraField::Unknown(field) if field.content == [0x01, 0x00, 0x00]));
+        assert!(matches!(&fields[2], ExtraField::ExtendedTimestamp(field) if field.modification_time.is_none()));
+        assert_eq!(fields.iter().flat_map(|field| field.as_bytes()).collect::<Vec<_>>(), malformed);
+    }
 }
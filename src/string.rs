@@ -4,14 +4,14 @@
 use crate::error::{Result, ZipError};
 
 /// A string encoding supported by this crate.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum StringEncoding {
     Utf8,
     Raw,
 }
 
 /// A string wrapper for handling different encodings.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ZipString {
     encoding: StringEncoding,
     raw: Vec<u8>,
@@ -0,0 +1,57 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::{estimate_cd_memory, mem, seek};
+use crate::base::write::ZipFileWriter;
+use crate::error::ZipError;
+use crate::{Compression, ZipEntryBuilder};
+
+use futures_lite::io::Cursor;
+
+async fn archive(num_entries: usize) -> Vec<u8> {
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    for index in 0..num_entries {
+        let entry = ZipEntryBuilder::new(format!("{index}.txt").into(), Compression::Stored);
+        writer.write_entry_whole(entry, b"foo").await.unwrap();
+    }
+
+    writer.close().await.unwrap()
+}
+
+#[test]
+fn estimate_scales_with_entries() {
+    assert_eq!(estimate_cd_memory(0), 0);
+    assert_eq!(estimate_cd_memory(10), estimate_cd_memory(1) * 10);
+    assert_eq!(estimate_cd_memory(u64::MAX), usize::MAX);
+}
+
+#[tokio::test]
+async fn memory_budget() {
+    let data = archive(10).await;
+    let estimate = estimate_cd_memory(10);
+
+    let reader = mem::ZipFileReader::new_with_memory_budget(data.clone(), estimate).await.unwrap();
+    assert_eq!(reader.file().entries().len(), 10);
+
+    let result = mem::ZipFileReader::new_with_memory_budget(data.clone(), estimate - 1).await;
+    let Err(ZipError::MemoryBudgetExceeded(actual, budget)) = result else {
+        panic!("expected the budget to be exceeded")
+    };
+    assert_eq!((actual, budget), (estimate, estimate - 1));
+
+    let result = seek::ZipFileReader::new_with_memory_budget(Cursor::new(data), estimate - 1).await;
+    assert!(matches!(result, Err(ZipError::MemoryBudgetExceeded(_, _))));
+}
+
+#[tokio::test]
+async fn memory_budget_inflated_entry_count() {
+    let mut data = archive(1).await;
+
+    // Declare the maximum number of entries (without ZIP64) within the end of central directory record.
+    let eocdr_offset = data.len() - 22;
+    data[eocdr_offset + 8..eocdr_offset + 12].copy_from_slice(&[0xFF; 4]);
+
+    let result = mem::ZipFileReader::new_with_memory_budget(data, 1024 * 1024).await;
+    assert!(matches!(result, Err(ZipError::MemoryBudgetExceeded(_, _))));
+}
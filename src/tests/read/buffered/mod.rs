@@ -0,0 +1,95 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::seek::{BufferedSeekReader, ZipFileReader};
+use crate::base::write::ZipFileWriter;
+use crate::{Compression, ZipEntryBuilder};
+
+use std::io::{Error, SeekFrom};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_lite::io::{AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt, BufReader, Cursor};
+
+/// A reader which counts the number of reads & seeks performed on it.
+struct CountingReader {
+    inner: Cursor<Vec<u8>>,
+    operations: usize,
+}
+
+impl AsyncRead for CountingReader {
+    fn poll_read(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<Result<usize, Error>> {
+        self.operations += 1;
+        Pin::new(&mut self.inner).poll_read(cx, buf)
+    }
+}
+
+impl AsyncSeek for CountingReader {
+    fn poll_seek(mut self: Pin<&mut Self>, cx: &mut Context<'_>, pos: SeekFrom) -> Poll<Result<u64, Error>> {
+        self.operations += 1;
+        Pin::new(&mut self.inner).poll_seek(cx, pos)
+    }
+}
+
+async fn small_entries_archive() -> Vec<u8> {
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    for index in 0..100 {
+        let entry = ZipEntryBuilder::new(format!("{index}.txt").into(), Compression::Stored);
+        writer.write_entry_whole(entry, format!("entry {index}").as_bytes()).await.unwrap();
+    }
+
+    writer.close().await.unwrap()
+}
+
+#[tokio::test]
+async fn buffered_seek_reader_seeks() {
+    let data: Vec<u8> = (0..=255).collect();
+    let mut reader = BufferedSeekReader::with_capacity(16, Cursor::new(data));
+
+    let mut buffer = [0; 4];
+    reader.read_exact(&mut buffer).await.unwrap();
+    assert_eq!(buffer, [0, 1, 2, 3]);
+
+    // Within the buffer, both forwards & backwards.
+    assert_eq!(reader.seek(SeekFrom::Current(8)).await.unwrap(), 12);
+    reader.read_exact(&mut buffer).await.unwrap();
+    assert_eq!(buffer, [12, 13, 14, 15]);
+    assert_eq!(reader.seek(SeekFrom::Start(2)).await.unwrap(), 2);
+    reader.read_exact(&mut buffer).await.unwrap();
+    assert_eq!(buffer, [2, 3, 4, 5]);
+
+    // Outside of the buffer.
+    assert_eq!(reader.seek(SeekFrom::Current(100)).await.unwrap(), 106);
+    reader.read_exact(&mut buffer).await.unwrap();
+    assert_eq!(buffer, [106, 107, 108, 109]);
+    assert_eq!(reader.seek(SeekFrom::End(-4)).await.unwrap(), 252);
+    reader.read_exact(&mut buffer).await.unwrap();
+    assert_eq!(buffer, [252, 253, 254, 255]);
+    assert!(reader.seek(SeekFrom::Current(-1000)).await.is_err());
+}
+
+#[tokio::test]
+async fn buffered_seek_reader_reduces_operations() {
+    let archive = small_entries_archive().await;
+
+    let source = CountingReader { inner: Cursor::new(archive.clone()), operations: 0 };
+    let mut reader = ZipFileReader::new(BufReader::new(source)).await.unwrap();
+    for index in 0..100 {
+        let mut buffer = String::new();
+        reader.reader_with_entry(index).await.unwrap().read_to_string_checked(&mut buffer).await.unwrap();
+        assert_eq!(buffer, format!("entry {index}"));
+    }
+    let unbuffered = reader.into_inner().into_inner().operations;
+
+    let source = CountingReader { inner: Cursor::new(archive), operations: 0 };
+    let mut reader = ZipFileReader::new_buffered(source).await.unwrap();
+    for index in 0..100 {
+        let mut buffer = String::new();
+        reader.reader_with_entry(index).await.unwrap().read_to_string_checked(&mut buffer).await.unwrap();
+        assert_eq!(buffer, format!("entry {index}"));
+    }
+    let buffered = reader.into_inner().into_inner().operations;
+
+    assert!(buffered * 10 < unbuffered, "buffered: {buffered}, unbuffered: {unbuffered}");
+}
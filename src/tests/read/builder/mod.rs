@@ -0,0 +1,55 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::seek::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::{Compression, StoredZipEntry, ZipEntryBuilder, ZipFileBuilder, ZipString};
+
+use futures_lite::io::Cursor;
+
+#[tokio::test]
+async fn zip_file_builder() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    for name in ["foo.txt", "bar.txt"] {
+        let entry = ZipEntryBuilder::new(name.into(), Compression::Stored);
+        writer.write_entry_whole(entry, name.as_bytes()).await.unwrap();
+    }
+
+    let data = writer.close().await.unwrap();
+    let reader = ZipFileReader::new(Cursor::new(&data)).await.unwrap();
+    let entries = reader.file().entries();
+
+    // Assemble a file listing only the second entry, which can then be read from the original data.
+    let file =
+        ZipFileBuilder::new().add_entry(entries[1].clone()).comment("baz".to_string().into()).zip64(true).build();
+    assert_eq!(file.entries().len(), 1);
+    assert_eq!(file.comment().as_str().unwrap(), "baz");
+    assert!(file.zip64());
+
+    let mut reader = ZipFileReader::from_raw_parts(Cursor::new(&data), file);
+    let mut read = Vec::new();
+    reader.reader_with_entry(0).await.unwrap().read_to_end_checked(&mut read).await.unwrap();
+    assert_eq!(read, b"bar.txt");
+
+    // A synthesised entry should match the header size of the one which was written.
+    let synthesised = StoredZipEntry::new(entries[1].to_entry(), entries[1].header_offset());
+    assert_eq!(synthesised.header_size(), entries[1].header_size());
+    assert_eq!(synthesised.header_offset(), entries[1].header_offset());
+}
+
+#[tokio::test]
+async fn stored_entry_alternative_filename() {
+    // The alternative filename is what's written within the header, and is shorter than the UTF-8 one.
+    let filename = ZipString::new_with_alternative("f\u{f3}o.txt".to_string(), b"f\xA2o.txt".to_vec());
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+    writer.write_entry_whole(ZipEntryBuilder::new(filename, Compression::Stored), b"foo").await.unwrap();
+
+    let data = writer.close().await.unwrap();
+    let reader = ZipFileReader::new(Cursor::new(&data)).await.unwrap();
+    let entry = &reader.file().entries()[0];
+    assert!(entry.filename().alternative().is_some());
+
+    let synthesised = StoredZipEntry::new(entry.to_entry(), entry.header_offset());
+    assert_eq!(synthesised.header_size(), entry.header_size());
+}
@@ -0,0 +1,159 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::seek::{BufferedSeekReader, ZipFileReader};
+use crate::base::write::ZipFileWriter;
+use crate::{Compression, ZipEntryBuilder};
+
+use std::io::{Error, SeekFrom};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_lite::future::poll_once;
+use futures_lite::io::{AsyncBufRead, AsyncRead, AsyncReadExt, AsyncSeek, BufReader, Cursor};
+
+/// A reader which returns [`Poll::Pending`] on every other read, as a slow network source might.
+///
+/// Seeks are started immediately but only complete on the following poll (as with [`tokio::fs::File`]), so the
+/// position of this reader will have changed if a seek is cancelled.
+struct SlowReader {
+    inner: Cursor<Vec<u8>>,
+    ready: bool,
+    seek: Option<(SeekFrom, u64)>,
+}
+
+impl SlowReader {
+    fn new(data: Vec<u8>) -> Self {
+        Self { inner: Cursor::new(data), ready: false, seek: None }
+    }
+}
+
+impl SlowReader {
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        self.ready = !self.ready;
+
+        if self.ready {
+            Poll::Ready(())
+        } else {
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+}
+
+impl AsyncRead for SlowReader {
+    fn poll_read(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<Result<usize, Error>> {
+        std::task::ready!(self.poll_ready(cx));
+        let length = std::cmp::min(buf.len(), 16);
+        Pin::new(&mut self.inner).poll_read(cx, &mut buf[..length])
+    }
+}
+
+impl AsyncSeek for SlowReader {
+    fn poll_seek(mut self: Pin<&mut Self>, cx: &mut Context<'_>, pos: SeekFrom) -> Poll<Result<u64, Error>> {
+        match self.seek.take() {
+            Some((started, offset)) if started == pos => Poll::Ready(Ok(offset)),
+            _ => {
+                let offset = std::task::ready!(Pin::new(&mut self.inner).poll_seek(cx, pos))?;
+                self.seek = Some((pos, offset));
+
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+        }
+    }
+}
+
+async fn archive() -> (Vec<u8>, Vec<Vec<u8>>) {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    let mut contents = Vec::new();
+
+    for index in 0..5u8 {
+        let data: Vec<u8> = (0..1000u32).map(|byte| (byte as u8).wrapping_mul(index)).collect();
+        let entry = ZipEntryBuilder::new(format!("{index}.bin").into(), Compression::Stored);
+        writer.write_entry_whole(entry, &data).await.unwrap();
+        contents.push(data);
+    }
+
+    (writer.close().await.unwrap(), contents)
+}
+
+/// Cancels entry readers at various points before checking that each entry can still be read in full.
+async fn check_cancellation<R>(reader: R, contents: &[Vec<u8>])
+where
+    R: AsyncBufRead + AsyncSeek + Unpin,
+{
+    let mut reader = ZipFileReader::new(reader).await.unwrap();
+
+    for (index, expected) in contents.iter().enumerate() {
+        // Cancel whilst locating the entry's data.
+        let _ = poll_once(reader.reader_with_entry(index)).await;
+
+        // Cancel part way through reading the entry's data.
+        let mut entry_reader = reader.reader_with_entry(index).await.unwrap();
+        let mut buffer = Vec::new();
+        let _ = poll_once(entry_reader.read_to_end(&mut buffer)).await;
+        let _ = entry_reader.read(&mut [0; 100]).await.unwrap();
+        drop(entry_reader);
+
+        let mut buffer = Vec::new();
+        reader.reader_with_entry(index).await.unwrap().read_to_end_checked(&mut buffer).await.unwrap();
+        assert_eq!(&buffer, expected);
+    }
+
+    // Read the entries in reverse, after cancelling the reading of each.
+    for (index, expected) in contents.iter().enumerate().rev() {
+        let mut entry_reader = reader.reader_without_entry(index).await.unwrap();
+        let _ = entry_reader.read(&mut [0; 100]).await.unwrap();
+        drop(entry_reader);
+
+        let mut buffer = Vec::new();
+        reader.reader_without_entry(index).await.unwrap().read_to_end(&mut buffer).await.unwrap();
+        assert_eq!(&buffer, expected);
+    }
+}
+
+#[tokio::test]
+async fn cancel_entry_reader_buffered() {
+    let (data, contents) = archive().await;
+
+    check_cancellation(BufReader::new(SlowReader::new(data.clone())), &contents).await;
+    check_cancellation(BufferedSeekReader::new(SlowReader::new(data)), &contents).await;
+}
+
+#[tokio::test]
+async fn cancel_entry_reader_mem() {
+    let (data, contents) = archive().await;
+    let reader = crate::base::read::mem::ZipFileReader::new(data).await.unwrap();
+
+    for (index, expected) in contents.iter().enumerate() {
+        let _ = poll_once(reader.reader_with_entry(index)).await;
+
+        let mut entry_reader = reader.reader_with_entry(index).await.unwrap();
+        let _ = entry_reader.read(&mut [0; 100]).await.unwrap();
+        drop(entry_reader);
+
+        let mut buffer = Vec::new();
+        reader.reader_with_entry(index).await.unwrap().read_to_end_checked(&mut buffer).await.unwrap();
+        assert_eq!(&buffer, expected);
+    }
+}
+
+#[tokio::test]
+async fn cancel_buffered_seek() {
+    use futures_lite::io::AsyncSeekExt;
+
+    let data: Vec<u8> = (0..100).collect();
+    let mut reader = BufferedSeekReader::with_capacity(16, SlowReader::new(data.clone()));
+    reader.seek(SeekFrom::Start(0)).await.unwrap();
+    reader.read_exact(&mut [0; 4]).await.unwrap();
+
+    // Cancel a seek outside of the buffer, after which the inner reader's position has changed.
+    let _ = poll_once(reader.seek(SeekFrom::Start(50))).await;
+
+    // Seeking to a position which was within the buffer must still read the correct data beyond it.
+    reader.seek(SeekFrom::Start(8)).await.unwrap();
+    let mut buffer = [0; 16];
+    reader.read_exact(&mut buffer).await.unwrap();
+    assert_eq!(buffer, data[8..24]);
+}
@@ -0,0 +1,41 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::mem::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::error::ZipError;
+use crate::{Compression, ZipEntryBuilder};
+
+/// Writes an archive of three entries, with the number of entries declared by the EOCDR overwritten.
+async fn write_archive(declared: u16) -> Vec<u8> {
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    for filename in ["foo.txt", "bar.txt", "baz.txt"] {
+        let entry = ZipEntryBuilder::new(filename.into(), Compression::Stored);
+        writer.write_entry_whole(entry, b"foo bar").await.unwrap();
+    }
+
+    let mut archive = writer.close().await.unwrap();
+    let eocdr_offset = archive.len() - 22;
+    archive[eocdr_offset + 8..eocdr_offset + 10].copy_from_slice(&declared.to_le_bytes());
+    archive[eocdr_offset + 10..eocdr_offset + 12].copy_from_slice(&declared.to_le_bytes());
+    archive
+}
+
+#[tokio::test]
+async fn central_directory_matches() {
+    let reader = ZipFileReader::new(write_archive(3).await).await.unwrap();
+    assert_eq!(reader.file().entries().len(), 3);
+}
+
+#[tokio::test]
+async fn central_directory_fewer_declared() {
+    let result = ZipFileReader::new(write_archive(2).await).await;
+    assert!(matches!(result, Err(ZipError::CentralDirectoryMismatch(2))));
+}
+
+#[tokio::test]
+async fn central_directory_more_declared() {
+    let result = ZipFileReader::new(write_archive(4).await).await;
+    assert!(matches!(result, Err(ZipError::CentralDirectoryMismatch(4))));
+}
@@ -0,0 +1,53 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::seek::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::spec::consts::{EOCDR_LENGTH, SIGNATURE_LENGTH};
+use crate::{Compression, ZipEntryBuilder};
+
+use futures_lite::io::Cursor;
+
+const COMMENT: &str = "foo bar";
+
+/// Writes an archive with the provided comment, returning it and the offset of the EOCDR's comment length.
+async fn archive(comment: &str) -> (Vec<u8>, usize) {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    writer.comment(comment.into());
+    writer.write_entry_whole(ZipEntryBuilder::new("foo.txt".into(), Compression::Stored), b"foo").await.unwrap();
+    let data = writer.close().await.unwrap();
+
+    let length_offset = data.len() - comment.len() - (SIGNATURE_LENGTH + EOCDR_LENGTH) + SIGNATURE_LENGTH + 16;
+    (data, length_offset)
+}
+
+#[tokio::test]
+async fn comment_length_consistent() {
+    let (data, _) = archive(COMMENT).await;
+
+    let reader = ZipFileReader::new(Cursor::new(data)).await.unwrap();
+    assert_eq!(reader.file().comment().as_str().unwrap(), COMMENT);
+    assert!(!reader.file().comment_length_mismatch());
+}
+
+#[tokio::test]
+async fn comment_length_zero_with_trailing_bytes() {
+    let (mut data, length_offset) = archive(COMMENT).await;
+    data[length_offset..length_offset + 2].copy_from_slice(&0u16.to_le_bytes());
+
+    let reader = ZipFileReader::new(Cursor::new(data)).await.unwrap();
+    assert_eq!(reader.file().comment().as_str().unwrap(), "");
+    assert!(reader.file().comment_length_mismatch());
+    assert_eq!(reader.file().entries().len(), 1);
+}
+
+#[tokio::test]
+async fn comment_length_exceeds_available() {
+    let (mut data, length_offset) = archive(COMMENT).await;
+    data[length_offset..length_offset + 2].copy_from_slice(&100u16.to_le_bytes());
+
+    let reader = ZipFileReader::new(Cursor::new(data)).await.unwrap();
+    assert_eq!(reader.file().comment().as_str().unwrap(), COMMENT);
+    assert!(reader.file().comment_length_mismatch());
+    assert_eq!(reader.file().entries().len(), 1);
+}
@@ -44,3 +44,88 @@ macro_rules! compressed_test_helper {
 }
 
 use compressed_test_helper;
+
+#[cfg(feature = "zstd")]
+#[tokio::test]
+async fn zstd_multiple_frames() {
+    use crate::base::read::io::entry::ZipEntryReader;
+    use crate::ZipEntryBuilder;
+    use async_compression::futures::write::ZstdEncoder;
+    use futures_lite::io::{AsyncReadExt, AsyncWriteExt, Cursor};
+
+    async fn frame(data: &[u8]) -> Vec<u8> {
+        let mut encoder = ZstdEncoder::new(Vec::new());
+        encoder.write_all(data).await.unwrap();
+        encoder.close().await.unwrap();
+        encoder.into_inner()
+    }
+
+    // Two frames separated by a skippable frame with a 3 byte payload.
+    let mut data = frame(b"foo ").await;
+    data.extend_from_slice(&0x184D2A50u32.to_le_bytes());
+    data.extend_from_slice(&3u32.to_le_bytes());
+    data.extend_from_slice(b"baz");
+    data.extend_from_slice(&frame(b"bar").await);
+
+    let mut reader = CompressedReader::new(Cursor::new(&data), Compression::Zstd).multiple_frames(true);
+    let mut read_data = String::new();
+    reader.read_to_string(&mut read_data).await.unwrap();
+    assert_eq!(read_data, "foo bar");
+
+    let entry = ZipEntryBuilder::new("foo.txt".into(), Compression::Zstd).build();
+    let mut reader =
+        ZipEntryReader::new_with_owned(Cursor::new(&data), &entry, data.len() as u64, &Default::default()).unwrap();
+    let mut read_data = String::new();
+    reader.read_to_string(&mut read_data).await.unwrap();
+    assert_eq!(read_data, "foo bar");
+
+    // Without multiple frames enabled, decoding stops at the end of the first frame.
+    let mut reader = CompressedReader::new(Cursor::new(&data), Compression::Zstd);
+    let mut read_data = String::new();
+    reader.read_to_string(&mut read_data).await.unwrap();
+    assert_eq!(read_data, "foo ");
+}
+
+#[cfg(feature = "lzma")]
+#[tokio::test]
+async fn lzma_without_eos_marker() {
+    use crate::base::write::ZipFileWriter;
+    use crate::ZipEntryBuilder;
+
+    use futures_lite::io::AsyncReadExt;
+
+    let data_raw = b"Hello, world! Hello, world! Hello, world!";
+    let data = include_bytes!("lzma-no-eos.data");
+
+    // Written by an encoder which relies on the uncompressed size rather than an end-of-stream marker.
+    let mut entry = ZipEntryBuilder::new("foo.txt".into(), Compression::Lzma).build();
+    entry.lzma_eos_marker = false;
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+    writer.write_entry_precompressed(entry, data, crc32fast::hash(data_raw), data_raw.len() as u64).await.unwrap();
+    let reader = writer.close_into_reader().await.unwrap();
+
+    let mut read_data = Vec::new();
+    reader.reader_with_entry(0).await.unwrap().read_to_end_checked(&mut read_data).await.unwrap();
+    assert_eq!(read_data, data_raw);
+
+    let mut reader = reader.reader_with_entry(0).await.unwrap();
+    assert!(!reader.entry().lzma_eos_marker);
+    reader.read_to_end(&mut Vec::new()).await.unwrap();
+}
+
+/// Tests that writing deflate64 compressed entries is rejected rather than panicking.
+#[cfg(feature = "deflate64")]
+#[tokio::test]
+async fn write_deflate64_unsupported() {
+    use crate::base::write::ZipFileWriter;
+    use crate::error::ZipError;
+    use crate::ZipEntryBuilder;
+
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    let entry = ZipEntryBuilder::new("foo.txt".to_string().into(), Compression::Deflate64);
+
+    let result = writer.write_entry_whole(entry.clone(), b"foo").await;
+    assert!(matches!(result, Err(ZipError::FeatureNotSupported(_))));
+    assert!(matches!(writer.write_entry_stream(entry).await, Err(ZipError::FeatureNotSupported(_))));
+}
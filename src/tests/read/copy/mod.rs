@@ -0,0 +1,41 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::mem::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::error::ZipError;
+use crate::{Compression, ZipEntryBuilder};
+
+async fn write_archive(data: &[u8]) -> Vec<u8> {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    let entry = ZipEntryBuilder::new("foo.bin".into(), Compression::Stored);
+    writer.write_entry_whole(entry, data).await.unwrap();
+    writer.close().await.unwrap()
+}
+
+#[tokio::test]
+async fn copy_to_checked() {
+    let data: Vec<u8> = (0..100_000u32).map(|i| (i % 251) as u8).collect();
+    let reader = ZipFileReader::new(write_archive(&data).await).await.unwrap();
+
+    let mut sink = Vec::new();
+    let (written, crc32) = reader.reader_with_entry(0).await.unwrap().copy_to_checked(&mut sink).await.unwrap();
+
+    assert_eq!(written, data.len() as u64);
+    assert_eq!(crc32, crc32fast::hash(&data));
+    assert_eq!(sink, data);
+}
+
+#[tokio::test]
+async fn copy_to_checked_corrupt() {
+    let mut archive = write_archive(b"foo bar").await;
+    let reader = ZipFileReader::new(archive.clone()).await.unwrap();
+    let entry = &reader.file().entries()[0];
+
+    let data_offset = (entry.header_offset() + entry.header_size()) as usize;
+    archive[data_offset] ^= 0xFF;
+
+    let reader = ZipFileReader::new(archive).await.unwrap();
+    let result = reader.reader_with_entry(0).await.unwrap().copy_to_checked(Vec::new()).await;
+    assert!(matches!(result, Err(ZipError::CRC32CheckError)));
+}
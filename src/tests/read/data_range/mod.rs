@@ -0,0 +1,40 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::mem::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::error::ZipError;
+use crate::{Compression, ZipEntryBuilder};
+
+use futures_lite::io::Cursor;
+
+#[tokio::test]
+async fn compressed_data_range() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    writer.write_entry_whole(ZipEntryBuilder::new("foo.txt".into(), Compression::Stored), b"foo").await.unwrap();
+    let entry = ZipEntryBuilder::new("bar.txt".into(), Compression::Stored);
+    writer.write_entry_stream(entry).await.unwrap().close().await.unwrap();
+    writer.write_entry_whole(ZipEntryBuilder::new("baz.txt".into(), Compression::Stored), b"baz").await.unwrap();
+    let data = writer.close().await.unwrap();
+
+    let reader = ZipFileReader::new(data.clone()).await.unwrap();
+    let mut cursor = Cursor::new(&data);
+
+    for (index, expected) in [&b"foo"[..], b"", b"baz"].into_iter().enumerate() {
+        let entry = &reader.file().entries()[index];
+        let range = entry.compressed_data_range(&mut cursor).await.unwrap();
+        assert_eq!(&data[range.start as usize..range.end as usize], expected);
+    }
+
+    // The local file header's size is cached, so a reader over different data isn't read from again.
+    let entry = &reader.file().entries()[0];
+    let expected = entry.compressed_data_range(&mut cursor).await.unwrap();
+    assert_eq!(entry.compressed_data_range(&mut Cursor::new(Vec::new())).await.unwrap(), expected);
+
+    let reader = ZipFileReader::new(data.clone()).await.unwrap();
+    let entry = &reader.file().entries()[2];
+    let mut corrupted = data;
+    corrupted[entry.header_offset() as usize] = 0;
+    let result = entry.compressed_data_range(&mut Cursor::new(corrupted)).await;
+    assert!(matches!(result, Err(ZipError::UnexpectedHeaderError(_, _))));
+}
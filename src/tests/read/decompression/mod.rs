@@ -0,0 +1,46 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::io::compressed::CompressedReader;
+use crate::base::read::mem::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::error::ZipError;
+use crate::{Compression, ZipEntryBuilder};
+
+use std::io::{Error, ErrorKind};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_lite::io::{AsyncRead, AsyncReadExt, BufReader};
+
+#[tokio::test]
+async fn corrupt_data_decompression_error() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    let entry = ZipEntryBuilder::new("foo.txt".into(), Compression::Deflate);
+    writer.write_entry_whole(entry, &[0xAB; 1000]).await.unwrap();
+    let mut data = writer.close().await.unwrap();
+
+    // Set the first deflate block's type to the reserved value (0b11).
+    data[30 + "foo.txt".len()] |= 0b110;
+
+    let reader = ZipFileReader::new(data).await.unwrap();
+    let result = reader.reader_with_entry(0).await.unwrap().read_to_end_checked(&mut Vec::new()).await;
+
+    assert!(matches!(result, Err(ZipError::DecompressionError { method: Compression::Deflate, .. })));
+}
+
+struct FailingReader;
+
+impl AsyncRead for FailingReader {
+    fn poll_read(self: Pin<&mut Self>, _: &mut Context<'_>, _: &mut [u8]) -> Poll<std::io::Result<usize>> {
+        Poll::Ready(Err(Error::from(ErrorKind::ConnectionReset)))
+    }
+}
+
+#[tokio::test]
+async fn upstream_failure_read_error() {
+    let mut reader = CompressedReader::new(BufReader::new(FailingReader), Compression::Deflate);
+    let result: Result<_, ZipError> = reader.read_to_end(&mut Vec::new()).await.map_err(Into::into);
+
+    assert!(matches!(result, Err(ZipError::UpstreamReadError(err)) if err.kind() == ErrorKind::ConnectionReset));
+}
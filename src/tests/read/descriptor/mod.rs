@@ -0,0 +1,121 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::seek::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::error::ZipError;
+use crate::spec::consts::{EOCDR_LENGTH, LFH_LENGTH, NON_ZIP64_MAX_SIZE, SIGNATURE_LENGTH, ZIP64_EOCDL_LENGTH};
+use crate::{Compression, ZipEntryBuilder};
+
+use futures_lite::io::{AsyncWriteExt, Cursor};
+
+const FILENAME: &str = "foo.txt";
+const DATA: &[u8] = b"Hello, world!";
+
+/// Writes a single stored entry via streaming, returning the archive and the offset of its data descriptor.
+async fn stream_archive(force_no_zip64: bool) -> (Vec<u8>, usize) {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    if force_no_zip64 {
+        writer = writer.force_no_zip64();
+    }
+
+    let mut entry_writer =
+        writer.write_entry_stream(ZipEntryBuilder::new(FILENAME.into(), Compression::Stored)).await.unwrap();
+    entry_writer.write_all(DATA).await.unwrap();
+    entry_writer.close().await.unwrap();
+    let data = writer.close().await.unwrap();
+
+    let descriptor_offset = if force_no_zip64 {
+        SIGNATURE_LENGTH + LFH_LENGTH + FILENAME.len() + DATA.len()
+    } else {
+        // Account for the ZIP64 extended information field written to the local file header.
+        SIGNATURE_LENGTH + LFH_LENGTH + FILENAME.len() + 20 + DATA.len()
+    };
+    (data, descriptor_offset)
+}
+
+#[tokio::test]
+async fn validate_data_descriptor() {
+    for force_no_zip64 in [true, false] {
+        let (data, _) = stream_archive(force_no_zip64).await;
+        let mut reader = ZipFileReader::new(Cursor::new(data)).await.unwrap();
+        reader.validate_data_descriptor(0).await.unwrap();
+    }
+}
+
+#[tokio::test]
+async fn validate_data_descriptor_without_signature() {
+    let (mut data, descriptor_offset) = stream_archive(true).await;
+
+    // Remove the descriptor's signature and shift the central directory offset accordingly.
+    data.drain(descriptor_offset..descriptor_offset + SIGNATURE_LENGTH);
+    let cd_offset_position = data.len() - (SIGNATURE_LENGTH + EOCDR_LENGTH) + 16;
+    let cd_offset = u32::from_le_bytes(data[cd_offset_position..cd_offset_position + 4].try_into().unwrap());
+    data[cd_offset_position..cd_offset_position + 4].copy_from_slice(&(cd_offset - 4).to_le_bytes());
+
+    let mut reader = ZipFileReader::new(Cursor::new(data)).await.unwrap();
+    reader.validate_data_descriptor(0).await.unwrap();
+}
+
+#[tokio::test]
+async fn validate_data_descriptor_mismatch() {
+    let (mut data, descriptor_offset) = stream_archive(true).await;
+
+    // Corrupt the descriptor's CRC32 value.
+    data[descriptor_offset + SIGNATURE_LENGTH] ^= 0xFF;
+
+    let mut reader = ZipFileReader::new(Cursor::new(data)).await.unwrap();
+    let result = reader.validate_data_descriptor(0).await;
+    assert!(matches!(result, Err(ZipError::DataDescriptorMismatch)));
+}
+
+/// Replaces the saturated 4 byte sizes of a streamed ZIP64 entry's data descriptor with the provided bytes, shifting
+/// the central directory offsets held within the end of central directory records accordingly.
+fn replace_zip64_descriptor_sizes(data: &mut Vec<u8>, descriptor_offset: usize, sizes: &[u8]) {
+    let sizes_offset = descriptor_offset + SIGNATURE_LENGTH + 4;
+    data.splice(sizes_offset..sizes_offset + 8, sizes.iter().copied());
+    let shift = sizes.len() as u64 - 8;
+
+    let eocdr_offset = data.len() - (SIGNATURE_LENGTH + EOCDR_LENGTH);
+    let eocdl_offset = eocdr_offset - ZIP64_EOCDL_LENGTH as usize;
+    let zip64_eocdr_offset = eocdl_offset - 56;
+
+    for position in [zip64_eocdr_offset + 48, eocdl_offset + 8] {
+        let offset = u64::from_le_bytes(data[position..position + 8].try_into().unwrap());
+        data[position..position + 8].copy_from_slice(&(offset + shift).to_le_bytes());
+    }
+
+    let position = eocdr_offset + 16;
+    let offset = u32::from_le_bytes(data[position..position + 4].try_into().unwrap());
+    if offset != NON_ZIP64_MAX_SIZE {
+        data[position..position + 4].copy_from_slice(&(offset + shift as u32).to_le_bytes());
+    }
+}
+
+#[tokio::test]
+async fn validate_data_descriptor_zip64_sizes() {
+    let (mut data, descriptor_offset) = stream_archive(false).await;
+
+    let mut sizes = Vec::new();
+    sizes.extend_from_slice(&(DATA.len() as u64).to_le_bytes());
+    sizes.extend_from_slice(&(DATA.len() as u64).to_le_bytes());
+    replace_zip64_descriptor_sizes(&mut data, descriptor_offset, &sizes);
+
+    let mut reader = ZipFileReader::new(Cursor::new(data)).await.unwrap();
+    reader.validate_data_descriptor(0).await.unwrap();
+}
+
+#[tokio::test]
+async fn validate_data_descriptor_zip64_short_sizes() {
+    let (mut data, descriptor_offset) = stream_archive(false).await;
+
+    // ZIP64 entries may only use 4 byte sizes if they're saturated.
+    let mut sizes = Vec::new();
+    sizes.extend_from_slice(&(DATA.len() as u32).to_le_bytes());
+    sizes.extend_from_slice(&(DATA.len() as u32).to_le_bytes());
+    replace_zip64_descriptor_sizes(&mut data, descriptor_offset, &sizes);
+
+    let mut reader = ZipFileReader::new(Cursor::new(data)).await.unwrap();
+    let result = reader.validate_data_descriptor(0).await;
+    assert!(matches!(result, Err(ZipError::DataDescriptorMismatch)));
+}
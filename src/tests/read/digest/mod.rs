@@ -0,0 +1,32 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::seek::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::{Compression, ZipDateTimeBuilder, ZipEntryBuilder};
+
+use futures_lite::io::Cursor;
+use sha2::{Digest, Sha256};
+
+async fn digest(entries: &[(&str, &[u8])], year: i32) -> Vec<u8> {
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    for (name, data) in entries {
+        let date = ZipDateTimeBuilder::new().year(year).month(1).day(1).build();
+        let entry = ZipEntryBuilder::new((*name).into(), Compression::Stored).last_modification_date(date);
+        writer.write_entry_whole(entry, data).await.unwrap();
+    }
+
+    let data = writer.close().await.unwrap();
+    let mut reader = ZipFileReader::new(Cursor::new(data)).await.unwrap();
+    reader.content_digest(Sha256::new()).await.unwrap().to_vec()
+}
+
+#[tokio::test]
+async fn content_digest_ignores_metadata() {
+    let expected = digest(&[("foo.txt", b"foo"), ("bar.txt", b"bar")], 2000).await;
+
+    assert_eq!(digest(&[("bar.txt", b"bar"), ("foo.txt", b"foo")], 2020).await, expected);
+    assert_ne!(digest(&[("foo.txt", b"foo"), ("bar.txt", b"baz")], 2000).await, expected);
+    assert_ne!(digest(&[("foo.txt", b"foob"), ("bar.txt", b"ar")], 2000).await, expected);
+}
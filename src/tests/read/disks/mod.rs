@@ -0,0 +1,46 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::mem::ZipFileReader;
+use crate::base::read::read_summary;
+use crate::base::write::ZipFileWriter;
+use crate::error::ZipError;
+use crate::{Compression, ZipEntryBuilder};
+
+use futures_lite::io::Cursor;
+
+#[tokio::test]
+async fn single_disk() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    let entry = ZipEntryBuilder::new("foo.txt".into(), Compression::Stored);
+    writer.write_entry_whole(entry, b"foo").await.unwrap();
+
+    let reader = writer.close_into_reader().await.unwrap();
+    assert_eq!(reader.file().disk_number(), 0);
+    assert_eq!(reader.file().total_disks(), 1);
+
+    let data = include_bytes!("../zip64/zip64.zip");
+    let reader = ZipFileReader::new(data.to_vec()).await.unwrap();
+    assert_eq!(reader.file().disk_number(), 0);
+    assert_eq!(reader.file().total_disks(), 1);
+}
+
+#[tokio::test]
+async fn spanned_disks() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    let entry = ZipEntryBuilder::new("foo.txt".into(), Compression::Stored);
+    writer.write_entry_whole(entry, b"foo").await.unwrap();
+
+    // Mark the EOCDR as being on the third disk, with the central directory on the first.
+    let mut data = writer.close().await.unwrap();
+    let eocdr = data.len() - 22;
+    data[eocdr + 4..eocdr + 6].copy_from_slice(&2u16.to_le_bytes());
+
+    let result = ZipFileReader::new(data.clone()).await;
+    assert!(matches!(result, Err(ZipError::FeatureNotSupported(_))));
+
+    // The summary can still be read in order to diagnose the failure.
+    let summary = read_summary(Cursor::new(&data)).await.unwrap();
+    assert_eq!(summary.disk_number(), 2);
+    assert_eq!(summary.total_disks(), 3);
+}
@@ -0,0 +1,30 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::mem::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::{Compression, ZipEntryBuilder};
+
+#[tokio::test]
+async fn read_to_string_with_encoding() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    // "café" encoded as Windows-1252 and "日本" encoded as Shift_JIS.
+    let entry = ZipEntryBuilder::new("cp1252.txt".into(), Compression::Stored);
+    writer.write_entry_whole(entry, &[0x63, 0x61, 0x66, 0xE9]).await.unwrap();
+    let entry = ZipEntryBuilder::new("shift_jis.txt".into(), Compression::Stored);
+    writer.write_entry_whole(entry, &[0x93, 0xFA, 0x96, 0x7B]).await.unwrap();
+
+    let reader = ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+
+    let mut buffer = String::new();
+    let mut entry_reader = reader.reader_with_entry(0).await.unwrap();
+    let read = entry_reader.read_to_string_with_encoding(encoding_rs::WINDOWS_1252, &mut buffer).await.unwrap();
+    assert_eq!(read, 4);
+    assert_eq!(buffer, "café");
+
+    let mut buffer = String::new();
+    let mut entry_reader = reader.reader_with_entry(1).await.unwrap();
+    entry_reader.read_to_string_with_encoding(encoding_rs::SHIFT_JIS, &mut buffer).await.unwrap();
+    assert_eq!(buffer, "日本");
+}
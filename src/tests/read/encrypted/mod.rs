@@ -0,0 +1,162 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::mem::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::error::ZipError;
+use crate::spec::consts::SIGNATURE_LENGTH;
+use crate::{Compression, ZipEntryBuilder};
+
+use futures_lite::io::Cursor;
+
+#[tokio::test]
+async fn encrypted_entry_metadata() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    let entry = ZipEntryBuilder::new("foo.txt".into(), Compression::Stored);
+    writer.write_entry_whole(entry, b"foo bar").await.unwrap();
+    let mut data = writer.close().await.unwrap();
+
+    // Set the encrypted bit of the general purpose flag in both the local file header and central directory.
+    let summary = crate::base::read::read_summary(Cursor::new(&data)).await.unwrap();
+    data[SIGNATURE_LENGTH + 2] |= 0x1;
+    data[summary.central_directory_offset() as usize + SIGNATURE_LENGTH + 4] |= 0x1;
+
+    let reader = ZipFileReader::new(data).await.unwrap();
+    let entry = &reader.file().entries()[0];
+
+    assert!(entry.encrypted());
+    assert_eq!(entry.filename().as_str().unwrap(), "foo.txt");
+    assert_eq!(entry.uncompressed_size(), 7);
+    assert!(matches!(reader.reader_with_entry(0).await, Err(ZipError::FeatureNotSupported("encryption"))));
+}
+
+#[tokio::test]
+async fn strong_encrypted_entry() {
+    use crate::base::read::stream::ZipFileReader as StreamZipFileReader;
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+    let entry = ZipEntryBuilder::new("foo.txt".into(), Compression::Stored);
+    writer.write_entry_whole(entry, b"foo bar").await.unwrap();
+    let mut data = writer.close().await.unwrap();
+
+    // Set both the encrypted & strong encryption bits of the general purpose flag.
+    let summary = crate::base::read::read_summary(Cursor::new(&data)).await.unwrap();
+    data[SIGNATURE_LENGTH + 2] |= 0x41;
+    data[summary.central_directory_offset() as usize + SIGNATURE_LENGTH + 4] |= 0x41;
+
+    let reader = ZipFileReader::new(data.clone()).await.unwrap();
+    let entry = &reader.file().entries()[0];
+
+    assert!(entry.encrypted());
+    assert!(entry.strong_encryption());
+    assert!(matches!(reader.reader_with_entry(0).await, Err(ZipError::StrongEncryptionUnsupported)));
+
+    let result = StreamZipFileReader::new(Cursor::new(data)).next_without_entry().await;
+    assert!(matches!(result, Err(ZipError::StrongEncryptionUnsupported)));
+}
+
+#[tokio::test]
+async fn aes_effective_compression() {
+    use crate::spec::header::{AeVersion, AesExtraField, ExtraField};
+    use crate::StoredZipEntry;
+
+    let field = AesExtraField { version: AeVersion::Ae2, vendor_id: *b"AE", strength: 3, compression: 0 };
+    let mut writer = ZipFileWriter::new(Vec::new());
+    let entry =
+        ZipEntryBuilder::new("foo.txt".into(), Compression::Stored).extra_fields(vec![ExtraField::Aes(field.clone())]);
+    writer.write_entry_whole(entry, b"foo bar").await.unwrap();
+    let mut data = writer.close().await.unwrap();
+
+    // Set the compression method to 99 (AES) in both the local file header and central directory.
+    let summary = crate::base::read::read_summary(Cursor::new(&data)).await.unwrap();
+    data[SIGNATURE_LENGTH + 4] = 99;
+    data[summary.central_directory_offset() as usize + SIGNATURE_LENGTH + 6] = 99;
+
+    let reader = ZipFileReader::new(data).await.unwrap();
+    let entry = &reader.file().entries()[0];
+    assert_eq!(entry.aes_version(), Some(AeVersion::Ae2));
+    assert_eq!(entry.effective_compression(), Compression::Stored);
+
+    let field = AesExtraField { compression: 12, ..field };
+    let entry = ZipEntryBuilder::new("bar.txt".into(), Compression::Stored).extra_fields(vec![ExtraField::Aes(field)]);
+    let entry = StoredZipEntry::new(entry.build(), 0);
+    assert_eq!(entry.compression(), Compression::Stored);
+    #[cfg(feature = "bzip2")]
+    assert_eq!(entry.effective_compression(), Compression::Bz);
+    #[cfg(not(feature = "bzip2"))]
+    assert_eq!(entry.effective_compression(), Compression::Stored);
+}
+
+/// Returns an archive holding a single AES encrypted entry, whose AES field has the provided vendor version.
+async fn aes_archive(version: u8) -> Vec<u8> {
+    use crate::spec::header::{AeVersion, AesExtraField, ExtraField};
+
+    let field = AesExtraField { version: AeVersion::Ae2, vendor_id: *b"AE", strength: 3, compression: 0 };
+    let mut writer = ZipFileWriter::new(Vec::new());
+    let entry = ZipEntryBuilder::new("foo.txt".into(), Compression::Stored).extra_fields(vec![ExtraField::Aes(field)]);
+    writer.write_entry_whole(entry, b"foo bar").await.unwrap();
+    let mut data = writer.close().await.unwrap();
+
+    // Set the encrypted bit, compression method (99), and AES vendor version in both headers.
+    let summary = crate::base::read::read_summary(Cursor::new(&data)).await.unwrap();
+    let cdh = summary.central_directory_offset() as usize + SIGNATURE_LENGTH;
+    for flags in [SIGNATURE_LENGTH + 2, cdh + 4] {
+        data[flags] |= 0x1;
+        data[flags + 2] = 99;
+    }
+    let mut start = 0;
+    while let Some(position) = data[start..].windows(2).position(|id| id == [0x01, 0x99]) {
+        data[start + position + 4] = version;
+        start += position + 2;
+    }
+
+    data
+}
+
+#[tokio::test]
+async fn aes_rewrite_round_trip() {
+    use crate::spec::header::AeVersion;
+
+    let reader = ZipFileReader::new(aes_archive(2).await).await.unwrap();
+    let stored = reader.file().entries()[0].clone();
+    assert_eq!(stored.compression(), Compression::Stored);
+    assert_eq!(stored.aes_version(), Some(AeVersion::Ae2));
+
+    // Listing the encrypted data as-is must keep the AES marker method, as the data itself is unchanged.
+    let index = ZipFileWriter::new(Vec::new()).write_index_only([stored.clone()]).await.unwrap();
+    let summary = crate::base::read::read_summary(Cursor::new(&index)).await.unwrap();
+    assert_eq!(index[summary.central_directory_offset() as usize + SIGNATURE_LENGTH + 6], 99);
+
+    let index = ZipFileReader::new(index).await.unwrap();
+    let entry = &index.file().entries()[0];
+    assert!(entry.encrypted());
+    assert_eq!(entry.compression(), Compression::Stored);
+    assert_eq!(entry.aes_version(), Some(AeVersion::Ae2));
+
+    // Rewriting the entry alongside its decrypted data must drop the AES marker method & field.
+    let mut writer = ZipFileWriter::new(Vec::new());
+    writer.write_entry_whole(stored.to_entry(), b"foo bar").await.unwrap();
+    let data = writer.close().await.unwrap();
+    assert_eq!(data[SIGNATURE_LENGTH + 4], 0);
+
+    let rewritten = ZipFileReader::new(data).await.unwrap();
+    let entry = &rewritten.file().entries()[0];
+    assert!(!entry.encrypted());
+    assert_eq!(entry.compression(), Compression::Stored);
+    assert_eq!(entry.aes_version(), None);
+}
+
+#[tokio::test]
+async fn aes_unknown_version() {
+    use crate::spec::consts::AES_COMPRESSION_METHOD;
+    use crate::spec::header::{ExtraField, HeaderId};
+
+    let reader = ZipFileReader::new(aes_archive(3).await).await.unwrap();
+    let entry = &reader.file().entries()[0];
+
+    assert_eq!(entry.aes_version(), None);
+    assert_eq!(entry.compression(), Compression::Custom(AES_COMPRESSION_METHOD));
+    assert!(
+        matches!(&entry.extra_fields()[0], ExtraField::Unknown(field) if field.header_id == HeaderId::AES_EXTRA_FIELD)
+    );
+}
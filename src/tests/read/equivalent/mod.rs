@@ -0,0 +1,62 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::archives_equivalent;
+use crate::base::write::ZipFileWriter;
+use crate::{Compression, ZipDateTimeBuilder, ZipEntryBuilder};
+
+use futures_lite::io::Cursor;
+
+async fn write_archive(entries: &[(&str, &str)], year: i32) -> Vec<u8> {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    let date = ZipDateTimeBuilder::new().year(year).month(1).day(1).build();
+
+    for (filename, data) in entries {
+        let entry = ZipEntryBuilder::new(filename.to_string().into(), Compression::Stored).last_modification_date(date);
+        writer.write_entry_whole(entry, data.as_bytes()).await.unwrap();
+    }
+
+    writer.close().await.unwrap()
+}
+
+async fn equivalent(a: Vec<u8>, b: Vec<u8>) -> bool {
+    archives_equivalent(Cursor::new(a), Cursor::new(b)).await.unwrap()
+}
+
+#[tokio::test]
+async fn equivalent_reordered() {
+    let a = write_archive(&[("foo.txt", "foo"), ("bar.txt", "bar")], 2000).await;
+    let b = write_archive(&[("bar.txt", "bar"), ("foo.txt", "foo")], 2020).await;
+
+    assert!(equivalent(a, b).await);
+}
+
+#[tokio::test]
+async fn equivalent_different_content() {
+    let a = write_archive(&[("foo.txt", "foo"), ("bar.txt", "bar")], 2000).await;
+    let b = write_archive(&[("foo.txt", "foo"), ("bar.txt", "baz")], 2000).await;
+
+    assert!(!equivalent(a, b).await);
+}
+
+#[tokio::test]
+async fn equivalent_different_names() {
+    let a = write_archive(&[("foo.txt", "foo"), ("bar.txt", "bar")], 2000).await;
+    let b = write_archive(&[("foo.txt", "foo"), ("baz.txt", "bar")], 2000).await;
+    let c = write_archive(&[("foo.txt", "foo")], 2000).await;
+
+    assert!(!equivalent(a.clone(), b).await);
+    assert!(!equivalent(a, c).await);
+}
+
+#[tokio::test]
+async fn equivalent_missing_crc() {
+    let a = write_archive(&[("foo.txt", "foo")], 2000).await;
+    let mut b = a.clone();
+
+    // Zero the CRC32 value within the central directory header, which starts directly after the entry's data.
+    let cd_offset = 30 + "foo.txt".len() + "foo".len();
+    b[cd_offset + 16..cd_offset + 20].copy_from_slice(&[0; 4]);
+
+    assert!(equivalent(a, b).await);
+}
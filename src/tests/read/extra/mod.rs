@@ -0,0 +1,45 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::mem::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::spec::header::{ExtraField, HeaderId, UnknownExtraField};
+use crate::{Compression, ZipEntryBuilder};
+
+/// Writes an archive with a single entry carrying the provided raw extra field.
+async fn write_archive(header_id: HeaderId, content: &[u8]) -> Vec<u8> {
+    let field = UnknownExtraField { header_id, data_size: content.len() as u16, content: content.to_vec() };
+    let entry =
+        ZipEntryBuilder::new("foo.txt".into(), Compression::Stored).extra_fields(vec![ExtraField::Unknown(field)]);
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+    writer.write_entry_whole(entry, b"foo").await.unwrap();
+    writer.close().await.unwrap()
+}
+
+/// Reads an archive written via `write_archive()`, asserting that its field was kept as an unknown field.
+async fn assert_unknown(data: Vec<u8>, header_id: HeaderId, content: &[u8]) {
+    let reader = ZipFileReader::new(data).await.unwrap();
+    let fields = reader.file().entries()[0].extra_fields();
+    assert!(matches!(fields, [ExtraField::Unknown(field)] if field.header_id == header_id && field.content == content));
+
+    let mut data = Vec::new();
+    reader.reader_with_entry(0).await.unwrap().read_to_end_checked(&mut data).await.unwrap();
+    assert_eq!(data, b"foo");
+}
+
+#[tokio::test]
+async fn metadata_truncated() {
+    // A key whose length exceeds the remaining data.
+    let content = [0x05, 0x00, b'f', b'o', b'o'];
+    let data = write_archive(HeaderId::METADATA_EXTRA_FIELD, &content).await;
+    assert_unknown(data, HeaderId::METADATA_EXTRA_FIELD, &content).await;
+}
+
+#[tokio::test]
+async fn crc32c_wrong_length() {
+    for content in [&[0x01, 0x02, 0x03][..], &[0x01, 0x02, 0x03, 0x04, 0x05]] {
+        let data = write_archive(HeaderId::CRC32C_EXTRA_FIELD, content).await;
+        assert_unknown(data, HeaderId::CRC32C_EXTRA_FIELD, content).await;
+    }
+}
@@ -0,0 +1,65 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::mem::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::spec::consts::{LFH_LENGTH, SIGNATURE_LENGTH};
+use crate::{Compression, ZipEntryBuilder};
+
+use futures_lite::io::Cursor;
+
+#[tokio::test]
+async fn local_filename_matches() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    let entry = ZipEntryBuilder::new("foo.txt".into(), Compression::Stored);
+    writer.write_entry_whole(entry, b"foo").await.unwrap();
+
+    let reader = ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+    let entry = &reader.file().entries()[0];
+    let local = entry.local_filename(&mut Cursor::new(reader.data())).await.unwrap();
+
+    assert_eq!(local.as_str().unwrap(), "foo.txt");
+}
+
+#[tokio::test]
+async fn local_filename_differs() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    let entry = ZipEntryBuilder::new("foo.txt".into(), Compression::Stored);
+    writer.write_entry_whole(entry, b"foo").await.unwrap();
+
+    // Overwrite the filename within the local file header only.
+    let mut data = writer.close().await.unwrap();
+    let offset = SIGNATURE_LENGTH + LFH_LENGTH;
+    data[offset..offset + 7].copy_from_slice(b"bar.txt");
+
+    let reader = ZipFileReader::new(data).await.unwrap();
+    let entry = &reader.file().entries()[0];
+    let local = entry.local_filename(&mut Cursor::new(reader.data())).await.unwrap();
+
+    assert_eq!(entry.filename().as_str().unwrap(), "foo.txt");
+    assert_eq!(local.as_str().unwrap(), "bar.txt");
+}
+
+#[tokio::test]
+async fn bom_prefixed_filename() {
+    use crate::{StringEncoding, ZipString};
+
+    let filename = [&[0xEF, 0xBB, 0xBF][..], b"foo.txt"].concat();
+    let comment = [&[0xEF, 0xBB, 0xBF][..], b"bar"].concat();
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    // Once with the UTF-8 flag set, and once without it.
+    for encoding in [StringEncoding::Utf8, StringEncoding::Raw] {
+        let entry = ZipEntryBuilder::new(ZipString::new(filename.clone(), encoding), Compression::Stored)
+            .comment(ZipString::new(comment.clone(), encoding));
+        writer.write_entry_whole(entry, b"foo").await.unwrap();
+    }
+
+    let reader = writer.close_into_reader().await.unwrap();
+    for entry in reader.file().entries() {
+        assert_eq!(entry.filename().as_str().unwrap(), "foo.txt");
+        assert_eq!(entry.filename().alternative(), Some(filename.as_slice()));
+        assert_eq!(entry.comment().as_str().unwrap(), "bar");
+        assert_eq!(entry.comment().alternative(), Some(comment.as_slice()));
+    }
+}
@@ -0,0 +1,38 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::seek::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::{Compression, ZipEntryBuilder};
+
+use futures_lite::io::Cursor;
+
+#[tokio::test]
+async fn entries_filtered() {
+    let entries: [(&str, &[u8]); 4] =
+        [("foo.json", b"{}"), ("bar.txt", b"bar"), ("baz/qux.json", b"[1, 2, 3]"), ("qux.json", &[0; 1024])];
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    for (name, data) in entries {
+        let entry = ZipEntryBuilder::new(name.into(), Compression::Stored);
+        writer.write_entry_whole(entry, data).await.unwrap();
+    }
+
+    let mut reader = ZipFileReader::new(Cursor::new(writer.close().await.unwrap())).await.unwrap();
+
+    let json = |entry: &crate::StoredZipEntry| entry.filename().as_str().unwrap().ends_with(".json");
+    let selected: Vec<usize> = reader.file().entries_filtered(json).collect();
+    assert_eq!(selected, [0, 2, 3]);
+
+    let small: Vec<usize> = reader.file().entries_filtered(|entry| entry.uncompressed_size() < 16).collect();
+    assert_eq!(small, [0, 1, 2]);
+
+    let none: Vec<usize> = reader.file().entries_filtered(|_| false).collect();
+    assert!(none.is_empty());
+
+    for index in selected {
+        let mut data = Vec::new();
+        reader.reader_with_entry(index).await.unwrap().read_to_end_checked(&mut data).await.unwrap();
+        assert_eq!(data, entries[index].1);
+    }
+}
@@ -0,0 +1,44 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::write::ZipFileWriter;
+use crate::utils::glob_match;
+use crate::{Compression, ZipEntryBuilder};
+
+#[tokio::test]
+async fn glob() {
+    let names = ["logs/", "logs/a.txt", "logs/b.log", "logs/2024/c.txt", "logs/2024/01/d.txt", "LOGS/e.txt", "f.txt"];
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    for name in names {
+        let entry = ZipEntryBuilder::new(name.into(), Compression::Stored);
+        writer.write_entry_whole(entry, &[]).await.unwrap();
+    }
+
+    let reader = writer.close_into_reader().await.unwrap();
+    let file = reader.file();
+
+    assert_eq!(file.glob("logs/*.txt"), [1]);
+    assert_eq!(file.glob("logs/?.*"), [1, 2]);
+    assert_eq!(file.glob("logs/**/*.txt"), [1, 3, 4]);
+    assert_eq!(file.glob("logs/**"), [0, 1, 2, 3, 4]);
+    assert_eq!(file.glob("*/*.txt"), [1, 5]);
+    assert_eq!(file.glob("**.txt"), [1, 3, 4, 5, 6]);
+    assert_eq!(file.glob("*"), [6]);
+    assert_eq!(file.glob("logs/2024/?/d.txt"), [] as [usize; 0]);
+}
+
+#[test]
+fn glob_recursive_directories() {
+    // A `**/` component only matches whole directories, or none at all.
+    assert!(glob_match("a/**/b", "a/b"));
+    assert!(glob_match("a/**/b", "a/x/b"));
+    assert!(glob_match("a/**/b", "a/x/y/b"));
+    assert!(!glob_match("a/**/b", "a/xb"));
+    assert!(!glob_match("a/**/b", "a/x/yb"));
+    assert!(glob_match("logs/**/x.txt", "logs/abc/x.txt"));
+    assert!(!glob_match("logs/**/x.txt", "logs/abcx.txt"));
+    assert!(glob_match("**/x.txt", "x.txt"));
+    assert!(glob_match("**/x.txt", "a/b/x.txt"));
+    assert!(!glob_match("**/x.txt", "ax.txt"));
+}
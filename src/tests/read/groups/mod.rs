@@ -0,0 +1,26 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::mem::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::{Compression, ZipEntryBuilder};
+
+#[tokio::test]
+async fn top_level_groups() {
+    let names = ["foo/", "foo/bar.txt", "bar/baz/qux.txt", "/bar//baz.txt", "./baz.txt", "baz.txt", "//", ""];
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    for name in names {
+        let entry = ZipEntryBuilder::new(name.into(), Compression::Stored);
+        writer.write_entry_whole(entry, &[]).await.unwrap();
+    }
+
+    let reader = ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+    let groups = reader.file().top_level_groups();
+
+    assert_eq!(groups.len(), 4);
+    assert_eq!(groups["foo"], [0, 1]);
+    assert_eq!(groups["bar"], [2, 3]);
+    assert_eq!(groups["baz.txt"], [4, 5]);
+    assert_eq!(groups[""], [6, 7]);
+}
@@ -0,0 +1,27 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::seek::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::error::ZipError;
+use crate::{Compression, ZipEntryBuilder};
+
+use futures_lite::io::Cursor;
+
+#[tokio::test]
+async fn read_entry_prefix() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    let entry = ZipEntryBuilder::new("image.png".into(), Compression::Stored);
+    let mut data = b"\x89PNG\r\n\x1a\n".to_vec();
+    data.extend_from_slice(&[0xAB; 100_000]);
+    writer.write_entry_whole(entry, &data).await.unwrap();
+    writer.write_entry_whole(ZipEntryBuilder::new("foo.txt".into(), Compression::Stored), b"foo").await.unwrap();
+
+    let mut reader = ZipFileReader::new(Cursor::new(writer.close().await.unwrap())).await.unwrap();
+
+    assert_eq!(reader.read_entry_prefix(0, 8).await.unwrap(), b"\x89PNG\r\n\x1a\n");
+    assert_eq!(reader.read_entry_prefix(1, 16).await.unwrap(), b"foo");
+    assert_eq!(reader.read_entry_prefix(1, 0).await.unwrap(), b"");
+    assert!(matches!(reader.read_entry_prefix(2, 8).await, Err(ZipError::EntryIndexOutOfBounds)));
+}
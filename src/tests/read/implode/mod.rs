@@ -0,0 +1,61 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::io::implode::{ImplodeDecoder, ImplodeOptions};
+use crate::base::read::mem::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::error::ZipError;
+use crate::{Compression, ZipEntryBuilder};
+
+use std::io::ErrorKind;
+
+use futures_lite::io::{AsyncReadExt, Cursor};
+
+const SAMPLE: &[u8] = include_bytes!("sample.txt");
+
+async fn assert_explodes(data: &[u8]) {
+    let reader = ZipFileReader::new(data.to_vec()).await.unwrap();
+    assert_eq!(reader.file().entries()[0].compression(), Compression::Implode);
+
+    let mut buffer = Vec::new();
+    reader.reader_with_entry(0).await.unwrap().read_to_end_checked(&mut buffer).await.unwrap();
+    assert_eq!(buffer, SAMPLE);
+}
+
+/// Tests reading an entry imploded with an 8K dictionary & a literal tree.
+#[tokio::test]
+async fn read_implode_8k_literal_tree() {
+    assert_explodes(include_bytes!("implode-8k-literal.zip")).await;
+}
+
+/// Tests reading an entry imploded with a 4K dictionary & raw literals.
+#[tokio::test]
+async fn read_implode_4k() {
+    assert_explodes(include_bytes!("implode-4k.zip")).await;
+}
+
+/// Tests that truncated imploded data is rejected rather than decoded partially.
+#[tokio::test]
+async fn read_implode_truncated() {
+    let data = include_bytes!("implode-4k.zip");
+    let reader = ZipFileReader::new(data.to_vec()).await.unwrap();
+    let entry = &reader.file().entries()[0];
+
+    let start = (entry.header_offset() + entry.header_size()) as usize;
+    let truncated = &data[start..start + entry.compressed_size() as usize / 2];
+
+    let mut decoder = ImplodeDecoder::new(Cursor::new(truncated), ImplodeOptions::default(), Some(SAMPLE.len() as u64));
+    let error = decoder.read_to_end(&mut Vec::new()).await.unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+}
+
+/// Tests that writing imploded entries is rejected.
+#[tokio::test]
+async fn write_implode_unsupported() {
+    let mut writer = ZipFileWriter::new(Vec::<u8>::new());
+    let entry = ZipEntryBuilder::new("foo.txt".to_string().into(), Compression::Implode);
+
+    let result = writer.write_entry_whole(entry.clone(), b"foo").await;
+    assert!(matches!(result, Err(ZipError::FeatureNotSupported(_))));
+    assert!(matches!(writer.write_entry_stream(entry).await, Err(ZipError::FeatureNotSupported(_))));
+}
@@ -0,0 +1,37 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::seek::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::{Compression, ZipEntryBuilder};
+
+use futures_lite::io::{AsyncReadExt, Cursor};
+
+#[tokio::test]
+async fn entries_lazy() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    for index in 0..10 {
+        let entry = ZipEntryBuilder::new(format!("{index}.txt").into(), Compression::Stored);
+        writer.write_entry_whole(entry, format!("entry {index}").as_bytes()).await.unwrap();
+    }
+    let data = writer.close().await.unwrap();
+
+    let mut entries = ZipFileReader::entries_lazy(Cursor::new(data)).await.unwrap();
+    assert_eq!(entries.remaining(), 10);
+
+    let mut index = 0;
+    while let Some(entry) = entries.next_entry().await.unwrap() {
+        assert_eq!(entry.filename().as_str().unwrap(), format!("{index}.txt"));
+
+        // Reading an entry's data between records shouldn't affect the iterator.
+        let mut buffer = String::new();
+        entries.reader_without_entry(&entry).await.unwrap().read_to_string(&mut buffer).await.unwrap();
+        assert_eq!(buffer, format!("entry {index}"));
+
+        index += 1;
+    }
+
+    assert_eq!(index, 10);
+    assert_eq!(entries.remaining(), 0);
+}
@@ -62,3 +62,27 @@ async fn locator_buffer_boundary_test() {
     assert!(eocdr.is_ok());
     assert_eq!(eocdr.unwrap(), 4);
 }
+
+#[tokio::test]
+async fn read_empty_archive() {
+    use crate::base::read::mem::ZipFileReader;
+
+    let reader = ZipFileReader::new(include_bytes!("empty.zip").to_vec()).await.unwrap();
+    assert!(reader.file().is_empty());
+    assert!(reader.file().entries().is_empty());
+}
+
+#[tokio::test]
+async fn read_zero_length_input() {
+    use crate::base::read::{mem, seek};
+    use crate::error::ZipError;
+    use futures_lite::io::Cursor;
+
+    assert!(matches!(mem::ZipFileReader::new(Vec::new()).await, Err(ZipError::EmptyArchive)));
+    assert!(matches!(seek::ZipFileReader::new(Cursor::new(&[])).await, Err(ZipError::EmptyArchive)));
+    assert!(matches!(crate::base::read::read_summary(Cursor::new(&[])).await, Err(ZipError::EmptyArchive)));
+
+    // Non-empty input without an EOCDR is still reported as such.
+    let result = mem::ZipFileReader::new(vec![0; 100]).await;
+    assert!(matches!(result, Err(ZipError::UnableToLocateEOCDR)));
+}
@@ -0,0 +1,32 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::write::ZipFileWriter;
+use crate::{Compression, ZipEntryBuilder, ZipFileBuilder};
+
+#[tokio::test]
+async fn entry_map() {
+    let names = ["zeta.txt", "alpha/", "alpha/beta.txt", "zeta.txt", "mid.txt"];
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    for name in names {
+        let entry = ZipEntryBuilder::new(name.into(), Compression::Stored);
+        writer.write_entry_whole(entry, &[]).await.unwrap();
+    }
+
+    let reader = writer.close_into_reader().await.unwrap();
+    let map = reader.file().entry_map();
+
+    let keys: Vec<&str> = map.keys().map(String::as_str).collect();
+    assert_eq!(keys, ["zeta.txt", "alpha/", "alpha/beta.txt", "mid.txt"]);
+    assert_eq!(map["zeta.txt"], 0);
+    assert_eq!(map["alpha/beta.txt"], 2);
+    assert_eq!(map["mid.txt"], 4);
+    assert!(std::ptr::eq(map, reader.file().entry_map()));
+
+    let file = ZipFileBuilder::from(reader.file().clone()).add_entry(reader.file().entries()[2].clone()).build();
+    assert_eq!(file.entry_map().len(), 4);
+
+    let file = ZipFileBuilder::new().add_entry(reader.file().entries()[4].clone()).build();
+    assert_eq!(file.entry_map().keys().collect::<Vec<_>>(), ["mid.txt"]);
+}
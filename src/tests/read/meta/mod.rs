@@ -0,0 +1,48 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::write::ZipFileWriter;
+use crate::error::ZipError;
+use crate::{Compression, ZipEntryBuilder};
+
+use futures_lite::io::Cursor;
+
+async fn write_archive() -> Vec<u8> {
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    for (filename, data) in [("foo.txt", "foo"), ("bar.txt", "bar baz")] {
+        let entry = ZipEntryBuilder::new(filename.into(), Compression::Stored);
+        writer.write_entry_whole(entry, data.as_bytes()).await.unwrap();
+    }
+
+    writer.close().await.unwrap()
+}
+
+#[tokio::test]
+async fn reader_with_entry_meta_mem() {
+    let reader = crate::base::read::mem::ZipFileReader::new(write_archive().await).await.unwrap();
+    let (entry, mut entry_reader) = reader.reader_with_entry_meta(1).await.unwrap();
+
+    let mut data = String::new();
+    entry_reader.read_to_string_checked(&mut data).await.unwrap();
+
+    assert_eq!(entry.filename().as_str().unwrap(), "bar.txt");
+    assert_eq!(entry.uncompressed_size(), 7);
+    assert_eq!(data, "bar baz");
+
+    let result = reader.reader_with_entry_meta(2).await;
+    assert!(matches!(result, Err(ZipError::EntryIndexOutOfBounds)));
+}
+
+#[tokio::test]
+async fn reader_with_entry_meta_seek() {
+    let mut reader = crate::base::read::seek::ZipFileReader::new(Cursor::new(write_archive().await)).await.unwrap();
+    let (entry, mut entry_reader) = reader.reader_with_entry_meta(0).await.unwrap();
+
+    let mut data = String::new();
+    entry_reader.read_to_string_checked(&mut data).await.unwrap();
+
+    assert_eq!(entry.filename().as_str().unwrap(), "foo.txt");
+    assert_eq!(entry.crc32(), crc32fast::hash(b"foo"));
+    assert_eq!(data, "foo");
+}
@@ -0,0 +1,64 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::mem::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::{Compression, ZipEntryBuilder};
+
+const PNG_DATA: &[u8] = b"\x89PNG\r\n\x1a\n\x00\x00\x00\x0dIHDR";
+
+async fn write_archive(filename: &str, data: &[u8]) -> Vec<u8> {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    let entry = ZipEntryBuilder::new(filename.into(), Compression::Stored);
+    writer.write_entry_whole(entry, data).await.unwrap();
+    writer.close().await.unwrap()
+}
+
+#[test]
+fn guessed_content_type_from_extension() {
+    let entry = ZipEntryBuilder::new("foo/bar.html".into(), Compression::Stored).build();
+    assert_eq!(entry.guessed_content_type(), Some(mime_guess::mime::TEXT_HTML));
+
+    let entry = ZipEntryBuilder::new("foo/bar".into(), Compression::Stored).build();
+    assert_eq!(entry.guessed_content_type(), None);
+
+    let entry = ZipEntryBuilder::new("foo.html/".into(), Compression::Stored).build();
+    assert_eq!(entry.guessed_content_type(), None);
+}
+
+#[tokio::test]
+async fn guessed_content_type_from_magic() {
+    let reader = ZipFileReader::new(write_archive("image.bin", PNG_DATA).await).await.unwrap();
+    let mut entry_reader = reader.reader_with_entry(0).await.unwrap();
+
+    assert_eq!(entry_reader.guessed_content_type().await.unwrap(), Some(mime_guess::mime::IMAGE_PNG));
+
+    // The peeked bytes should still be returned by subsequent reads.
+    let mut data = Vec::new();
+    entry_reader.read_to_end_checked(&mut data).await.unwrap();
+    assert_eq!(data, PNG_DATA);
+}
+
+#[tokio::test]
+async fn guessed_content_type_fallback() {
+    let reader = ZipFileReader::new(write_archive("style.css", b"body {}").await).await.unwrap();
+    let mut entry_reader = reader.reader_with_entry(0).await.unwrap();
+
+    assert_eq!(entry_reader.guessed_content_type().await.unwrap(), Some(mime_guess::mime::TEXT_CSS));
+
+    let mut data = Vec::new();
+    entry_reader.read_to_end_checked(&mut data).await.unwrap();
+    assert_eq!(data, b"body {}");
+}
+
+#[tokio::test]
+async fn guessed_content_type_weak_magic() {
+    // Short magic numbers are ignored in favour of a recognised extension.
+    let reader = ZipFileReader::new(write_archive("notes.txt", b"BMX is a sport").await).await.unwrap();
+    let mut entry_reader = reader.reader_with_entry(0).await.unwrap();
+    assert_eq!(entry_reader.guessed_content_type().await.unwrap(), Some(mime_guess::mime::TEXT_PLAIN));
+
+    let reader = ZipFileReader::new(write_archive("image", b"BM\x36\x00\x00\x00").await).await.unwrap();
+    let mut entry_reader = reader.reader_with_entry(0).await.unwrap();
+    assert_eq!(entry_reader.guessed_content_type().await.unwrap(), Some(mime_guess::mime::IMAGE_BMP));
+}
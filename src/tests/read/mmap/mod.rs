@@ -0,0 +1,28 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::write::ZipFileWriter;
+use crate::error::ZipError;
+use crate::tokio::read::fs::ZipFileReader;
+use crate::{Compression, ZipEntryBuilder};
+
+#[tokio::test]
+async fn mmap_stored_entries() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    writer.write_entry_whole(ZipEntryBuilder::new("foo.txt".into(), Compression::Stored), b"foo").await.unwrap();
+    writer.write_entry_whole(ZipEntryBuilder::new("empty.txt".into(), Compression::Stored), b"").await.unwrap();
+    #[cfg(feature = "deflate")]
+    writer.write_entry_whole(ZipEntryBuilder::new("bar.txt".into(), Compression::Deflate), b"bar").await.unwrap();
+
+    let path = std::env::temp_dir().join(format!("async_zip_mmap_{}.zip", std::process::id()));
+    tokio::fs::write(&path, writer.close().await.unwrap()).await.unwrap();
+
+    let reader = ZipFileReader::new(&path).await.unwrap();
+    assert_eq!(&*reader.mmap_entry(0).await.unwrap(), b"foo");
+    assert!(reader.mmap_entry(1).await.unwrap().is_empty());
+    #[cfg(feature = "deflate")]
+    assert!(matches!(reader.mmap_entry(2).await, Err(ZipError::FeatureNotSupported(_))));
+    assert!(matches!(reader.mmap_entry(3).await, Err(ZipError::EntryIndexOutOfBounds)));
+
+    tokio::fs::remove_file(&path).await.unwrap();
+}
@@ -1,6 +1,49 @@
 // Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
+pub(crate) mod budget;
+pub(crate) mod buffered;
+pub(crate) mod builder;
+pub(crate) mod cancel;
+pub(crate) mod central_directory;
+pub(crate) mod comment;
 pub(crate) mod compression;
+pub(crate) mod copy;
+pub(crate) mod data_range;
+#[cfg(feature = "deflate")]
+pub(crate) mod decompression;
+pub(crate) mod descriptor;
+#[cfg(feature = "sha2")]
+pub(crate) mod digest;
+pub(crate) mod disks;
+#[cfg(feature = "encoding_rs")]
+pub(crate) mod encoding;
+pub(crate) mod encrypted;
+pub(crate) mod equivalent;
+pub(crate) mod extra;
+pub(crate) mod filename;
+pub(crate) mod filter;
+pub(crate) mod glob;
+pub(crate) mod groups;
+pub(crate) mod head;
+#[cfg(feature = "legacy")]
+pub(crate) mod implode;
+pub(crate) mod lazy;
 pub(crate) mod locator;
+#[cfg(feature = "indexmap")]
+pub(crate) mod map;
+pub(crate) mod meta;
+#[cfg(feature = "mime")]
+pub(crate) mod mime;
+#[cfg(feature = "memmap2")]
+pub(crate) mod mmap;
+pub(crate) mod multi;
+pub(crate) mod prefix;
+pub(crate) mod range;
+pub(crate) mod recovery;
+pub(crate) mod shared;
+#[cfg(feature = "deflate")]
+pub(crate) mod sizes;
+pub(crate) mod summary;
+pub(crate) mod verify;
 pub(crate) mod zip64;
@@ -0,0 +1,62 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::multi::MultiArchiveReader;
+use crate::base::write::ZipFileWriter;
+use crate::error::ZipError;
+use crate::{Compression, ZipEntryBuilder};
+
+use futures_lite::io::{AsyncReadExt, Cursor};
+
+async fn archive(names: &[&str]) -> Vec<u8> {
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    for name in names {
+        let entry = ZipEntryBuilder::new(name.to_string().into(), Compression::Stored);
+        writer.write_entry_whole(entry, name.as_bytes()).await.unwrap();
+    }
+
+    writer.close().await.unwrap()
+}
+
+#[tokio::test]
+async fn for_each_entry() {
+    let sources = vec![archive(&["foo.txt", "bar.txt"]).await, archive(&["baz.txt"]).await];
+    let mut seen = Vec::new();
+
+    MultiArchiveReader::new(sources.into_iter().map(Cursor::new))
+        .for_each_entry(async |archive, reader| {
+            let filename = reader.entry().filename().as_str()?.to_string();
+
+            // Leave the data of the first entry unread, which should be skipped.
+            if filename != "foo.txt" {
+                let mut data = String::new();
+                reader.read_to_string(&mut data).await?;
+                assert_eq!(data, filename);
+            }
+
+            seen.push((archive, filename));
+            Ok(())
+        })
+        .await
+        .unwrap();
+
+    let expected = [(0, "foo.txt"), (0, "bar.txt"), (1, "baz.txt")];
+    assert_eq!(seen, expected.map(|(archive, filename)| (archive, filename.to_string())));
+}
+
+#[tokio::test]
+async fn for_each_entry_error() {
+    let sources = vec![archive(&["foo.txt"]).await, archive(&["bar.txt"]).await];
+    let mut calls = 0;
+
+    let result = MultiArchiveReader::new(sources.into_iter().map(Cursor::new))
+        .for_each_entry(async |_, _| {
+            calls += 1;
+            Err(ZipError::EOFNotReached)
+        })
+        .await;
+
+    assert!(matches!(result, Err(ZipError::EOFNotReached)));
+    assert_eq!(calls, 1);
+}
@@ -0,0 +1,51 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::seek::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::{Compression, ZipEntryBuilder};
+
+use futures_lite::io::Cursor;
+
+const PREFIX: &[u8] = b"#!/bin/sh\nexec unzip \"$0\"\n";
+
+async fn prefixed_archive(mut writer: ZipFileWriter<Vec<u8>>) -> Vec<u8> {
+    writer.write_entry_whole(ZipEntryBuilder::new("foo.txt".into(), Compression::Stored), b"foo").await.unwrap();
+    writer.write_entry_whole(ZipEntryBuilder::new("bar.txt".into(), Compression::Stored), b"bar").await.unwrap();
+
+    let mut data = PREFIX.to_vec();
+    data.extend_from_slice(&writer.close().await.unwrap());
+    data
+}
+
+#[tokio::test]
+async fn read_with_base_offset() {
+    let data = prefixed_archive(ZipFileWriter::new(Vec::new())).await;
+    let mut reader = ZipFileReader::new_with_base_offset(Cursor::new(data), PREFIX.len() as u64).await.unwrap();
+
+    // Offsets remain relative to the start of the archive.
+    assert_eq!(reader.file().entries()[0].header_offset(), 0);
+
+    let mut contents = String::new();
+    reader.reader_with_entry(1).await.unwrap().read_to_string_checked(&mut contents).await.unwrap();
+    assert_eq!(contents, "bar");
+}
+
+#[tokio::test]
+async fn read_with_detected_prefix() {
+    for writer in [ZipFileWriter::new(Vec::new()), ZipFileWriter::new(Vec::new()).force_zip64()] {
+        let data = prefixed_archive(writer).await;
+        let mut reader = ZipFileReader::new_with_prefix(Cursor::new(data)).await.unwrap();
+        assert_eq!(reader.inner_mut().base(), PREFIX.len() as u64);
+
+        let mut contents = String::new();
+        reader.reader_with_entry(0).await.unwrap().read_to_string_checked(&mut contents).await.unwrap();
+        assert_eq!(contents, "foo");
+    }
+
+    // Archives without a prefix are detected as such.
+    let mut writer = ZipFileWriter::new(Vec::new());
+    writer.write_entry_whole(ZipEntryBuilder::new("foo.txt".into(), Compression::Stored), b"foo").await.unwrap();
+    let mut reader = ZipFileReader::new_with_prefix(Cursor::new(writer.close().await.unwrap())).await.unwrap();
+    assert_eq!(reader.inner_mut().base(), 0);
+}
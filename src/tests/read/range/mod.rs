@@ -0,0 +1,148 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::range::{RangeFetcher, ZipFileReader};
+use crate::base::write::ZipFileWriter;
+use crate::error::{Result, ZipError};
+use crate::{Compression, ZipEntryBuilder};
+
+use std::sync::Mutex;
+
+/// A fetcher over an in-memory archive which records each range requested.
+struct Fetcher {
+    data: Vec<u8>,
+    requests: Mutex<Vec<(u64, u64)>>,
+}
+
+impl Fetcher {
+    fn new(data: Vec<u8>) -> Self {
+        Self { data, requests: Mutex::new(Vec::new()) }
+    }
+
+    fn requests(&self) -> Vec<(u64, u64)> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+impl RangeFetcher for Fetcher {
+    async fn fetch(&self, offset: u64, length: u64) -> Result<Vec<u8>> {
+        self.requests.lock().unwrap().push((offset, length));
+
+        let start = std::cmp::min(offset as usize, self.data.len());
+        let end = std::cmp::min((offset + length) as usize, self.data.len());
+        Ok(self.data[start..end].to_vec())
+    }
+}
+
+async fn write_archive() -> Vec<u8> {
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    for (filename, data) in [("foo.txt", "foo"), ("bar/baz.txt", "bar baz"), ("empty.txt", "")] {
+        let entry = ZipEntryBuilder::new(filename.into(), Compression::Stored);
+        writer.write_entry_whole(entry, data.as_bytes()).await.unwrap();
+    }
+
+    writer.close().await.unwrap()
+}
+
+#[tokio::test]
+async fn range_read() {
+    let archive = write_archive().await;
+    let size = archive.len() as u64;
+    let reader = ZipFileReader::new(Fetcher::new(archive), size).await.unwrap();
+
+    // The whole archive fits within the tail, so only a single range should've been fetched.
+    assert_eq!(reader.inner().requests(), [(0, size)]);
+    assert_eq!(reader.file().entries().len(), 3);
+
+    let mut data = String::new();
+    reader.reader_with_entry(1).await.unwrap().read_to_string_checked(&mut data).await.unwrap();
+    assert_eq!(data, "bar baz");
+
+    // Reading an entry should fetch only its local file header and then its data.
+    let entry = &reader.file().entries()[1];
+    let data_offset = entry.header_offset() + entry.header_size();
+    assert_eq!(reader.inner().requests()[1..], [(entry.header_offset(), 30), (data_offset, 7)]);
+}
+
+#[tokio::test]
+async fn range_read_chunked() {
+    let archive = write_archive().await;
+    let size = archive.len() as u64;
+    let mut reader = ZipFileReader::new(Fetcher::new(archive), size).await.unwrap();
+    reader.set_chunk_size(3);
+
+    let mut data = String::new();
+    reader.reader_with_entry(1).await.unwrap().read_to_string_checked(&mut data).await.unwrap();
+    assert_eq!(data, "bar baz");
+
+    // The entry's data should've been fetched in bounded chunks, rather than as a single range.
+    let entry = &reader.file().entries()[1];
+    let data_offset = entry.header_offset() + entry.header_size();
+    let expected = [(entry.header_offset(), 30), (data_offset, 3), (data_offset + 3, 3), (data_offset + 6, 1)];
+    assert_eq!(reader.inner().requests()[1..], expected);
+}
+
+#[tokio::test]
+async fn range_read_spawned() {
+    let archive = write_archive().await;
+    let size = archive.len() as u64;
+    let reader = std::sync::Arc::new(ZipFileReader::new(Fetcher::new(archive), size).await.unwrap());
+
+    // Reading an entry should be possible from a spawned task, which requires the fetcher's futures to be Send.
+    let data = tokio::spawn(async move {
+        let mut data = String::new();
+        reader.reader_with_entry(0).await.unwrap().read_to_string_checked(&mut data).await.unwrap();
+        data
+    });
+    assert_eq!(data.await.unwrap(), "foo");
+}
+
+#[tokio::test]
+async fn range_read_from_tail() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    for index in 0..100 {
+        let entry = ZipEntryBuilder::new(format!("file-{index}.txt").into(), Compression::Stored);
+        writer.write_entry_whole(entry, format!("{index}").as_bytes()).await.unwrap();
+    }
+    let archive = writer.close().await.unwrap();
+    let size = archive.len() as u64;
+
+    // Include enough to locate the EOCDR, but not the start of the central directory.
+    let tail_length = 4096;
+    let tail = archive[archive.len() - tail_length..].to_vec();
+    let tail_offset = size - tail_length as u64;
+    let reader = ZipFileReader::from_tail(Fetcher::new(archive), tail, tail_offset).await.unwrap();
+
+    let cd_offset = reader.inner().requests()[0].0;
+    assert!(cd_offset < tail_offset);
+    assert_eq!(reader.inner().requests(), [(cd_offset, tail_offset - cd_offset)]);
+    assert_eq!(reader.file().entries().len(), 100);
+
+    for index in [0, 42, 99] {
+        let mut data = String::new();
+        reader.reader_with_entry(index).await.unwrap().read_to_string_checked(&mut data).await.unwrap();
+        assert_eq!(data, format!("{index}"));
+    }
+}
+
+#[tokio::test]
+async fn range_read_from_short_tail() {
+    let archive = write_archive().await;
+    let size = archive.len() as u64;
+    let tail = archive[archive.len() - 10..].to_vec();
+    let reader = ZipFileReader::from_tail(Fetcher::new(archive), tail, size - 10).await.unwrap();
+
+    // The tail didn't contain the EOCDR, so the rest of the (small) archive should've been fetched.
+    assert_eq!(reader.inner().requests(), [(0, size - 10)]);
+    assert_eq!(reader.file().entries().len(), 3);
+}
+
+#[tokio::test]
+async fn range_read_truncated() {
+    let archive = write_archive().await;
+    let size = archive.len() as u64;
+    let result = ZipFileReader::new(Fetcher::new(archive), size + 10).await;
+
+    assert!(matches!(result, Err(ZipError::UpstreamReadError(_))));
+}
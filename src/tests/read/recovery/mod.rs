@@ -0,0 +1,68 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::mem::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::{Compression, ZipEntryBuilder};
+
+use futures_lite::io::AsyncWriteExt;
+
+async fn archive() -> Vec<u8> {
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    let entry = ZipEntryBuilder::new("whole.txt".into(), Compression::Stored);
+    writer.write_entry_whole(entry, b"foo bar").await.unwrap();
+
+    let entry = ZipEntryBuilder::new("stream.txt".into(), Compression::Stored);
+    let mut entry_writer = writer.write_entry_stream(entry).await.unwrap();
+    entry_writer.write_all(b"PK\x03\x04 baz").await.unwrap();
+    entry_writer.close().await.unwrap();
+
+    writer.close().await.unwrap()
+}
+
+async fn central_directory_offset(data: &[u8]) -> usize {
+    let summary = crate::base::read::read_summary(futures_lite::io::Cursor::new(data)).await.unwrap();
+    summary.central_directory_offset() as usize
+}
+
+#[tokio::test]
+async fn recovery_unused_when_intact() {
+    let reader = ZipFileReader::new_with_recovery(archive().await).await.unwrap();
+
+    assert!(!reader.file().recovered());
+    assert_eq!(reader.file().entries().len(), 2);
+}
+
+#[tokio::test]
+async fn recovery_without_central_directory() {
+    let mut data = archive().await;
+    let cd_offset =
+        crate::base::read::read_summary(futures_lite::io::Cursor::new(&data)).await.unwrap().central_directory_offset();
+    data.truncate(cd_offset as usize);
+
+    assert!(ZipFileReader::new(data.clone()).await.is_err());
+    let reader = ZipFileReader::new_with_recovery(data).await.unwrap();
+
+    assert!(reader.file().recovered());
+    assert_eq!(reader.file().entries().len(), 2);
+    assert_eq!(reader.file().entries()[0].filename().as_str().unwrap(), "whole.txt");
+    assert_eq!(reader.file().entries()[1].filename().as_str().unwrap(), "stream.txt");
+
+    for (index, expected) in [(0, &b"foo bar"[..]), (1, &b"PK\x03\x04 baz"[..])] {
+        let mut buffer = Vec::new();
+        reader.reader_with_entry(index).await.unwrap().read_to_end_checked(&mut buffer).await.unwrap();
+        assert_eq!(buffer, expected);
+    }
+}
+
+#[tokio::test]
+async fn recovery_skips_truncated_entry() {
+    // Truncate part way through the second entry's data descriptor.
+    let mut data = archive().await;
+    data.truncate(central_directory_offset(&data).await - 4);
+
+    let reader = ZipFileReader::new_with_recovery(data).await.unwrap();
+    assert!(reader.file().recovered());
+    assert_eq!(reader.file().entries().len(), 1);
+}
@@ -0,0 +1,50 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::mem::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::{Compression, ZipEntryBuilder};
+
+use std::sync::Arc;
+
+#[tokio::test]
+async fn from_shared() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    let entry = ZipEntryBuilder::new("foo.txt".into(), Compression::Stored);
+    writer.write_entry_whole(entry, b"foo bar").await.unwrap();
+
+    let data: Arc<[u8]> = writer.close().await.unwrap().into();
+    let reader = ZipFileReader::from_shared(data.clone()).await.unwrap();
+
+    // The reader should hold onto the shared bytes rather than a copy.
+    assert_eq!(reader.data().as_ptr(), data.as_ptr());
+    assert_eq!(Arc::strong_count(&data), 2);
+
+    let mut buffer = String::new();
+    reader.reader_with_entry(0).await.unwrap().read_to_string_checked(&mut buffer).await.unwrap();
+    assert_eq!(buffer, "foo bar");
+
+    drop(reader);
+    assert_eq!(Arc::strong_count(&data), 1);
+}
+
+#[cfg(feature = "bytes")]
+#[tokio::test]
+async fn from_bytes() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    let entry = ZipEntryBuilder::new("foo.txt".into(), Compression::Stored);
+    writer.write_entry_whole(entry, b"foo bar").await.unwrap();
+
+    // Slice the archive out of a larger buffer, as a request body might be.
+    let mut body = b"header".to_vec();
+    body.extend(writer.close().await.unwrap());
+    let data = bytes::Bytes::from(body).slice(6..);
+    let reader = ZipFileReader::from_bytes(data.clone()).await.unwrap();
+
+    // The reader should hold onto the buffer rather than a copy.
+    assert_eq!(reader.data().as_ptr(), data.as_ptr());
+
+    let mut buffer = String::new();
+    reader.reader_with_entry(0).await.unwrap().read_to_string_checked(&mut buffer).await.unwrap();
+    assert_eq!(buffer, "foo bar");
+}
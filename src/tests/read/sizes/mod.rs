@@ -0,0 +1,50 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::seek::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::{Compression, ZipEntryBuilder};
+
+use futures_lite::io::{AsyncReadExt, Cursor};
+
+/// Writes a single deflate compressed entry, and then zeroes its sizes within both of its headers.
+async fn zero_sized_archive(data: &[u8]) -> Vec<u8> {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    writer.write_entry_whole(ZipEntryBuilder::new("foo.txt".into(), Compression::Deflate), data).await.unwrap();
+    let mut archive = writer.close().await.unwrap();
+
+    let eocdr_offset = archive.len() - 22;
+    let cd_offset = u32::from_le_bytes(archive[eocdr_offset + 16..eocdr_offset + 20].try_into().unwrap()) as usize;
+
+    archive[18..26].fill(0);
+    archive[cd_offset + 20..cd_offset + 28].fill(0);
+    archive
+}
+
+#[tokio::test]
+async fn read_entry_to_vec_zero_sizes() {
+    let data = b"foo bar baz ".repeat(1000);
+    let mut reader = ZipFileReader::new(Cursor::new(zero_sized_archive(&data).await)).await.unwrap();
+
+    assert_eq!(reader.file().entries()[0].uncompressed_size(), 0);
+    assert_eq!(reader.read_entry_to_vec(0).await.unwrap(), data);
+}
+
+#[tokio::test]
+async fn bytes_read() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    writer
+        .write_entry_whole(ZipEntryBuilder::new("foo.txt".into(), Compression::Deflate), &[0xAB; 1000])
+        .await
+        .unwrap();
+    let mut reader = ZipFileReader::new(Cursor::new(writer.close().await.unwrap())).await.unwrap();
+
+    let mut entry_reader = reader.reader_without_entry(0).await.unwrap();
+    assert_eq!(entry_reader.bytes_read(), 0);
+
+    entry_reader.read_exact(&mut [0; 10]).await.unwrap();
+    assert_eq!(entry_reader.bytes_read(), 10);
+
+    entry_reader.read_to_end(&mut Vec::new()).await.unwrap();
+    assert_eq!(entry_reader.bytes_read(), 1000);
+}
@@ -0,0 +1,97 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::read_summary;
+use crate::base::write::ZipFileWriter;
+use crate::{Compression, ZipEntryBuilder};
+
+use futures_lite::io::Cursor;
+
+#[tokio::test]
+async fn read_summary_matches_file() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    writer.comment(String::from("foo bar"));
+
+    for index in 0..3 {
+        let entry = ZipEntryBuilder::new(format!("{index}.txt").into(), Compression::Stored);
+        writer.write_entry_whole(entry, b"foo").await.unwrap();
+    }
+
+    let data = writer.close().await.unwrap();
+    let summary = read_summary(Cursor::new(&data)).await.unwrap();
+
+    assert_eq!(summary.num_entries(), 3);
+    assert_eq!(summary.comment().as_str().unwrap(), "foo bar");
+    assert!(!summary.zip64());
+
+    // The central directory is immediately followed by the EOCDR and its comment.
+    let cd_end = summary.central_directory_offset() + summary.central_directory_size();
+    assert_eq!(cd_end as usize, data.len() - 22 - "foo bar".len());
+}
+
+#[tokio::test]
+async fn read_summary_zip64() {
+    let data = include_bytes!("../zip64/zip64.zip");
+    let summary = read_summary(Cursor::new(data)).await.unwrap();
+
+    assert_eq!(summary.num_entries(), 1);
+    assert!(summary.zip64());
+}
+
+#[tokio::test]
+async fn stream_finish_matches_summary() {
+    use crate::base::read::stream::ZipFileReader;
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+    writer.comment(String::from("foo bar"));
+
+    for index in 0..3 {
+        let entry = ZipEntryBuilder::new(format!("{index}.txt").into(), Compression::Stored);
+        writer.write_entry_whole(entry, b"foo").await.unwrap();
+    }
+
+    let data = writer.close().await.unwrap();
+    let expected = read_summary(Cursor::new(&data)).await.unwrap();
+
+    // Read only the first entry, leaving the remaining entries to be skipped.
+    let reader = ZipFileReader::new(Cursor::new(&data));
+    let reader = reader.next_with_entry().await.unwrap().unwrap().skip().await.unwrap();
+    let summary = reader.finish().await.unwrap();
+
+    assert_eq!(summary.num_entries(), expected.num_entries());
+    assert_eq!(summary.central_directory_offset(), expected.central_directory_offset());
+    assert_eq!(summary.central_directory_size(), expected.central_directory_size());
+    assert_eq!(summary.comment().as_bytes(), b"foo bar");
+    assert!(!summary.zip64());
+}
+
+#[tokio::test]
+async fn stream_finish_zip64() {
+    use crate::base::read::stream::ZipFileReader;
+
+    let data = include_bytes!("../zip64/zip64.zip");
+    let summary = ZipFileReader::new(Cursor::new(data)).finish().await.unwrap();
+
+    assert_eq!(summary.num_entries(), 1);
+    assert!(summary.zip64());
+}
+
+#[tokio::test]
+async fn stream_finish_empty() {
+    use crate::base::read::stream::ZipFileReader;
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+    writer.comment(String::from("foo bar"));
+    let data = writer.close().await.unwrap();
+
+    let summary = ZipFileReader::new(Cursor::new(&data)).finish().await.unwrap();
+    assert_eq!(summary.num_entries(), 0);
+    assert_eq!(summary.comment().as_bytes(), b"foo bar");
+
+    assert!(ZipFileReader::new(Cursor::new(&data)).next_with_entry().await.unwrap().is_none());
+
+    let data = ZipFileWriter::new(Vec::new()).force_zip64().close().await.unwrap();
+    let summary = ZipFileReader::new(Cursor::new(&data)).finish().await.unwrap();
+    assert_eq!(summary.num_entries(), 0);
+    assert!(summary.zip64());
+}
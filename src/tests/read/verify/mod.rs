@@ -0,0 +1,33 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::seek::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::error::ZipError;
+use crate::{Compression, ZipEntryBuilder};
+
+use futures_lite::io::Cursor;
+
+#[tokio::test]
+async fn verify_entry() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    let entry = ZipEntryBuilder::new("foo.txt".into(), Compression::Stored);
+    writer.write_entry_whole(entry, b"foo").await.unwrap();
+    let entry = ZipEntryBuilder::new("bar.bin".into(), Compression::Stored);
+    writer.write_entry_whole(entry, &[0xAB; 100_000]).await.unwrap();
+
+    let mut data = writer.close().await.unwrap();
+
+    let mut reader = ZipFileReader::new(Cursor::new(data.clone())).await.unwrap();
+    reader.verify_entry(0).await.unwrap();
+    reader.verify_entry(1).await.unwrap();
+    assert!(matches!(reader.verify_entry(2).await, Err(ZipError::EntryIndexOutOfBounds)));
+
+    // Corrupt the first byte of the first entry's data, directly after its local file header.
+    data[30 + "foo.txt".len()] ^= 0xFF;
+
+    let mut reader = ZipFileReader::new(Cursor::new(data)).await.unwrap();
+    assert!(matches!(reader.verify_entry(0).await, Err(ZipError::CRC32CheckError)));
+    reader.verify_entry(1).await.unwrap();
+}
@@ -31,6 +31,20 @@ async fn test_read_zip64_archive_mem() {
     assert_eq!(read_data, ZIP64_ZIP_CONTENTS);
 }
 
+/// Tests that the per-entry ZIP64 flag is independent of the archive's ZIP64 EOCDR.
+/// The central directory record in this archive has no zip64 extended field (only its local file header does).
+#[tokio::test]
+async fn test_read_zip64_archive_entry_flag() {
+    use crate::base::read::mem::ZipFileReader;
+    init_logger();
+
+    let data = include_bytes!("zip64.zip").to_vec();
+    let reader = ZipFileReader::new(data).await.unwrap();
+
+    assert!(reader.file().zip64());
+    assert!(!reader.file().entries()[0].is_zip64());
+}
+
 /// Like test_read_zip64_archive_mem() but for the streaming version
 #[tokio::test]
 async fn test_read_zip64_archive_stream() {
@@ -53,6 +67,37 @@ async fn test_read_zip64_archive_stream() {
     assert_eq!(read_data, ZIP64_ZIP_CONTENTS);
 }
 
+/// Tests that a zip64 extensible data sector within the ZIP64 EOCDR is retained, and doesn't desync the stream reader.
+#[tokio::test]
+async fn test_read_zip64_extensible_data() {
+    use crate::base::read::{mem, stream};
+    use crate::base::write::ZipFileWriter;
+    use crate::spec::consts::ZIP64_EOCDR_SIGNATURE;
+    use crate::{Compression, ZipEntryBuilder};
+    init_logger();
+
+    let mut writer = ZipFileWriter::new(Vec::new()).force_zip64();
+    let entry = ZipEntryBuilder::new("foo.txt".into(), Compression::Stored);
+    writer.write_entry_whole(entry, b"foo").await.unwrap();
+    let mut data = writer.close().await.unwrap();
+
+    // Insert a sector directly after the fixed fields of the ZIP64 EOCDR and account for it within the record's size.
+    let extensible_data = b"\x01\x00\x04\x00data";
+    let signature = ZIP64_EOCDR_SIGNATURE.to_le_bytes();
+    let position = data.windows(4).position(|window| window == signature).unwrap();
+    let size = u64::from_le_bytes(data[position + 4..position + 12].try_into().unwrap());
+    data[position + 4..position + 12].copy_from_slice(&(size + extensible_data.len() as u64).to_le_bytes());
+    data.splice(position + 56..position + 56, extensible_data.iter().copied());
+
+    let reader = mem::ZipFileReader::new(data.clone()).await.unwrap();
+    assert_eq!(reader.file().zip64_extensible_data(), extensible_data);
+    assert_eq!(reader.file().entries().len(), 1);
+
+    let summary = stream::ZipFileReader::new(data.as_slice()).finish().await.unwrap();
+    assert_eq!(summary.num_entries(), 1);
+    assert!(summary.zip64());
+}
+
 /// Generate an example file only if it doesn't exist already.
 /// The file is placed adjacent to this rs file.
 #[cfg(feature = "tokio")]
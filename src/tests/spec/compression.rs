@@ -0,0 +1,33 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::error::ZipError;
+use crate::Compression;
+
+#[test]
+fn compression_from_name() {
+    assert_eq!(Compression::from_name("stored").unwrap(), Compression::Stored);
+    assert_eq!(Compression::from_name("STORED").unwrap(), Compression::Stored);
+    assert_eq!("Stored".parse::<Compression>().unwrap(), Compression::Stored);
+
+    #[cfg(feature = "deflate")]
+    assert_eq!(Compression::from_name("Deflate").unwrap(), Compression::Deflate);
+
+    match Compression::from_name("foo") {
+        Err(ZipError::CompressionNameNotSupported(name, supported)) => {
+            assert_eq!(name, "foo");
+            assert!(supported.starts_with("stored"));
+        }
+        result => panic!("unexpected result: {result:?}"),
+    }
+}
+
+#[test]
+fn compression_display_round_trip() {
+    assert_eq!(Compression::Stored.to_string(), "stored");
+
+    #[cfg(feature = "zstd")]
+    assert_eq!(Compression::Zstd.to_string(), "zstd");
+    #[cfg(feature = "bzip2")]
+    assert_eq!(Compression::from_name(&Compression::Bz.to_string()).unwrap(), Compression::Bz);
+}
@@ -4,7 +4,9 @@
 #[cfg(feature = "chrono")]
 use chrono::{TimeZone, Utc};
 
-use crate::ZipDateTimeBuilder;
+use crate::{Compression, ZipDateTime, ZipDateTimeBuilder, ZipEntryBuilder};
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 #[test]
 #[cfg(feature = "chrono")]
@@ -42,3 +44,104 @@ fn date_conversion_test() {
     assert_eq!(minute, built.minute());
     assert_eq!(second, built.second());
 }
+
+#[test]
+fn date_conversion_test_system_time() {
+    // 2022-10-23 16:55:02 UTC
+    let time = UNIX_EPOCH + Duration::from_secs(1666544102);
+    let built = ZipDateTime::from_system_time(time);
+
+    assert_eq!(built.year(), 2022);
+    assert_eq!(built.month(), 10);
+    assert_eq!(built.day(), 23);
+    assert_eq!(built.hour(), 16);
+    assert_eq!(built.minute(), 55);
+    assert_eq!(built.second(), 2);
+
+    // 2000-02-29 23:59:59 UTC, which rounds down to the nearest even second.
+    let built = ZipDateTime::from_system_time(UNIX_EPOCH + Duration::from_secs(951868799));
+    assert_eq!((built.year(), built.month(), built.day()), (2000, 2, 29));
+    assert_eq!((built.hour(), built.minute(), built.second()), (23, 59, 58));
+}
+
+#[test]
+fn date_conversion_test_system_time_clamped() {
+    let minimum = ZipDateTimeBuilder::new().year(1980).month(1).day(1).build();
+    assert_eq!(ZipDateTime::from_system_time(UNIX_EPOCH), minimum);
+    assert_eq!(ZipDateTime::from_system_time(UNIX_EPOCH - Duration::from_secs(1)), minimum);
+
+    // 2200-01-01 00:00:00 UTC
+    let built = ZipDateTime::from_system_time(UNIX_EPOCH + Duration::from_secs(7258118400));
+    assert_eq!((built.year(), built.month(), built.day()), (2107, 12, 31));
+    assert_eq!((built.hour(), built.minute(), built.second()), (23, 59, 58));
+}
+
+#[test]
+fn date_conversion_test_entry_builder() {
+    let time = SystemTime::now();
+    let entry = ZipEntryBuilder::new("foo.txt".into(), Compression::Stored).last_modified_system_time(time).build();
+    assert_eq!(*entry.last_modification_date(), ZipDateTime::from(time));
+}
+
+#[test]
+#[cfg(feature = "chrono")]
+fn date_conversion_test_entry_builder_chrono() {
+    let original_dt = Utc.with_ymd_and_hms(2024, 3, 2, 23, 59, 58).unwrap();
+    let entry = ZipEntryBuilder::new("foo.txt".into(), Compression::Stored).last_modified_chrono(original_dt).build();
+
+    let date = entry.last_modification_date();
+    assert_eq!((date.year(), date.month(), date.day()), (2024, 3, 2));
+    assert_eq!((date.hour(), date.minute(), date.second()), (23, 59, 58));
+    assert_eq!(date.as_chrono().single(), Some(original_dt));
+}
+
+#[test]
+fn date_conversion_test_hours() {
+    for hour in 0..24 {
+        let built = ZipDateTimeBuilder::new().year(2024).month(3).day(2).hour(hour).minute(59).second(58).build();
+
+        assert_eq!(built.hour(), hour);
+        assert_eq!(built.minute(), 59);
+        assert_eq!(built.second(), 58);
+    }
+}
+
+#[tokio::test]
+async fn date_conversion_test_write_read() {
+    use crate::base::read::mem::ZipFileReader;
+    use crate::base::write::ZipFileWriter;
+    use futures_lite::io::AsyncWriteExt;
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    for hour in 0..24 {
+        let date = ZipDateTimeBuilder::new().year(2024).month(3).day(2).hour(hour).minute(30).build();
+        let entry =
+            ZipEntryBuilder::new(format!("{hour}.txt").into(), Compression::Stored).last_modification_date(date);
+
+        if hour % 2 == 0 {
+            writer.write_entry_whole(entry, b"foo").await.unwrap();
+        } else {
+            let mut entry_writer = writer.write_entry_stream(entry).await.unwrap();
+            entry_writer.write_all(b"foo").await.unwrap();
+            entry_writer.close().await.unwrap();
+        }
+    }
+
+    let reader = ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+    for (hour, entry) in reader.file().entries().iter().enumerate() {
+        assert_eq!(entry.last_modification_date().hour(), hour as u32);
+        assert_eq!(entry.last_modification_date().minute(), 30);
+    }
+}
+
+#[test]
+fn date_conversion_test_unix_timestamp() {
+    // 2000-02-29 23:59:58 UTC
+    let built = ZipDateTime::from_system_time(UNIX_EPOCH + Duration::from_secs(951868798));
+    assert_eq!(built.unix_timestamp(), Some(951868798));
+
+    let minimum = ZipDateTimeBuilder::new().year(1980).month(1).day(1).build();
+    assert_eq!(minimum.unix_timestamp(), Some(315532800));
+    assert_eq!(ZipDateTime::default().unix_timestamp(), None);
+}
@@ -0,0 +1,100 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::mem::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::{Compression, ZipEntryBuilder};
+
+use futures_lite::io::AsyncWrite;
+use std::io::{Error, ErrorKind};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// A writer which rejects any write that would exceed its capacity, rather than accepting part of it.
+struct LimitedWriter {
+    data: Vec<u8>,
+    capacity: usize,
+}
+
+impl AsyncWrite for LimitedWriter {
+    fn poll_write(self: Pin<&mut Self>, _: &mut Context<'_>, buf: &[u8]) -> Poll<Result<usize, Error>> {
+        let this = self.get_mut();
+
+        if this.data.len() + buf.len() > this.capacity {
+            return Poll::Ready(Err(Error::from(ErrorKind::WriteZero)));
+        }
+
+        this.data.extend_from_slice(buf);
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Result<(), Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Result<(), Error>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// Writes two entries and closes the writer, returning the offset at which the closing records start and whether
+/// closing succeeded.
+async fn write_archive(writer: &mut LimitedWriter, atomic: bool) -> (u64, bool) {
+    let mut writer = ZipFileWriter::new(writer).atomic_close(atomic);
+    writer.write_entry_whole(ZipEntryBuilder::new("foo.txt".into(), Compression::Stored), b"foo").await.unwrap();
+    writer.write_entry_whole(ZipEntryBuilder::new("bar.txt".into(), Compression::Stored), b"bar").await.unwrap();
+
+    let data_end = writer.writer.offset();
+    (data_end, writer.close().await.is_ok())
+}
+
+#[tokio::test]
+async fn atomic_close_matches_regular_close() {
+    let mut regular = LimitedWriter { data: Vec::new(), capacity: usize::MAX };
+    let mut atomic = LimitedWriter { data: Vec::new(), capacity: usize::MAX };
+
+    assert!(write_archive(&mut regular, false).await.1);
+    assert!(write_archive(&mut atomic, true).await.1);
+    assert_eq!(regular.data, atomic.data);
+}
+
+#[tokio::test]
+async fn atomic_close_failure_leaves_data_intact() {
+    // Enough capacity for the entries and the first central directory header, but not the remaining records.
+    let mut regular = LimitedWriter { data: Vec::new(), capacity: 150 };
+    let (data_end, closed) = write_archive(&mut regular, false).await;
+    assert!(!closed);
+    assert!(regular.data.len() as u64 > data_end);
+
+    let mut atomic = LimitedWriter { data: Vec::new(), capacity: 150 };
+    let (data_end, closed) = write_archive(&mut atomic, true).await;
+    assert!(!closed);
+    assert_eq!(atomic.data.len() as u64, data_end);
+
+    let reader = ZipFileReader::new_with_recovery(atomic.data).await.unwrap();
+    assert!(reader.file().recovered());
+    assert_eq!(reader.file().entries().len(), 2);
+}
+
+#[tokio::test]
+async fn try_close_retry() {
+    let mut limited = LimitedWriter { data: Vec::new(), capacity: 150 };
+    let mut writer = ZipFileWriter::new(&mut limited);
+    writer.comment("foo".into());
+    writer.write_entry_whole(ZipEntryBuilder::new("foo.txt".into(), Compression::Stored), b"foo").await.unwrap();
+    writer.write_entry_whole(ZipEntryBuilder::new("bar.txt".into(), Compression::Stored), b"bar").await.unwrap();
+    let data_end = writer.writer.offset();
+
+    let Err((_, mut writer)) = writer.try_close().await else {
+        panic!("closing should fail whilst the writer is at capacity");
+    };
+    assert_eq!(writer.writer.offset(), data_end);
+
+    writer.inner_mut().capacity = usize::MAX;
+    assert!(writer.try_close().await.is_ok());
+
+    let reader = ZipFileReader::new(limited.data).await.unwrap();
+    assert!(!reader.file().recovered());
+    assert_eq!(reader.file().entries().len(), 2);
+    assert_eq!(reader.file().comment().as_str().unwrap(), "foo");
+}
@@ -0,0 +1,258 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::mem::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::{Compression, ZipEntryBuilder};
+
+#[tokio::test]
+async fn text_bit_explicit() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    let entry = ZipEntryBuilder::new("text.txt".into(), Compression::Stored).text(true);
+    writer.write_entry_whole(entry, &[0, 1, 2]).await.unwrap();
+    let entry = ZipEntryBuilder::new("binary.bin".into(), Compression::Stored).text(true).text(false);
+    writer.write_entry_whole(entry, b"foo bar").await.unwrap();
+
+    let reader = ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+    assert!(reader.file().entries()[0].text());
+    assert!(!reader.file().entries()[1].text());
+}
+
+#[tokio::test]
+async fn text_bit_auto_detection() {
+    let mut writer = ZipFileWriter::new(Vec::new()).auto_text_detection();
+
+    let entry = ZipEntryBuilder::new("text.txt".into(), Compression::Stored);
+    writer.write_entry_whole(entry, b"foo\r\nbar\tbaz\n").await.unwrap();
+    let entry = ZipEntryBuilder::new("binary.bin".into(), Compression::Stored);
+    writer.write_entry_whole(entry, b"foo\0bar").await.unwrap();
+    let entry = ZipEntryBuilder::new("empty".into(), Compression::Stored);
+    writer.write_entry_whole(entry, &[]).await.unwrap();
+    let entry = ZipEntryBuilder::new("controls".into(), Compression::Stored);
+    writer.write_entry_whole(entry, &[7, 8, 11, 12, 26, 27]).await.unwrap();
+
+    // Entries explicitly marked either way aren't overridden.
+    let entry = ZipEntryBuilder::new("explicit.bin".into(), Compression::Stored).text(false);
+    writer.write_entry_whole(entry, b"foo bar").await.unwrap();
+    let entry = ZipEntryBuilder::new("explicit.txt".into(), Compression::Stored).text(true);
+    writer.write_entry_whole(entry, b"foo\0bar").await.unwrap();
+
+    let reader = ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+    let text: Vec<_> = reader.file().entries().iter().map(|entry| entry.text()).collect();
+    assert_eq!(text, [true, false, false, false, false, true]);
+}
+
+#[cfg(feature = "crc32c")]
+#[tokio::test]
+async fn crc32c_extra_field() {
+    use futures_lite::io::AsyncWriteExt;
+
+    let mut writer = ZipFileWriter::new(Vec::new()).crc32c();
+
+    let entry = ZipEntryBuilder::new("whole.txt".into(), Compression::Stored);
+    writer.write_entry_whole(entry, b"foo bar").await.unwrap();
+    let entry = ZipEntryBuilder::new("stream.txt".into(), Compression::Stored);
+    let mut entry_writer = writer.write_entry_stream(entry).await.unwrap();
+    entry_writer.write_all(b"foo ").await.unwrap();
+    entry_writer.write_all(b"bar").await.unwrap();
+    entry_writer.close().await.unwrap();
+
+    let data = writer.close().await.unwrap();
+    let reader = ZipFileReader::new(data.clone()).await.unwrap();
+
+    for index in 0..2 {
+        assert_eq!(reader.file().entries()[index].crc32c(), Some(crc32c::crc32c(b"foo bar")));
+
+        let mut buffer = Vec::new();
+        let mut entry_reader = reader.reader_with_entry(index).await.unwrap();
+        entry_reader.read_to_end_checked_crc32c(&mut buffer).await.unwrap();
+        assert_eq!(buffer, b"foo bar");
+    }
+}
+
+#[tokio::test]
+async fn metadata_extra_field() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    let entry = ZipEntryBuilder::new("foo.txt".into(), Compression::Stored)
+        .metadata("author", "Harry")
+        .metadata("revision", "1")
+        .metadata("revision", "2");
+    writer.write_entry_whole(entry, b"foo").await.unwrap();
+    let entry = ZipEntryBuilder::new("bar.txt".into(), Compression::Stored);
+    writer.write_entry_whole(entry, b"bar").await.unwrap();
+
+    let reader = ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+    let metadata = reader.file().entries()[0].metadata();
+    assert_eq!(metadata.len(), 2);
+    assert_eq!(metadata["author"], "Harry");
+    assert_eq!(metadata["revision"], "2");
+    assert!(reader.file().entries()[1].metadata().is_empty());
+}
+
+#[tokio::test]
+async fn metadata_extra_field_too_large() {
+    use crate::error::ZipError;
+
+    let value = "a".repeat(u16::MAX as usize);
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    let entry = ZipEntryBuilder::new("foo.txt".into(), Compression::Stored).metadata("foo", &value);
+    let result = writer.write_entry_whole(entry, b"foo").await;
+    assert!(matches!(result, Err(ZipError::ExtraFieldTooLarge)));
+
+    let entry = ZipEntryBuilder::new("foo.txt".into(), Compression::Stored).metadata("foo", &value);
+    let result = writer.write_entry_stream(entry).await;
+    assert!(matches!(result, Err(ZipError::ExtraFieldTooLarge)));
+}
+
+#[tokio::test]
+async fn rewrite_stored_entry() {
+    use futures_lite::io::AsyncWriteExt;
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+    let entry = ZipEntryBuilder::new("foo.txt".into(), Compression::Stored)
+        .comment("bar".into())
+        .internal_file_attribute(1)
+        .external_file_attribute(0o100644 << 16)
+        .metadata("foo", "bar");
+    let mut entry_writer = writer.write_entry_stream(entry).await.unwrap();
+    entry_writer.write_all(b"foo bar").await.unwrap();
+    entry_writer.close().await.unwrap();
+
+    let reader = ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+    let stored = &reader.file().entries()[0];
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+    writer.write_entry_whole(stored.to_entry(), b"foo bar").await.unwrap();
+    let rewritten = ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+    let entry = &rewritten.file().entries()[0];
+
+    assert_eq!(entry.filename().as_str().unwrap(), "foo.txt");
+    assert_eq!(entry.comment().as_str().unwrap(), "bar");
+    assert_eq!(entry.internal_file_attribute(), 1);
+    assert_eq!(entry.external_file_attribute(), 0o100644 << 16);
+    assert_eq!(entry.last_modification_date(), stored.last_modification_date());
+    assert_eq!(entry.metadata()["foo"], "bar");
+    assert!(!entry.is_zip64());
+}
+
+#[tokio::test]
+async fn dos_attributes() {
+    use crate::{AttributeCompatibility, DosAttributes};
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    let attributes = DosAttributes::READ_ONLY | DosAttributes::HIDDEN;
+    let entry = ZipEntryBuilder::new("foo.txt".into(), Compression::Stored).dos_attributes(attributes);
+    writer.write_entry_whole(entry, b"foo").await.unwrap();
+
+    let reader = ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+    let entry = &reader.file().entries()[0];
+
+    assert_eq!(entry.attribute_compatibility(), AttributeCompatibility::Dos);
+    assert_eq!(entry.dos_attributes(), attributes);
+    assert!(!entry.dos_attributes().contains(DosAttributes::SYSTEM));
+    assert_eq!(entry.unix_permissions(), None);
+}
+
+#[tokio::test]
+async fn version_needed_to_extract() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    let file = ZipEntryBuilder::new("foo.txt".into(), Compression::Stored).build();
+    let dir = ZipEntryBuilder::new("bar/".into(), Compression::Stored).build();
+    assert_eq!(file.version_needed_to_extract(), 10);
+    assert_eq!(dir.version_needed_to_extract(), 20);
+
+    writer.write_entry_whole(file, b"foo").await.unwrap();
+    writer.write_entry_whole(dir, &[]).await.unwrap();
+
+    let reader = ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+    assert_eq!(reader.file().entries()[0].version_needed_to_extract(), 10);
+    assert_eq!(reader.file().entries()[1].version_needed_to_extract(), 20);
+}
+
+#[tokio::test]
+async fn version_needed_override() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    let entry = ZipEntryBuilder::new("foo.txt".into(), Compression::Stored).version_needed(20);
+    assert_eq!(entry.clone().build().version_needed_to_extract(), 20);
+    writer.write_entry_whole(entry, b"foo").await.unwrap();
+
+    let entry = ZipEntryBuilder::new("bar.txt".into(), Compression::Stored).version_needed(20);
+    writer.write_entry_stream(entry).await.unwrap().close().await.unwrap();
+
+    let data = writer.close().await.unwrap();
+    let reader = ZipFileReader::new(data.clone()).await.unwrap();
+
+    for entry in reader.file().entries() {
+        assert_eq!(entry.version_needed_to_extract(), 20);
+
+        let offset = entry.header_offset() as usize;
+        assert_eq!(u16::from_le_bytes([data[offset + 4], data[offset + 5]]), 20);
+    }
+}
+
+#[tokio::test]
+async fn made_by() {
+    use crate::{AttributeCompatibility, DosAttributes};
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    let entry = ZipEntryBuilder::new("foo.txt".into(), Compression::Stored).unix_permissions(0o644);
+    writer.write_entry_whole(entry, b"foo").await.unwrap();
+    let entry = ZipEntryBuilder::new("bar.txt".into(), Compression::Stored).dos_attributes(DosAttributes::HIDDEN);
+    writer.write_entry_whole(entry, b"bar").await.unwrap();
+
+    let reader = ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+    assert_eq!(reader.file().entries()[0].made_by(), (3, 63));
+    assert_eq!(reader.file().entries()[0].made_by_compatibility(), Some(AttributeCompatibility::Unix));
+    assert_eq!(reader.file().entries()[1].made_by(), (0, 63));
+    assert_eq!(reader.file().entries()[1].made_by_compatibility(), Some(AttributeCompatibility::Dos));
+}
+
+#[tokio::test]
+async fn made_by_unknown_host() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    let entry = ZipEntryBuilder::new("foo.txt".into(), Compression::Stored);
+    writer.write_entry_whole(entry, b"foo").await.unwrap();
+    let mut data = writer.close().await.unwrap();
+
+    // Mark the central directory record as made by a Macintosh host (7), which isn't supported.
+    let cdh = data.windows(4).position(|window| window == [0x50, 0x4B, 0x01, 0x02]).unwrap();
+    data[cdh + 5] = 7;
+
+    let reader = ZipFileReader::new(data).await.unwrap();
+    assert_eq!(reader.file().entries()[0].made_by(), (7, 63));
+    assert_eq!(reader.file().entries()[0].made_by_compatibility(), None);
+}
+
+#[tokio::test]
+async fn utf8_flag_round_trip() {
+    use crate::{StringEncoding, ZipString};
+
+    // An ASCII filename written without the UTF-8 flag is read back as UTF-8.
+    let mut writer = ZipFileWriter::new(Vec::new());
+    let filename = ZipString::new(b"foo.txt".to_vec(), StringEncoding::Raw);
+    writer.write_entry_whole(ZipEntryBuilder::new(filename, Compression::Stored), b"foo").await.unwrap();
+    writer.write_entry_whole(ZipEntryBuilder::new("bar.txt".into(), Compression::Stored), b"bar").await.unwrap();
+
+    let reader = ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+    assert_eq!(reader.file().entries()[0].utf8_flag(), Some(false));
+    assert_eq!(reader.file().entries()[1].utf8_flag(), Some(true));
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+    for stored in reader.file().entries() {
+        writer.write_entry_whole(stored.to_entry(), b"").await.unwrap();
+    }
+    let renamed = ZipEntryBuilder::from(reader.file().entries()[0].to_entry()).filename("baz.txt".into());
+    assert_eq!(renamed.clone().build().utf8_flag(), None);
+    writer.write_entry_whole(renamed, b"").await.unwrap();
+
+    let rewritten = ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+    let flags: Vec<_> = rewritten.file().entries().iter().map(|entry| entry.utf8_flag()).collect();
+    assert_eq!(flags, [Some(false), Some(true), Some(true)]);
+}
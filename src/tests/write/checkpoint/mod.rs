@@ -0,0 +1,45 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::seek::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::{Compression, ZipEntryBuilder};
+
+use futures_lite::io::{AsyncWriteExt, Cursor};
+
+#[tokio::test]
+async fn checkpoint_tailing() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    assert!(writer.checkpoint().await.unwrap().entries().is_empty());
+
+    let entry = ZipEntryBuilder::new("foo.txt".into(), Compression::Stored);
+    writer.write_entry_whole(entry, b"foo").await.unwrap();
+
+    let entry = ZipEntryBuilder::new("bar.txt".into(), Compression::Stored);
+    let mut entry_writer = writer.write_entry_stream(entry).await.unwrap();
+    entry_writer.write_all(b"bar").await.unwrap();
+    entry_writer.close().await.unwrap();
+
+    // Read the completed entries from a snapshot of the data written so far, as a separate reader of a growing file
+    // would see it.
+    let file = writer.checkpoint().await.unwrap();
+    assert_eq!(file.entries().len(), 2);
+
+    let mut reader = ZipFileReader::from_raw_parts(Cursor::new(writer.inner_mut().clone()), file);
+    for (index, expected) in [b"foo", b"bar"].into_iter().enumerate() {
+        let mut read = Vec::new();
+        reader.reader_with_entry(index).await.unwrap().read_to_end_checked(&mut read).await.unwrap();
+        assert_eq!(read, expected);
+    }
+
+    let entry = ZipEntryBuilder::new("baz.txt".into(), Compression::Stored);
+    writer.write_entry_whole(entry, b"baz").await.unwrap();
+    let file = writer.checkpoint().await.unwrap();
+    assert_eq!(file.entries().len(), 3);
+
+    // The final archive should list the entries at the same offsets as the last checkpoint.
+    let offsets: Vec<u64> = file.entries().iter().map(|entry| entry.header_offset()).collect();
+    let reader = writer.close_into_reader().await.unwrap();
+    let final_offsets: Vec<u64> = reader.file().entries().iter().map(|entry| entry.header_offset()).collect();
+    assert_eq!(offsets, final_offsets);
+}
@@ -0,0 +1,106 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::mem::ZipFileReader;
+use crate::base::write::{ChunkedZipFileWriter, MIN_CHUNK_SIZE};
+use crate::{Compression, ZipEntryBuilder};
+
+use crate::error::ZipError;
+
+use std::future::Future;
+use std::io::{Error, ErrorKind};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+
+type Parts = Arc<Mutex<Vec<(u64, Vec<u8>)>>>;
+type Upload = Pin<Box<dyn Future<Output = std::io::Result<()>>>>;
+
+/// Returns a chunk closure which yields once before recording each chunk, as an upload would.
+fn record(parts: &Parts) -> impl FnMut(u64, Vec<u8>) -> Upload + Unpin {
+    let parts = parts.clone();
+    move |offset, data| {
+        let parts = parts.clone();
+        Box::pin(async move {
+            tokio::task::yield_now().await;
+            parts.lock().unwrap().push((offset, data));
+            Ok(())
+        })
+    }
+}
+
+#[tokio::test]
+async fn chunked_parts() {
+    let parts = Parts::default();
+    let mut writer = ChunkedZipFileWriter::new(MIN_CHUNK_SIZE, record(&parts));
+
+    let data: Vec<u8> = (0..MIN_CHUNK_SIZE as u32 + 1_000_000).map(|i| (i % 251) as u8).collect();
+    let entry = ZipEntryBuilder::new("foo.bin".into(), Compression::Stored);
+    writer.write_entry_whole(entry, &data).await.unwrap();
+
+    let entry = ZipEntryBuilder::new("bar.bin".into(), Compression::Stored);
+    writer.write_entry_whole(entry, &data).await.unwrap();
+
+    let size = writer.close().await.unwrap();
+    let parts = std::mem::take(&mut *parts.lock().unwrap());
+    assert_eq!(parts.len(), 3);
+    assert!(parts[..2].iter().all(|(_, data)| data.len() == MIN_CHUNK_SIZE));
+    assert!(parts[2].1.len() < MIN_CHUNK_SIZE);
+
+    let mut archive = Vec::new();
+    for (offset, data) in parts {
+        assert_eq!(offset, archive.len() as u64);
+        archive.extend_from_slice(&data);
+    }
+    assert_eq!(size, archive.len() as u64);
+
+    let reader = ZipFileReader::new(archive).await.unwrap();
+    for index in 0..2 {
+        let mut read = Vec::new();
+        reader.reader_with_entry(index).await.unwrap().read_to_end_checked(&mut read).await.unwrap();
+        assert_eq!(read, data);
+    }
+}
+
+#[tokio::test]
+async fn chunked_small_archive() {
+    let parts = Parts::default();
+    let mut writer = ChunkedZipFileWriter::new(1024, record(&parts));
+
+    let entry = ZipEntryBuilder::new("foo.txt".into(), Compression::Stored);
+    writer.write_entry_whole(entry, b"foo").await.unwrap();
+    writer.comment_bytes(b"\xFFbar".to_vec());
+
+    let size = writer.close().await.unwrap();
+    let parts = std::mem::take(&mut *parts.lock().unwrap());
+    assert_eq!(parts.len(), 1);
+    assert_eq!(parts[0].0, 0);
+    assert_eq!(parts[0].1.len() as u64, size);
+
+    let reader = ZipFileReader::new(parts.into_iter().next().unwrap().1).await.unwrap();
+    assert_eq!(reader.file().comment().as_bytes(), b"\xFFbar");
+}
+
+#[tokio::test]
+async fn chunked_with_options() {
+    let parts = Parts::default();
+    let mut writer = ChunkedZipFileWriter::with_options(1024, record(&parts), |writer| writer.force_zip64());
+
+    let entry = ZipEntryBuilder::new("foo.txt".into(), Compression::Stored);
+    writer.write_entry_whole(entry, b"foo").await.unwrap();
+    writer.close().await.unwrap();
+
+    let archive: Vec<u8> = parts.lock().unwrap().iter().flat_map(|(_, data)| data.clone()).collect();
+    let reader = ZipFileReader::new(archive).await.unwrap();
+    assert!(reader.file().zip64());
+}
+
+#[tokio::test]
+async fn chunked_error() {
+    let mut writer = ChunkedZipFileWriter::new(MIN_CHUNK_SIZE, |_, _| async {
+        Err(Error::new(ErrorKind::ConnectionReset, "failed"))
+    });
+
+    let entry = ZipEntryBuilder::new("foo.bin".into(), Compression::Stored);
+    let result = writer.write_entry_whole(entry, &vec![0; MIN_CHUNK_SIZE + 1]).await;
+    assert!(matches!(result, Err(ZipError::UpstreamReadError(err)) if err.kind() == ErrorKind::ConnectionReset));
+}
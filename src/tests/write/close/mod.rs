@@ -0,0 +1,34 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::write::ZipFileWriter;
+use crate::{Compression, ZipEntryBuilder};
+
+async fn write_archive() -> ZipFileWriter<Vec<u8>> {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    writer.comment(String::from("foo bar"));
+
+    let entry = ZipEntryBuilder::new("foo.txt".into(), Compression::Stored);
+    writer.write_entry_whole(entry, b"foo").await.unwrap();
+    let entry = ZipEntryBuilder::new("bar.txt".into(), Compression::Stored);
+    writer.write_entry_whole(entry, b"bar").await.unwrap();
+
+    writer
+}
+
+#[tokio::test]
+async fn close_into_reader() {
+    let expected = write_archive().await.close().await.unwrap();
+    let reader = write_archive().await.close_into_reader().await.unwrap();
+
+    // The reader is over exactly the bytes which close() would have returned.
+    assert_eq!(reader.data(), expected.as_slice());
+    assert_eq!(reader.file().comment().as_str().unwrap(), "foo bar");
+    assert_eq!(reader.file().entries().len(), 2);
+
+    for (index, data) in [b"foo", b"bar"].into_iter().enumerate() {
+        let mut buffer = Vec::new();
+        reader.reader_with_entry(index).await.unwrap().read_to_end_checked(&mut buffer).await.unwrap();
+        assert_eq!(buffer, data);
+    }
+}
@@ -0,0 +1,162 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::{mem, stream};
+use crate::base::write::ZipFileWriter;
+use crate::codec::{Codec, Decoder, Encoder};
+use crate::error::ZipError;
+use crate::{Compression, ZipEntryBuilder};
+
+use std::io::{Error, ErrorKind};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{ready, Context, Poll};
+
+use futures_lite::io::{AsyncBufRead, AsyncWrite, AsyncWriteExt, Cursor};
+
+const METHOD: u16 = 0xC0DE;
+const MARKER: u8 = 0xAA;
+
+/// A codec which XORs the data and prefixes it with a marker byte, so decoding can detect foreign data.
+#[derive(Debug)]
+struct Marked;
+
+impl Codec for Marked {
+    fn encoder(&self) -> Box<dyn Encoder> {
+        Box::new(MarkedCoder { marker: true })
+    }
+
+    fn decoder(&self) -> Box<dyn Decoder> {
+        Box::new(MarkedCoder { marker: true })
+    }
+}
+
+struct MarkedCoder {
+    /// Whether the marker byte has yet to be written or checked.
+    marker: bool,
+}
+
+impl MarkedCoder {
+    fn poll_marker(&mut self, cx: &mut Context<'_>, output: Pin<&mut dyn AsyncWrite>) -> Poll<std::io::Result<()>> {
+        if self.marker {
+            if ready!(output.poll_write(cx, &[MARKER]))? == 0 {
+                return Poll::Ready(Err(ErrorKind::WriteZero.into()));
+            }
+            self.marker = false;
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl Encoder for MarkedCoder {
+    fn poll_encode(
+        &mut self,
+        cx: &mut Context<'_>,
+        mut output: Pin<&mut dyn AsyncWrite>,
+        input: &[u8],
+    ) -> Poll<std::io::Result<usize>> {
+        ready!(self.poll_marker(cx, output.as_mut()))?;
+        let encoded: Vec<u8> = input.iter().map(|byte| byte ^ 0x5A).collect();
+        output.poll_write(cx, &encoded)
+    }
+
+    fn poll_finish(&mut self, cx: &mut Context<'_>, output: Pin<&mut dyn AsyncWrite>) -> Poll<std::io::Result<()>> {
+        self.poll_marker(cx, output)
+    }
+}
+
+impl Decoder for MarkedCoder {
+    fn poll_decode(
+        &mut self,
+        cx: &mut Context<'_>,
+        mut input: Pin<&mut dyn AsyncBufRead>,
+        output: &mut [u8],
+    ) -> Poll<std::io::Result<usize>> {
+        if self.marker {
+            let mut marker = [0; 1];
+            if ready!(input.as_mut().poll_read(cx, &mut marker))? == 0 || marker[0] != MARKER {
+                return Poll::Ready(Err(Error::new(ErrorKind::InvalidData, "missing marker")));
+            }
+            self.marker = false;
+        }
+
+        let read = ready!(input.poll_read(cx, output))?;
+        output[..read].iter_mut().for_each(|byte| *byte ^= 0x5A);
+        Poll::Ready(Ok(read))
+    }
+}
+
+#[tokio::test]
+async fn custom_codec_round_trip() {
+    let data: Vec<u8> = (0..10_000u32).map(|i| (i % 251) as u8).collect();
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    let entry =
+        ZipEntryBuilder::new("whole.bin".into(), Compression::Stored).custom_compression(METHOD, Box::new(Marked));
+    writer.write_entry_whole(entry, &data).await.unwrap();
+
+    let entry =
+        ZipEntryBuilder::new("stream.bin".into(), Compression::Stored).custom_compression(METHOD, Box::new(Marked));
+    let mut entry_writer = writer.write_entry_stream(entry).await.unwrap();
+    entry_writer.write_all(&data).await.unwrap();
+    entry_writer.close().await.unwrap();
+
+    let archive = writer.close().await.unwrap();
+
+    // Entries can be listed without a codec, but their data can't be read.
+    let mut reader = mem::ZipFileReader::new(archive.clone()).await.unwrap();
+    assert_eq!(reader.file().entries()[0].compression(), Compression::Custom(METHOD));
+    assert!(matches!(reader.reader_with_entry(0).await, Err(ZipError::CompressionNotSupported(METHOD))));
+
+    reader.register_codec(METHOD, Arc::new(Marked)).unwrap();
+
+    for index in 0..2 {
+        let entry = &reader.file().entries()[index];
+        assert_eq!(entry.compression(), Compression::Custom(METHOD));
+        assert_eq!(entry.compressed_size(), data.len() as u64 + 1);
+
+        let mut read = Vec::new();
+        reader.reader_with_entry(index).await.unwrap().read_to_end_checked(&mut read).await.unwrap();
+        assert_eq!(read, data);
+    }
+
+    // Codecs are registered per reader, so a separate reader over the same data still lacks one.
+    let other = mem::ZipFileReader::new(archive.clone()).await.unwrap();
+    assert!(matches!(other.reader_with_entry(0).await, Err(ZipError::CompressionNotSupported(METHOD))));
+
+    let mut reader = stream::ZipFileReader::new(Cursor::new(archive));
+    reader.register_codec(METHOD, Arc::new(Marked)).unwrap();
+    let mut entry = reader.next_with_entry().await.unwrap().unwrap();
+    let mut read = Vec::new();
+    entry.reader_mut().read_to_end_checked(&mut read).await.unwrap();
+    assert_eq!(read, data);
+}
+
+#[tokio::test]
+async fn custom_codec_missing() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    let entry = ZipEntryBuilder::new("foo.bin".into(), Compression::Custom(METHOD));
+    let result = writer.write_entry_whole(entry, b"foo").await;
+    assert!(matches!(result, Err(ZipError::CompressionNotSupported(METHOD))));
+
+    let entry = ZipEntryBuilder::new("foo.bin".into(), Compression::Custom(METHOD));
+    let result = writer.write_entry_stream(entry).await;
+    assert!(matches!(result, Err(ZipError::CompressionNotSupported(METHOD))));
+}
+
+#[tokio::test]
+async fn custom_codec_reserved_method() {
+    let mut reader = stream::ZipFileReader::new(Cursor::new(Vec::<u8>::new()));
+    assert!(matches!(reader.register_codec(8, Arc::new(Marked)), Err(ZipError::CompressionMethodReserved(8))));
+    assert!(matches!(reader.register_codec(99, Arc::new(Marked)), Err(ZipError::CompressionMethodReserved(99))));
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+    let entry = ZipEntryBuilder::new("foo.bin".into(), Compression::Stored).custom_compression(0, Box::new(Marked));
+    let result = writer.write_entry_whole(entry, b"foo").await;
+    assert!(matches!(result, Err(ZipError::CompressionMethodReserved(0))));
+
+    let entry = ZipEntryBuilder::new("foo.bin".into(), Compression::Stored).custom_compression(12, Box::new(Marked));
+    let result = writer.write_entry_stream(entry).await;
+    assert!(matches!(result, Err(ZipError::CompressionMethodReserved(12))));
+}
@@ -0,0 +1,22 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::mem::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::string::StringEncoding;
+use crate::{Compression, ZipEntryBuilder};
+
+#[tokio::test]
+async fn comment_bytes() {
+    // "Größe" encoded as CP437, which isn't valid UTF-8.
+    let comment = vec![0x47, 0x72, 0x94, 0xE1, 0x65];
+    let mut writer = ZipFileWriter::new(Vec::new());
+    writer.comment_bytes(comment.clone());
+
+    let entry = ZipEntryBuilder::new("foo.txt".into(), Compression::Stored);
+    writer.write_entry_whole(entry, b"foo bar").await.unwrap();
+
+    let reader = ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+    assert_eq!(reader.file().comment().as_bytes(), comment);
+    assert_eq!(reader.file().comment().encoding(), StringEncoding::Raw);
+}
@@ -0,0 +1,68 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::write::ZipFileWriter;
+use crate::error::ZipError;
+use crate::{Compression, ZipEntryBuilder};
+
+use futures_lite::io::{AsyncReadExt, AsyncWriteExt};
+
+const DATA: &[u8] = b"foo bar baz";
+
+#[tokio::test]
+async fn skip_crc() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    let entry = ZipEntryBuilder::new("whole.txt".into(), Compression::Stored).skip_crc(true);
+    writer.write_entry_whole(entry, DATA).await.unwrap();
+
+    let entry = ZipEntryBuilder::new("stream.txt".into(), Compression::Stored).skip_crc(true);
+    let mut entry_writer = writer.write_entry_stream(entry).await.unwrap();
+    entry_writer.write_all(DATA).await.unwrap();
+    entry_writer.close().await.unwrap();
+
+    let entry = ZipEntryBuilder::new("prepared.txt".into(), Compression::Stored).skip_crc(true);
+    writer.write_prepared(entry.prepare(DATA).await.unwrap()).await.unwrap();
+
+    let reader = writer.close_into_reader().await.unwrap();
+
+    for index in 0..3 {
+        assert_eq!(reader.file().entries()[index].crc32(), 0);
+        assert!(!reader.file().entries()[index].skip_crc());
+
+        let mut buffer = Vec::new();
+        reader.reader_with_entry(index).await.unwrap().read_to_end(&mut buffer).await.unwrap();
+        assert_eq!(buffer, DATA);
+
+        let result = reader.reader_with_entry(index).await.unwrap().read_to_end_checked(&mut Vec::new()).await;
+        assert!(matches!(result, Err(ZipError::CRC32CheckError)));
+    }
+}
+
+#[cfg(feature = "deflate")]
+#[tokio::test]
+async fn skip_crc_compressed() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    let entry = ZipEntryBuilder::new("whole.txt".into(), Compression::Deflate).skip_crc(true);
+    let result = writer.write_entry_whole(entry, DATA).await;
+    assert!(matches!(result, Err(ZipError::FeatureNotSupported(_))));
+
+    let entry = ZipEntryBuilder::new("stream.txt".into(), Compression::Deflate).skip_crc(true);
+    let result = writer.write_entry_stream(entry).await;
+    assert!(matches!(result, Err(ZipError::FeatureNotSupported(_))));
+
+    let entry = ZipEntryBuilder::new("prepared.txt".into(), Compression::Deflate).skip_crc(true);
+    let result = entry.prepare(DATA).await;
+    assert!(matches!(result, Err(ZipError::FeatureNotSupported(_))));
+}
+
+#[tokio::test]
+async fn skip_crc_disabled() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    let entry = ZipEntryBuilder::new("foo.txt".into(), Compression::Stored).skip_crc(false);
+    writer.write_entry_whole(entry, DATA).await.unwrap();
+
+    let reader = writer.close_into_reader().await.unwrap();
+    assert_eq!(reader.file().entries()[0].crc32(), crc32fast::hash(DATA));
+}
@@ -0,0 +1,49 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::mem::ZipFileReader;
+use crate::base::read::stream;
+use crate::base::write::ZipFileWriter;
+use crate::{Compression, ZipEntryBuilder};
+
+use futures_lite::io::Cursor;
+
+const ENTRIES: [(&str, &str, Compression); 5] = [
+    ("a.txt", "foo bar", Compression::Deflate),
+    ("b.txt", "foo bar", Compression::Deflate),
+    ("c.txt", "bar foo", Compression::Deflate),
+    ("d.txt", "foo bar", Compression::Stored),
+    ("e.txt", "", Compression::Deflate),
+];
+
+#[tokio::test]
+async fn dedupe_identical_contents() {
+    let mut writer = ZipFileWriter::new(Vec::new()).dedupe(true);
+
+    for (name, data, compression) in ENTRIES {
+        writer.write_entry_whole(ZipEntryBuilder::new(name.into(), compression), data.as_bytes()).await.unwrap();
+    }
+
+    // The stored entry isn't deduplicated, and the duplicate deflate entry reuses the first's compressed data.
+    assert_eq!(writer.dedupe.as_ref().unwrap().len(), 3);
+    let data = writer.close().await.unwrap();
+
+    let reader = ZipFileReader::new(data.clone()).await.unwrap();
+    let offsets: Vec<u64> = reader.file().entries().iter().map(|entry| entry.header_offset()).collect();
+    assert!(offsets.windows(2).all(|pair| pair[0] < pair[1]));
+
+    for (index, (_, expected, _)) in ENTRIES.iter().enumerate() {
+        let mut contents = String::new();
+        reader.reader_with_entry(index).await.unwrap().read_to_string_checked(&mut contents).await.unwrap();
+        assert_eq!(&contents, expected);
+    }
+
+    // Each entry has its own local file header, so the archive can still be read sequentially.
+    let mut zip = stream::ZipFileReader::new(Cursor::new(data));
+    let mut names = Vec::new();
+    while let Some(entry) = zip.next_with_entry().await.unwrap() {
+        names.push(entry.reader().entry().filename().as_str().unwrap().to_owned());
+        zip = entry.skip().await.unwrap();
+    }
+    assert_eq!(names, ENTRIES.map(|(name, _, _)| name));
+}
@@ -0,0 +1,69 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::write::ZipFileWriter;
+use crate::error::ZipError;
+use crate::spec::attribute::AttributeCompatibility;
+use crate::{Compression, DosAttributes, ZipEntryBuilder};
+
+use futures_lite::io::AsyncWriteExt;
+
+#[cfg(feature = "deflate")]
+const COMPRESSION: Compression = Compression::Deflate;
+#[cfg(not(feature = "deflate"))]
+const COMPRESSION: Compression = Compression::Stored;
+
+#[tokio::test]
+async fn directory() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    let entry = ZipEntryBuilder::new("foo".into(), COMPRESSION).directory(true);
+    assert_eq!(entry.clone().build().filename().as_str().unwrap(), "foo/");
+    writer.write_entry_whole(entry, &[]).await.unwrap();
+
+    let entry = ZipEntryBuilder::new("bar/".into(), COMPRESSION).unix_permissions(0o700).directory(true);
+    writer.write_entry_whole(entry, &[]).await.unwrap();
+
+    let entry = ZipEntryBuilder::new("baz".into(), COMPRESSION)
+        .attribute_compatibility(AttributeCompatibility::Dos)
+        .directory(true)
+        .filename("qux".into())
+        .compression(COMPRESSION);
+    writer.write_entry_whole(entry, &[]).await.unwrap();
+
+    let reader = writer.close_into_reader().await.unwrap();
+    let names: Vec<_> = reader.file().entries().iter().map(|entry| entry.filename().as_str().unwrap()).collect();
+    assert_eq!(names, ["foo/", "bar/", "qux/"]);
+
+    for entry in reader.file().entries() {
+        assert!(entry.dir().unwrap());
+        assert_eq!(entry.compression(), Compression::Stored);
+        assert_eq!(entry.uncompressed_size(), 0);
+        assert!(entry.dos_attributes().contains(DosAttributes::DIRECTORY));
+    }
+
+    assert_eq!(reader.file().entries()[0].unix_permissions(), Some(0o040755));
+    assert_eq!(reader.file().entries()[1].unix_permissions(), Some(0o040700));
+    assert_eq!(reader.file().entries()[2].unix_permissions(), None);
+}
+
+#[tokio::test]
+async fn directory_with_data() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    let entry = ZipEntryBuilder::new("foo".into(), Compression::Stored).directory(true);
+    let result = writer.write_entry_whole(entry.clone(), b"foo").await;
+    assert!(matches!(result, Err(ZipError::DirectoryWithData(name)) if name == "foo/"));
+
+    let result = entry.clone().prepare(b"foo").await;
+    assert!(matches!(result, Err(ZipError::DirectoryWithData(_))));
+
+    // Streamed data is rejected as it's written, rather than once the entry is closed.
+    let mut entry_writer = writer.write_entry_stream(entry).await.unwrap();
+    let result = entry_writer.write_all(b"foo").await.map_err(ZipError::from);
+    assert!(matches!(result, Err(ZipError::DirectoryWithData(_))));
+    entry_writer.close().await.unwrap();
+
+    let reader = writer.close_into_reader().await.unwrap();
+    assert_eq!(reader.file().entries()[0].uncompressed_size(), 0);
+}
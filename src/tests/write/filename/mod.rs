@@ -0,0 +1,98 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::write::ZipFileWriter;
+use crate::error::ZipError;
+use crate::{Compression, ZipEntryBuilder};
+
+#[test]
+fn validate_filename() {
+    let valid = ZipEntryBuilder::new("foo/bar.txt".into(), Compression::Stored);
+    assert!(valid.validate().is_ok());
+
+    for filename in ["foo\0.txt", "foo\n.txt", "foo\x7F.txt", "foo\\bar.txt"] {
+        let invalid = ZipEntryBuilder::new(filename.into(), Compression::Stored);
+        assert!(matches!(invalid.validate(), Err(ZipError::InvalidFileName(_))), "for {filename:?}");
+    }
+}
+
+#[tokio::test]
+async fn reject_nul_filename() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    let entry = ZipEntryBuilder::new("foo\0.txt".into(), Compression::Stored);
+    let result = writer.write_entry_whole(entry.clone(), b"foo").await;
+    assert!(matches!(result, Err(ZipError::InvalidFileName(_))));
+
+    let result = writer.write_entry_stream(entry).await;
+    assert!(matches!(result, Err(ZipError::InvalidFileName(_))));
+
+    // Only NUL bytes are rejected by default.
+    let entry = ZipEntryBuilder::new("foo\\bar.txt".into(), Compression::Stored);
+    writer.write_entry_whole(entry, b"foo").await.unwrap();
+}
+
+#[tokio::test]
+async fn allow_invalid_filenames() {
+    let mut writer = ZipFileWriter::new(Vec::new()).allow_invalid_filenames();
+
+    let entry = ZipEntryBuilder::new("foo\0.txt".into(), Compression::Stored);
+    writer.write_entry_whole(entry, b"foo").await.unwrap();
+    writer.close().await.unwrap();
+}
+
+#[tokio::test]
+async fn reject_duplicate_names() {
+    let entry = ZipEntryBuilder::new("foo.txt".into(), Compression::Stored);
+
+    // Duplicates are allowed by default.
+    let mut writer = ZipFileWriter::new(Vec::new());
+    writer.write_entry_whole(entry.clone(), b"foo").await.unwrap();
+    writer.write_entry_whole(entry.clone(), b"foo").await.unwrap();
+
+    let mut writer = ZipFileWriter::new(Vec::new()).reject_duplicate_names();
+    writer.write_entry_whole(entry.clone(), b"foo").await.unwrap();
+
+    let result = writer.write_entry_whole(entry.clone(), b"foo").await;
+    assert!(matches!(result, Err(ZipError::DuplicateFileName(name)) if name == "foo.txt"));
+    assert!(matches!(writer.write_entry_stream(entry).await, Err(ZipError::DuplicateFileName(_))));
+
+    let entry = ZipEntryBuilder::new("Foo.txt".into(), Compression::Stored);
+    writer.write_entry_whole(entry, b"foo").await.unwrap();
+}
+
+#[tokio::test]
+async fn reject_duplicate_names_after_failure() {
+    let mut writer = ZipFileWriter::new(Vec::new()).reject_duplicate_names();
+
+    // An entry which fails to be written doesn't reserve its filename.
+    let entry = ZipEntryBuilder::new("foo.txt".into(), Compression::Custom(0xC0DE));
+    assert!(matches!(writer.write_entry_whole(entry.clone(), b"foo").await, Err(ZipError::CompressionNotSupported(_))));
+    assert!(matches!(writer.write_entry_stream(entry).await, Err(ZipError::CompressionNotSupported(_))));
+
+    let entry = ZipEntryBuilder::new("foo.txt".into(), Compression::Stored);
+    writer.write_entry_whole(entry.clone(), b"foo").await.unwrap();
+    assert!(matches!(writer.write_entry_stream(entry).await, Err(ZipError::DuplicateFileName(_))));
+
+    let entry = ZipEntryBuilder::new("bar.txt".into(), Compression::Stored);
+    writer.write_entry_stream(entry.clone()).await.unwrap().close().await.unwrap();
+    assert!(matches!(writer.write_entry_whole(entry, b"bar").await, Err(ZipError::DuplicateFileName(_))));
+}
+
+#[tokio::test]
+async fn normalize_separators() {
+    let mut writer = ZipFileWriter::new(Vec::new()).normalize_separators(true).reject_duplicate_names();
+
+    let entry = ZipEntryBuilder::new("dir\\sub/foo.txt".into(), Compression::Stored);
+    writer.write_entry_whole(entry, b"foo").await.unwrap();
+    let entry = ZipEntryBuilder::new("dir\\bar.txt".into(), Compression::Stored);
+    writer.write_entry_stream(entry).await.unwrap().close().await.unwrap();
+
+    // Duplicates are detected after normalization.
+    let entry = ZipEntryBuilder::new("dir/bar.txt".into(), Compression::Stored);
+    assert!(matches!(writer.write_entry_whole(entry, b"bar").await, Err(ZipError::DuplicateFileName(_))));
+
+    let reader = writer.close_into_reader().await.unwrap();
+    let filenames: Vec<_> = reader.file().entries().iter().map(|entry| entry.filename().as_str().unwrap()).collect();
+    assert_eq!(filenames, ["dir/sub/foo.txt", "dir/bar.txt"]);
+}
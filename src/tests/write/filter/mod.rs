@@ -0,0 +1,48 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::mem;
+use crate::base::read::seek::ZipFileReader;
+use crate::base::write::{filter_archive, ZipFileWriter};
+use crate::{Compression, ZipEntryBuilder};
+
+use futures_lite::io::Cursor;
+
+#[cfg(feature = "deflate")]
+const COMPRESSION: Compression = Compression::Deflate;
+#[cfg(not(feature = "deflate"))]
+const COMPRESSION: Compression = Compression::Stored;
+
+#[tokio::test]
+async fn filter_archive_keeps_matching_entries() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    for name in ["foo.txt", "stale.log", "bar.txt"] {
+        let entry = ZipEntryBuilder::new(name.into(), COMPRESSION).comment(format!("{name} comment").into());
+        writer.write_entry_whole(entry, name.repeat(100).as_bytes()).await.unwrap();
+    }
+    writer.comment(String::from("archive comment"));
+    let data = writer.close().await.unwrap();
+
+    let mut reader = ZipFileReader::new(Cursor::new(data)).await.unwrap();
+    let original_offset = reader.file().entries()[2].header_offset();
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+    filter_archive(&mut reader, &mut writer, |entry| !entry.filename().as_bytes().ends_with(b".log")).await.unwrap();
+    let filtered = writer.close().await.unwrap();
+
+    let reader = mem::ZipFileReader::new(filtered).await.unwrap();
+    let entries = reader.file().entries();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(reader.file().comment().as_str().unwrap(), "archive comment");
+    assert!(entries[1].header_offset() < original_offset);
+
+    for (index, name) in ["foo.txt", "bar.txt"].into_iter().enumerate() {
+        assert_eq!(entries[index].filename().as_str().unwrap(), name);
+        assert_eq!(entries[index].comment().as_str().unwrap(), format!("{name} comment"));
+        assert_eq!(entries[index].compression(), COMPRESSION);
+
+        let mut buffer = String::new();
+        reader.reader_with_entry(index).await.unwrap().read_to_string_checked(&mut buffer).await.unwrap();
+        assert_eq!(buffer, name.repeat(100));
+    }
+}
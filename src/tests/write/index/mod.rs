@@ -0,0 +1,32 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::{mem, seek};
+use crate::base::write::ZipFileWriter;
+use crate::{Compression, ZipEntryBuilder};
+
+use futures_lite::io::Cursor;
+
+#[tokio::test]
+async fn write_index_only() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    writer.write_entry_whole(ZipEntryBuilder::new("foo.txt".into(), Compression::Stored), b"foo").await.unwrap();
+    writer.write_entry_whole(ZipEntryBuilder::new("bar.txt".into(), Compression::Stored), b"bar").await.unwrap();
+    let data = writer.close().await.unwrap();
+
+    let data_reader = mem::ZipFileReader::new(data.clone()).await.unwrap();
+    let entries = data_reader.file().entries().to_vec();
+    let index = ZipFileWriter::new(Vec::new()).write_index_only(entries).await.unwrap();
+
+    // The index only holds the central directory, so it's much smaller than the archive holding the data.
+    assert!(index.len() < data.len());
+
+    let index_reader = mem::ZipFileReader::new(index).await.unwrap();
+    let mut reader = seek::ZipFileReader::from_raw_parts(Cursor::new(data), index_reader.file().clone());
+
+    for (index, expected) in [&b"foo"[..], b"bar"].into_iter().enumerate() {
+        let mut output = Vec::new();
+        reader.reader_with_entry(index).await.unwrap().read_to_end_checked(&mut output).await.unwrap();
+        assert_eq!(output, expected);
+    }
+}
@@ -0,0 +1,72 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::read_summary;
+use crate::base::write::ZipFileWriter;
+use crate::spec::consts::{EOCDR_SIGNATURE, LFH_SIGNATURE, ZIP64_EOCDR_SIGNATURE};
+use crate::{Compression, ZipEntryBuilder};
+
+use futures_lite::io::Cursor;
+
+fn signature_at(data: &[u8], offset: u64) -> u32 {
+    let offset = offset as usize;
+    u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap())
+}
+
+#[tokio::test]
+async fn close_with_layout() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    writer.write_entry_whole(ZipEntryBuilder::new("foo.txt".into(), Compression::Stored), b"foo").await.unwrap();
+    writer.write_entry_whole(ZipEntryBuilder::new("bar.txt".into(), Compression::Stored), b"bar").await.unwrap();
+    let (data, layout) = writer.close_with_layout().await.unwrap();
+
+    assert_eq!(layout.first_entry_offset(), Some(0));
+    assert_eq!(signature_at(&data, layout.first_entry_offset().unwrap()), LFH_SIGNATURE);
+    assert_eq!(layout.zip64_eocdr_offset(), None);
+    assert_eq!(signature_at(&data, layout.eocdr_offset()), EOCDR_SIGNATURE);
+
+    let summary = read_summary(Cursor::new(&data)).await.unwrap();
+    assert_eq!(layout.central_directory_offset(), summary.central_directory_offset());
+    assert_eq!(layout.central_directory_size(), summary.central_directory_size());
+}
+
+#[tokio::test]
+async fn close_with_layout_zip64() {
+    let mut writer = ZipFileWriter::new(Vec::new()).force_zip64();
+    writer.write_entry_whole(ZipEntryBuilder::new("foo.txt".into(), Compression::Stored), b"foo").await.unwrap();
+    let (data, layout) = writer.close_with_layout().await.unwrap();
+
+    let zip64_eocdr_offset = layout.zip64_eocdr_offset().unwrap();
+    assert_eq!(signature_at(&data, zip64_eocdr_offset), ZIP64_EOCDR_SIGNATURE);
+    assert_eq!(signature_at(&data, layout.eocdr_offset()), EOCDR_SIGNATURE);
+    assert_eq!(layout.central_directory_offset() + layout.central_directory_size(), zip64_eocdr_offset);
+}
+
+#[tokio::test]
+async fn close_with_layout_empty() {
+    let (data, layout) = ZipFileWriter::new(Vec::new()).close_with_layout().await.unwrap();
+
+    assert_eq!(layout.first_entry_offset(), None);
+    assert_eq!(layout.central_directory_offset(), 0);
+    assert_eq!(layout.eocdr_offset(), 0);
+    assert_eq!(signature_at(&data, layout.eocdr_offset()), EOCDR_SIGNATURE);
+}
+
+#[tokio::test]
+async fn close_with_layout_ranges() {
+    use crate::spec::consts::CDH_SIGNATURE;
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+    writer.write_entry_whole(ZipEntryBuilder::new("foo.txt".into(), Compression::Stored), b"foo").await.unwrap();
+    writer.comment(String::from("signed"));
+    let (data, layout) = writer.close_with_layout().await.unwrap();
+
+    let cd_range = layout.central_directory_range();
+    let eocdr_range = layout.eocdr_range();
+    assert_eq!(signature_at(&data, cd_range.start), CDH_SIGNATURE);
+    assert_eq!(cd_range.end, eocdr_range.start);
+    assert_eq!(signature_at(&data, eocdr_range.start), EOCDR_SIGNATURE);
+    assert_eq!(eocdr_range.end, data.len() as u64);
+    assert_eq!(layout.end_offset(), data.len() as u64);
+    assert!(data[eocdr_range.start as usize..eocdr_range.end as usize].ends_with(b"signed"));
+}
@@ -0,0 +1,63 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::write::ZipFileWriter;
+use crate::{Compression, ZipEntryBuilder};
+
+#[cfg(feature = "deflate")]
+#[tokio::test]
+async fn mmap_ready_entries() {
+    use crate::base::read::mem::ZipFileReader;
+    use crate::spec::header::{ExtraField, HeaderId};
+    use futures_lite::io::{AsyncWriteExt, Cursor};
+
+    let mut writer = ZipFileWriter::new(Vec::new()).mmap_ready_entries(&["tensors/*.bin", "?.raw"], 4096);
+
+    for filename in ["notes.txt", "tensors/a.bin", "tensors/nested/b.bin", "a.raw", "tensors/c.txt"] {
+        let entry = ZipEntryBuilder::new(filename.into(), Compression::Deflate);
+        writer.write_entry_whole(entry, &[0xAB; 5000]).await.unwrap();
+    }
+
+    let entry = ZipEntryBuilder::new("tensors/streamed.bin".into(), Compression::Deflate);
+    let mut entry_writer = writer.write_entry_stream(entry).await.unwrap();
+    entry_writer.write_all(&[0xAB; 5000]).await.unwrap();
+    entry_writer.close().await.unwrap();
+
+    let data = writer.close().await.unwrap();
+    let reader = ZipFileReader::new(data.clone()).await.unwrap();
+    let mut cursor = Cursor::new(&data);
+
+    // As with `ZipFile::glob()`, `*` doesn't match across directories, so nested entries aren't aligned.
+    for (index, aligned) in [false, true, false, true, false, true].into_iter().enumerate() {
+        let entry = &reader.file().entries()[index];
+        let range = entry.compressed_data_range(&mut cursor).await.unwrap();
+
+        assert_eq!(entry.compression() == Compression::Stored, aligned);
+        assert_eq!(range.start % 4096 == 0, aligned);
+        assert!(!entry.extra_fields().iter().any(|field| {
+            matches!(field, ExtraField::Unknown(field) if field.header_id == HeaderId::ALIGNMENT_EXTRA_FIELD)
+        }));
+
+        let mut output = Vec::new();
+        reader.reader_with_entry(index).await.unwrap().read_to_end_checked(&mut output).await.unwrap();
+        assert_eq!(output, [0xAB; 5000]);
+    }
+}
+
+#[tokio::test]
+async fn mmap_ready_stored_archive_size() {
+    let mut writer = ZipFileWriter::new(Vec::new()).mmap_ready_entries(&["*.bin"], 4096);
+    writer.write_entry_whole(ZipEntryBuilder::new("foo.txt".into(), Compression::Stored), b"foo").await.unwrap();
+
+    let entries = [
+        (ZipEntryBuilder::new("bar.bin".into(), Compression::Stored).build(), 100),
+        (ZipEntryBuilder::new("baz.bin".into(), Compression::Stored).build(), 5000),
+    ];
+    let expected = writer.stored_archive_size(&entries).unwrap();
+
+    for (entry, size) in entries {
+        writer.write_entry_whole(entry, &vec![0; size as usize]).await.unwrap();
+    }
+
+    assert_eq!(writer.close().await.unwrap().len() as u64, expected);
+}
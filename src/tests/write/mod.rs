@@ -6,7 +6,37 @@ use std::io::Error;
 use std::pin::Pin;
 use std::task::{Context, Poll};
 
+mod atomic;
+pub(crate) mod attribute;
+mod checkpoint;
+mod chunked;
+mod close;
+mod codec;
+mod comment;
+mod crc;
+#[cfg(all(feature = "sha2", feature = "deflate"))]
+mod dedupe;
+mod directory;
+mod filename;
+mod filter;
+mod index;
+mod layout;
+mod mmap;
 pub(crate) mod offset;
+mod order;
+mod padding;
+mod precompressed;
+mod prepared;
+#[cfg(feature = "deflate")]
+mod recompress;
+mod scope;
+mod size;
+mod split;
+#[cfg(feature = "deflate")]
+mod store;
+mod stream;
+mod timestamp;
+mod unicode;
 mod zip64;
 
 /// /dev/null for AsyncWrite.
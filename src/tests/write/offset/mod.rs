@@ -20,3 +20,20 @@ async fn basic() {
     writer.write_all(b"Bar. Bar.").await.expect("failed to write data");
     assert_eq!(writer.offset(), 37);
 }
+
+#[tokio::test]
+async fn bytes_written() {
+    use crate::base::write::ZipFileWriter;
+    use crate::{Compression, ZipEntryBuilder};
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+    assert_eq!(writer.bytes_written(), 0);
+
+    let entry = ZipEntryBuilder::new("foo.txt".into(), Compression::Stored);
+    writer.write_entry_whole(entry, b"foo bar").await.unwrap();
+    let written = writer.bytes_written();
+    assert_eq!(written, 30 + 7 + 7);
+
+    let data = writer.close().await.unwrap();
+    assert!(data.len() as u64 > written);
+}
@@ -0,0 +1,31 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::mem::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::{Compression, ZipEntry, ZipEntryBuilder};
+
+#[tokio::test]
+async fn close_sorted_by() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    for name in ["b.txt", "c.txt", "manifest.json", "a.txt"] {
+        let entry = ZipEntryBuilder::new(name.into(), Compression::Stored);
+        writer.write_entry_whole(entry, name.as_bytes()).await.unwrap();
+    }
+
+    // Place the manifest first, followed by all other entries in the order they were written.
+    let is_manifest = |entry: &ZipEntry| entry.filename().as_bytes() == b"manifest.json";
+    let data = writer.close_sorted_by(|a, b| is_manifest(b).cmp(&is_manifest(a))).await.unwrap();
+
+    let reader = ZipFileReader::new(data).await.unwrap();
+    let names: Vec<_> = reader.file().entries().iter().map(|entry| entry.filename().as_str().unwrap()).collect();
+    assert_eq!(names, ["manifest.json", "b.txt", "c.txt", "a.txt"]);
+
+    // Each entry still points at its own data, despite no longer being in write order.
+    for (index, name) in names.iter().enumerate() {
+        let mut buffer = Vec::new();
+        reader.reader_with_entry(index).await.unwrap().read_to_end_checked(&mut buffer).await.unwrap();
+        assert_eq!(buffer, name.as_bytes());
+    }
+}
@@ -0,0 +1,35 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::mem::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::error::ZipError;
+use crate::{Compression, ZipEntryBuilder};
+
+#[tokio::test]
+async fn pad_to_alignment() {
+    for alignment in [1, 7, 512, 4096] {
+        let mut writer = ZipFileWriter::new(Vec::new()).pad_to(alignment);
+        writer.comment(String::from("foo"));
+
+        let entry = ZipEntryBuilder::new("foo.txt".into(), Compression::Stored);
+        writer.write_entry_whole(entry, b"foo bar").await.unwrap();
+
+        let data = writer.close().await.unwrap();
+        assert_eq!(data.len() as u64 % alignment, 0);
+
+        let reader = ZipFileReader::new(data).await.unwrap();
+        assert_eq!(reader.file().entries().len(), 1);
+        assert!(reader.file().comment().as_bytes().starts_with(b"foo"));
+    }
+}
+
+#[tokio::test]
+async fn pad_to_too_large() {
+    let mut writer = ZipFileWriter::new(Vec::new()).pad_to(u64::MAX);
+
+    let entry = ZipEntryBuilder::new("foo.txt".into(), Compression::Stored);
+    writer.write_entry_whole(entry, b"foo bar").await.unwrap();
+
+    assert!(matches!(writer.close().await, Err(ZipError::CommentTooLarge)));
+}
@@ -0,0 +1,59 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::write::ZipFileWriter;
+use crate::error::ZipError;
+use crate::{Compression, ZipEntryBuilder};
+
+#[tokio::test]
+async fn write_entry_precompressed_stored() {
+    let data = b"foo bar baz";
+    let mut writer = ZipFileWriter::new(Vec::new()).reject_duplicate_names();
+
+    let entry = ZipEntryBuilder::new("foo.txt".into(), Compression::Stored);
+    writer.write_entry_precompressed(entry.clone(), data, crc32fast::hash(data), data.len() as u64).await.unwrap();
+
+    let result = writer.write_entry_precompressed(entry, data, crc32fast::hash(data), data.len() as u64).await;
+    assert!(matches!(result, Err(ZipError::DuplicateFileName(_))));
+
+    let reader = writer.close_into_reader().await.unwrap();
+    let entry = &reader.file().entries()[0];
+    assert_eq!(entry.crc32(), crc32fast::hash(data));
+    assert_eq!(entry.uncompressed_size(), data.len() as u64);
+
+    let mut buffer = Vec::new();
+    reader.reader_with_entry(0).await.unwrap().read_to_end_checked(&mut buffer).await.unwrap();
+    assert_eq!(buffer, data);
+}
+
+#[cfg(feature = "deflate")]
+#[tokio::test]
+async fn write_entry_precompressed_deflate() {
+    let data = [b'a'; 1000];
+    let prepared = ZipEntryBuilder::new("foo.txt".into(), Compression::Deflate).prepare(&data).await.unwrap();
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+    let entry = ZipEntryBuilder::new("foo.txt".into(), Compression::Deflate);
+    writer.write_entry_precompressed(entry, prepared.data(), prepared.crc32(), 1000).await.unwrap();
+
+    let reader = writer.close_into_reader().await.unwrap();
+    let entry = &reader.file().entries()[0];
+    assert_eq!(entry.compressed_size(), prepared.compressed_size());
+    assert_eq!(entry.uncompressed_size(), 1000);
+
+    let mut buffer = Vec::new();
+    reader.reader_with_entry(0).await.unwrap().read_to_end_checked(&mut buffer).await.unwrap();
+    assert_eq!(buffer, data);
+}
+
+#[tokio::test]
+async fn write_entry_precompressed_bad_crc() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    let entry = ZipEntryBuilder::new("foo.txt".into(), Compression::Stored);
+    writer.write_entry_precompressed(entry, b"foo", 0, 3).await.unwrap();
+
+    let reader = writer.close_into_reader().await.unwrap();
+    let mut buffer = Vec::new();
+    let result = reader.reader_with_entry(0).await.unwrap().read_to_end_checked(&mut buffer).await;
+    assert!(result.is_err());
+}
@@ -0,0 +1,66 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::mem::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::error::ZipError;
+use crate::{Compression, ZipEntryBuilder};
+
+async fn write_prepared(compression: Compression) -> Vec<u8> {
+    let handles: Vec<_> = (0..8)
+        .map(|index| {
+            tokio::spawn(async move {
+                let data = format!("entry {index} ").repeat(100);
+                let builder = ZipEntryBuilder::new(format!("{index}.txt").into(), compression);
+                builder.prepare(data.as_bytes()).await.unwrap()
+            })
+        })
+        .collect();
+
+    let mut writer = ZipFileWriter::new(Vec::new()).auto_text_detection();
+    for handle in handles {
+        writer.write_prepared(handle.await.unwrap()).await.unwrap();
+    }
+    writer.close().await.unwrap()
+}
+
+async fn assert_entries(archive: Vec<u8>, compression: Compression) {
+    let reader = ZipFileReader::new(archive).await.unwrap();
+    assert_eq!(reader.file().entries().len(), 8);
+
+    for (index, entry) in reader.file().entries().iter().enumerate() {
+        assert_eq!(entry.filename().as_str().unwrap(), format!("{index}.txt"));
+        assert_eq!(entry.compression(), compression);
+        assert_eq!(entry.internal_file_attribute() & 0x1, 0x1);
+
+        let mut data = String::new();
+        reader.reader_with_entry(index).await.unwrap().read_to_string_checked(&mut data).await.unwrap();
+        assert_eq!(data, format!("entry {index} ").repeat(100));
+    }
+}
+
+#[tokio::test]
+async fn write_prepared_stored() {
+    assert_entries(write_prepared(Compression::Stored).await, Compression::Stored).await;
+}
+
+#[cfg(feature = "deflate")]
+#[tokio::test]
+async fn write_prepared_deflate() {
+    let prepared = ZipEntryBuilder::new("foo.txt".into(), Compression::Deflate).prepare(&[b'a'; 1000]).await.unwrap();
+    assert_eq!(prepared.uncompressed_size(), 1000);
+    assert!(prepared.compressed_size() < 1000);
+
+    assert_entries(write_prepared(Compression::Deflate).await, Compression::Deflate).await;
+}
+
+#[tokio::test]
+async fn write_prepared_duplicate() {
+    let mut writer = ZipFileWriter::new(Vec::new()).reject_duplicate_names();
+
+    let prepared = ZipEntryBuilder::new("foo.txt".into(), Compression::Stored).prepare(b"foo").await.unwrap();
+    writer.write_prepared(prepared.clone()).await.unwrap();
+
+    let result = writer.write_prepared(prepared).await;
+    assert!(matches!(result, Err(ZipError::DuplicateFileName(_))));
+}
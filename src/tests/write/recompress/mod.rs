@@ -0,0 +1,74 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::seek::ZipFileReader;
+use crate::base::write::{recompress, ZipFileWriter};
+use crate::{Compression, ZipEntryBuilder};
+
+use futures_lite::io::Cursor;
+
+#[tokio::test]
+async fn recompress_deflate_to_stored() {
+    let data = b"Hello, world! Hello, world! Hello, world!".repeat(100);
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+    writer.comment("foo".to_string());
+    let entry = ZipEntryBuilder::new("dir/".into(), Compression::Deflate);
+    writer.write_entry_whole(entry, &[]).await.unwrap();
+    let entry =
+        ZipEntryBuilder::new("dir/foo.txt".into(), Compression::Deflate).comment("bar".into()).unix_permissions(0o644);
+    writer.write_entry_whole(entry, &data).await.unwrap();
+
+    let mut reader = ZipFileReader::new(Cursor::new(writer.close().await.unwrap())).await.unwrap();
+    let mut writer = ZipFileWriter::new(Vec::new());
+    recompress(&mut reader, &mut writer, Compression::Stored).await.unwrap();
+
+    let mut rewritten = ZipFileReader::new(Cursor::new(writer.close().await.unwrap())).await.unwrap();
+    assert_eq!(rewritten.file().comment().as_str().unwrap(), "foo");
+
+    let entries = rewritten.file().entries();
+    assert_eq!(entries.len(), 2);
+    assert!(entries[0].dir().unwrap());
+
+    let entry = &entries[1];
+    let original = &reader.file().entries()[1];
+    assert_eq!(entry.compression(), Compression::Stored);
+    assert_eq!(entry.filename().as_str().unwrap(), "dir/foo.txt");
+    assert_eq!(entry.comment().as_str().unwrap(), "bar");
+    assert_eq!(entry.unix_permissions(), Some(0o644));
+    assert_eq!(entry.last_modification_date(), original.last_modification_date());
+    assert_eq!(entry.crc32(), original.crc32());
+
+    let mut buffer = Vec::new();
+    rewritten.reader_with_entry(1).await.unwrap().read_to_end_checked(&mut buffer).await.unwrap();
+    assert_eq!(buffer, data);
+}
+
+#[cfg(feature = "crc32c")]
+#[tokio::test]
+async fn recompress_crc32c() {
+    use crate::spec::header::{Crc32cExtraField, ExtraField};
+
+    let data = b"Hello, world!";
+
+    // An existing field isn't trusted, so this incorrect value shouldn't be carried over.
+    let mut writer = ZipFileWriter::new(Vec::new());
+    let field = ExtraField::Crc32c(Crc32cExtraField { crc32c: 1234 });
+    let entry = ZipEntryBuilder::new("foo.txt".into(), Compression::Stored).extra_fields(vec![field]);
+    writer.write_entry_whole(entry, data).await.unwrap();
+    let archive = writer.close().await.unwrap();
+
+    let mut reader = ZipFileReader::new(Cursor::new(archive.clone())).await.unwrap();
+    let mut writer = ZipFileWriter::new(Vec::new());
+    recompress(&mut reader, &mut writer, Compression::Stored).await.unwrap();
+    let rewritten = ZipFileReader::new(Cursor::new(writer.close().await.unwrap())).await.unwrap();
+    assert_eq!(rewritten.file().entries()[0].crc32c(), None);
+
+    let mut reader = ZipFileReader::new(Cursor::new(archive)).await.unwrap();
+    let mut writer = ZipFileWriter::new(Vec::new()).crc32c();
+    recompress(&mut reader, &mut writer, Compression::Stored).await.unwrap();
+    let rewritten = ZipFileReader::new(Cursor::new(writer.close().await.unwrap())).await.unwrap();
+    let fields = rewritten.file().entries()[0].extra_fields();
+    assert_eq!(fields.iter().filter(|field| matches!(field, ExtraField::Crc32c(_))).count(), 1);
+    assert_eq!(rewritten.file().entries()[0].crc32c(), Some(crc32c::crc32c(data)));
+}
@@ -0,0 +1,40 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::write::ZipFileWriter;
+use crate::error::ZipError;
+use crate::{Compression, ZipEntryBuilder};
+
+use futures_lite::io::AsyncWriteExt;
+
+#[tokio::test]
+async fn entry_scope() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    let entry = ZipEntryBuilder::new("foo.txt".into(), Compression::Stored);
+    let written = writer
+        .entry_scope(entry, async |entry_writer| {
+            entry_writer.write_all(b"foo").await?;
+            Ok(3)
+        })
+        .await
+        .unwrap();
+    assert_eq!(written, 3);
+
+    // The entry is still closed when the closure returns an error part way through.
+    let entry = ZipEntryBuilder::new("bar.txt".into(), Compression::Stored);
+    let result: Result<(), _> = writer
+        .entry_scope(entry, async |entry_writer| {
+            entry_writer.write_all(b"ba").await?;
+            Err(ZipError::FeatureNotSupported("bar"))
+        })
+        .await;
+    assert!(matches!(result, Err(ZipError::FeatureNotSupported("bar"))));
+
+    let reader = writer.close_into_reader().await.unwrap();
+    for (index, expected) in [&b"foo"[..], b"ba"].into_iter().enumerate() {
+        let mut data = Vec::new();
+        reader.reader_with_entry(index).await.unwrap().read_to_end_checked(&mut data).await.unwrap();
+        assert_eq!(data, expected);
+    }
+}
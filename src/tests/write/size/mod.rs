@@ -0,0 +1,86 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::write::ZipFileWriter;
+use crate::{Compression, ZipEntry, ZipEntryBuilder, ZipString};
+
+fn entries() -> Vec<(ZipEntry, Vec<u8>)> {
+    vec![
+        (ZipEntryBuilder::new("foo.txt".into(), Compression::Stored).build(), b"foo".to_vec()),
+        (
+            ZipEntryBuilder::new("bar/baz.txt".into(), Compression::Stored)
+                .comment("a comment".into())
+                .metadata("foo", "bar")
+                .build(),
+            vec![0; 1000],
+        ),
+        (
+            ZipEntryBuilder::new(
+                ZipString::new_with_alternative("ünicode".into(), b"unicode".to_vec()),
+                Compression::Stored,
+            )
+            .build(),
+            Vec::new(),
+        ),
+    ]
+}
+
+async fn check(mut writer: ZipFileWriter<Vec<u8>>) {
+    let entry = ZipEntryBuilder::new("first.txt".into(), Compression::Stored);
+    writer.write_entry_whole(entry, b"already written").await.unwrap();
+
+    let entries = entries();
+    let sizes: Vec<_> = entries.iter().map(|(entry, data)| (entry.clone(), data.len() as u64)).collect();
+    let expected = writer.stored_archive_size(&sizes).unwrap();
+
+    for (entry, data) in entries {
+        writer.write_entry_whole(entry, &data).await.unwrap();
+    }
+
+    assert_eq!(writer.close().await.unwrap().len() as u64, expected);
+}
+
+#[tokio::test]
+async fn stored_archive_size() {
+    check(ZipFileWriter::new(Vec::new())).await;
+
+    let mut writer = ZipFileWriter::new(Vec::new()).force_zip64().pad_to(512);
+    writer.comment(String::from("an archive comment"));
+    check(writer).await;
+
+    #[cfg(feature = "crc32c")]
+    check(ZipFileWriter::new(Vec::new()).crc32c()).await;
+}
+
+#[tokio::test]
+async fn reserve_stored_archive_size() {
+    let entries = entries();
+    let sizes: Vec<_> = entries.iter().map(|(entry, data)| (entry.clone(), data.len() as u64)).collect();
+
+    let mut writer = ZipFileWriter::with_capacity(16);
+    assert!(writer.inner_mut().capacity() >= 16);
+
+    let expected = writer.stored_archive_size(&sizes).unwrap();
+    writer.reserve(expected as usize);
+    let capacity = writer.inner_mut().capacity();
+
+    for (entry, data) in entries {
+        writer.write_entry_whole(entry, &data).await.unwrap();
+    }
+
+    // The output buffer should never have needed to reallocate.
+    let data = writer.close().await.unwrap();
+    assert_eq!(data.len() as u64, expected);
+    assert_eq!(data.capacity(), capacity);
+}
+
+#[cfg(feature = "deflate")]
+#[tokio::test]
+async fn stored_archive_size_compressed_errors() {
+    use crate::error::ZipError;
+
+    let writer = ZipFileWriter::new(Vec::new());
+    let entry = ZipEntryBuilder::new("foo.txt".into(), Compression::Deflate).build();
+
+    assert!(matches!(writer.stored_archive_size(&[(entry, 3)]), Err(ZipError::FeatureNotSupported(_))));
+}
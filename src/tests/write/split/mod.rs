@@ -0,0 +1,83 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::write::{SplitZipFileWriter, MIN_VOLUME_SIZE};
+use crate::spec::consts::{CDH_SIGNATURE, DATA_DESCRIPTOR_SIGNATURE, EOCDR_SIGNATURE, LFH_SIGNATURE};
+use crate::{Compression, ZipEntryBuilder};
+
+fn u16_at(data: &[u8], offset: usize) -> u16 {
+    u16::from_le_bytes(data[offset..offset + 2].try_into().unwrap())
+}
+
+fn u32_at(data: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap())
+}
+
+#[tokio::test]
+async fn split_volumes() {
+    let mut next_volume = 0;
+    let mut writer = SplitZipFileWriter::new(MIN_VOLUME_SIZE, |volume| {
+        assert_eq!(volume, next_volume);
+        next_volume += 1;
+        async { Ok(Vec::new()) }
+    });
+
+    let data: Vec<u8> = (0..150_000u32).map(|i| (i % 251) as u8).collect();
+    let entry = ZipEntryBuilder::new("foo.bin".into(), Compression::Stored);
+    writer.write_entry_whole(entry, &data).await.unwrap();
+
+    let entry = ZipEntryBuilder::new("bar.bin".into(), Compression::Stored);
+    writer.write_entry_whole(entry, &data[..50_000]).await.unwrap();
+
+    let volumes = writer.close().await.unwrap();
+    assert_eq!(volumes.len(), 4);
+    assert_eq!(u32_at(&volumes[0], 0), DATA_DESCRIPTOR_SIGNATURE);
+    assert_eq!(u32_at(&volumes[0], 4), LFH_SIGNATURE);
+    assert!(volumes.iter().all(|volume| volume.len() as u64 <= MIN_VOLUME_SIZE));
+    assert!(volumes[..3].iter().all(|volume| volume.len() as u64 == MIN_VOLUME_SIZE));
+
+    // The EOCDR must be wholly contained within the final volume.
+    let last = volumes.last().unwrap();
+    let eocdr = last.len() - 22;
+    assert_eq!(u32_at(last, eocdr), EOCDR_SIGNATURE);
+    assert_eq!(u16_at(last, eocdr + 4), 3);
+    assert_eq!(u16_at(last, eocdr + 10), 2);
+
+    let cd_disk = u16_at(last, eocdr + 6) as usize;
+    let cd_offset = u32_at(last, eocdr + 16) as usize;
+    assert_eq!(u32_at(&volumes[cd_disk], cd_offset), CDH_SIGNATURE);
+
+    // Each central directory header should point at its local file header within the relevant volume.
+    let first_cdh = &volumes[cd_disk][cd_offset..];
+    assert_eq!(u16_at(first_cdh, 34), 0);
+    assert_eq!(u32_at(first_cdh, 42), 4);
+
+    let second_disk = (150_000 + 4 + 30 + 7) / MIN_VOLUME_SIZE as usize;
+    let second_offset = (150_000 + 4 + 30 + 7) % MIN_VOLUME_SIZE as usize;
+    let second_cdh = &first_cdh[46 + 7..];
+    assert_eq!(u32_at(second_cdh, 0), CDH_SIGNATURE);
+    assert_eq!(u16_at(second_cdh, 34) as usize, second_disk);
+    assert_eq!(u32_at(second_cdh, 42) as usize, second_offset);
+    assert_eq!(u32_at(&volumes[second_disk], second_offset), LFH_SIGNATURE);
+}
+
+#[tokio::test]
+async fn split_central_directory_boundary() {
+    let mut writer = SplitZipFileWriter::new(MIN_VOLUME_SIZE, |_| async { Ok(Vec::new()) });
+
+    // Leave 60 bytes at the end of the first volume, which is too few for the entry's central directory header.
+    let data = vec![0; MIN_VOLUME_SIZE as usize - 4 - 30 - 5 - 60];
+    let entry = ZipEntryBuilder::new("a.bin".into(), Compression::Stored).comment("c".repeat(100).into());
+    writer.write_entry_whole(entry, &data).await.unwrap();
+
+    let volumes = writer.close().await.unwrap();
+    assert_eq!(volumes.len(), 2);
+    assert_eq!(volumes[0].len() as u64, MIN_VOLUME_SIZE - 60);
+
+    let last = volumes.last().unwrap();
+    let eocdr = last.len() - 22;
+    assert_eq!(u32_at(last, eocdr), EOCDR_SIGNATURE);
+    assert_eq!(u16_at(last, eocdr + 6), 1);
+    assert_eq!(u32_at(last, eocdr + 16), 0);
+    assert_eq!(u32_at(last, 0), CDH_SIGNATURE);
+}
@@ -0,0 +1,45 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::mem::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::{Compression, ZipEntryBuilder};
+
+use futures_lite::io::AsyncWriteExt;
+
+#[tokio::test]
+async fn store_extensions() {
+    let mut writer = ZipFileWriter::new(Vec::new()).store_extensions(&["jpg", ".GZ"]);
+
+    for filename in ["photo.jpg", "dir/PHOTO.JPG", "archive.tar.gz", "notes.txt", "jpg", "dir.jpg/notes"] {
+        let entry = ZipEntryBuilder::new(filename.into(), Compression::Deflate);
+        writer.write_entry_whole(entry, b"foo bar").await.unwrap();
+    }
+
+    let entry = ZipEntryBuilder::new("streamed.jpg".into(), Compression::Deflate);
+    let mut entry_writer = writer.write_entry_stream(entry).await.unwrap();
+    entry_writer.write_all(b"foo bar").await.unwrap();
+    entry_writer.close().await.unwrap();
+
+    let reader = ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+    let compressions: Vec<_> = reader.file().entries().iter().map(|entry| entry.compression()).collect();
+
+    assert_eq!(
+        compressions,
+        [
+            Compression::Stored,
+            Compression::Stored,
+            Compression::Stored,
+            Compression::Deflate,
+            Compression::Deflate,
+            Compression::Deflate,
+            Compression::Stored,
+        ]
+    );
+
+    for index in 0..compressions.len() {
+        let mut data = Vec::new();
+        reader.reader_with_entry(index).await.unwrap().read_to_end_checked(&mut data).await.unwrap();
+        assert_eq!(data, b"foo bar");
+    }
+}
@@ -0,0 +1,207 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::mem::ZipFileReader;
+use crate::base::write::ZipFileWriter;
+use crate::{Compression, ZipEntryBuilder};
+
+use futures_lite::io::Cursor;
+
+#[tokio::test]
+async fn write_entry_from_reader() {
+    let data: Vec<u8> = (0..200_000u32).map(|i| (i % 251) as u8).collect();
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    let entry = ZipEntryBuilder::new("foo.bin".into(), Compression::Stored);
+    writer.write_entry_from_reader(entry, Cursor::new(&data)).await.unwrap();
+    let entry = ZipEntryBuilder::new("empty".into(), Compression::Stored);
+    writer.write_entry_from_reader(entry, Cursor::new(&[])).await.unwrap();
+
+    let reader = ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+    assert_eq!(reader.file().entries().len(), 2);
+    assert_eq!(reader.file().entries()[0].uncompressed_size(), data.len() as u64);
+
+    let mut buffer = Vec::new();
+    reader.reader_with_entry(0).await.unwrap().read_to_end_checked(&mut buffer).await.unwrap();
+    assert_eq!(buffer, data);
+
+    let mut buffer = Vec::new();
+    reader.reader_with_entry(1).await.unwrap().read_to_end_checked(&mut buffer).await.unwrap();
+    assert!(buffer.is_empty());
+}
+
+/// A writer which exposes the number of bytes written to it whilst it's borrowed by an entry writer.
+#[cfg(feature = "deflate")]
+struct SharedWriter(std::sync::Arc<std::sync::Mutex<Vec<u8>>>);
+
+#[cfg(feature = "deflate")]
+impl futures_lite::io::AsyncWrite for SharedWriter {
+    fn poll_write(
+        self: std::pin::Pin<&mut Self>,
+        _: &mut std::task::Context<'_>,
+        buf: &[u8],
+    ) -> std::task::Poll<std::io::Result<usize>> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        std::task::Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(
+        self: std::pin::Pin<&mut Self>,
+        _: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<std::io::Result<()>> {
+        std::task::Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(
+        self: std::pin::Pin<&mut Self>,
+        _: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<std::io::Result<()>> {
+        std::task::Poll::Ready(Ok(()))
+    }
+}
+
+#[cfg(feature = "deflate")]
+#[tokio::test]
+async fn flush_stream_entry_data() {
+    use futures_lite::io::AsyncWriteExt;
+
+    let shared = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let mut writer = ZipFileWriter::new(SharedWriter(shared.clone()));
+
+    let entry = ZipEntryBuilder::new("foo.txt".into(), Compression::Deflate);
+    let mut entry_writer = writer.write_entry_stream(entry).await.unwrap();
+
+    entry_writer.write_all(b"first chunk of data").await.unwrap();
+    let before = shared.lock().unwrap().len();
+    entry_writer.flush_data().await.unwrap();
+    let after = shared.lock().unwrap().len();
+    assert!(after > before);
+
+    entry_writer.write_all(b", second chunk of data").await.unwrap();
+    entry_writer.close().await.unwrap();
+    writer.close().await.unwrap();
+
+    let data = shared.lock().unwrap().clone();
+    let reader = ZipFileReader::new(data).await.unwrap();
+    let mut buffer = Vec::new();
+    reader.reader_with_entry(0).await.unwrap().read_to_end_checked(&mut buffer).await.unwrap();
+    assert_eq!(buffer, b"first chunk of data, second chunk of data");
+}
+
+#[tokio::test]
+async fn write_entry_from_reader_buffer_size() {
+    let data: Vec<u8> = (0..10_000u32).map(|i| (i % 251) as u8).collect();
+
+    for size in [0, 7, 1024 * 1024] {
+        let mut writer = ZipFileWriter::new(Vec::new()).with_copy_buffer_size(size);
+        let entry = ZipEntryBuilder::new("foo.bin".into(), Compression::Stored);
+        writer.write_entry_from_reader(entry, Cursor::new(&data)).await.unwrap();
+
+        let reader = ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+        let mut buffer = Vec::new();
+        reader.reader_with_entry(0).await.unwrap().read_to_end_checked(&mut buffer).await.unwrap();
+        assert_eq!(buffer, data, "for a buffer size of {size}");
+    }
+}
+
+#[tokio::test]
+async fn close_and_patch() {
+    use crate::base::read::stream::ZipFileReader as StreamZipFileReader;
+    use futures_lite::io::AsyncWriteExt;
+
+    for force_no_zip64 in [true, false] {
+        let mut writer = ZipFileWriter::new(Cursor::new(Vec::new()));
+        if force_no_zip64 {
+            writer = writer.force_no_zip64();
+        }
+
+        for name in ["foo.txt", "bar.txt"] {
+            let entry = ZipEntryBuilder::new(name.into(), Compression::Stored);
+            let mut entry_writer = writer.write_entry_stream(entry).await.unwrap();
+            entry_writer.write_all(name.as_bytes()).await.unwrap();
+            entry_writer.close_and_patch().await.unwrap();
+        }
+        let data = writer.close().await.unwrap().into_inner();
+
+        let reader = ZipFileReader::new(data.clone()).await.unwrap();
+        for (index, entry) in reader.file().entries().iter().enumerate() {
+            assert!(!entry.data_descriptor);
+            assert_eq!(entry.compressed_size(), 7);
+
+            let mut buffer = Vec::new();
+            reader.reader_with_entry(index).await.unwrap().read_to_end_checked(&mut buffer).await.unwrap();
+            assert_eq!(buffer, entry.filename().as_bytes());
+        }
+
+        // The stream reader relies on the sizes within the local file header when there's no data descriptor.
+        let mut reader = StreamZipFileReader::new(Cursor::new(data));
+        let mut names = Vec::new();
+        while let Some(mut entry_reader) = reader.next_with_entry().await.unwrap() {
+            let mut buffer = Vec::new();
+            entry_reader.reader_mut().read_to_end_checked(&mut buffer).await.unwrap();
+            names.push(String::from_utf8(buffer).unwrap());
+            reader = entry_reader.done().await.unwrap();
+        }
+        assert_eq!(names, ["foo.txt", "bar.txt"]);
+    }
+}
+
+#[tokio::test]
+async fn lazy_zip64_stream() {
+    use futures_lite::io::AsyncWriteExt;
+
+    let mut writer = ZipFileWriter::new(Vec::new()).lazy_zip64_stream();
+    let entry = ZipEntryBuilder::new("foo.txt".into(), Compression::Stored);
+    let mut entry_writer = writer.write_entry_stream(entry).await.unwrap();
+    entry_writer.write_all(b"foo").await.unwrap();
+    entry_writer.close().await.unwrap();
+
+    let reader = ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+    assert!(!reader.file().zip64());
+
+    let entry = &reader.file().entries()[0];
+    assert!(entry.extra_fields().is_empty());
+    assert_eq!(entry.compressed_size(), 3);
+    assert_eq!(entry.uncompressed_size(), 3);
+
+    let mut buffer = Vec::new();
+    reader.reader_with_entry(0).await.unwrap().read_to_end_checked(&mut buffer).await.unwrap();
+    assert_eq!(buffer, b"foo");
+}
+
+#[cfg(feature = "sha2")]
+#[tokio::test]
+async fn close_with_digest() {
+    use futures_lite::io::AsyncWriteExt;
+    use sha2::{Digest, Sha256};
+
+    let mut writer = ZipFileWriter::new(Cursor::new(Vec::new()));
+    let entry = ZipEntryBuilder::new("foo.txt".into(), Compression::Stored);
+    let mut entry_writer = writer.write_entry_stream(entry).await.unwrap().with_digest(Sha256::new()).unwrap();
+    entry_writer.write_all(b"foo ").await.unwrap();
+    entry_writer.write_all(b"bar").await.unwrap();
+    let digest = entry_writer.close_with_digest().await.unwrap();
+
+    assert_eq!(digest.sha256().unwrap()[..], Sha256::digest(b"foo bar")[..]);
+    assert_eq!(digest.crc32(), crc32fast::hash(b"foo bar"));
+
+    let data = writer.close().await.unwrap().into_inner();
+    let reader = ZipFileReader::new(data).await.unwrap();
+    assert_eq!(reader.file().entries()[0].crc32(), digest.crc32());
+}
+
+#[cfg(feature = "sha2")]
+#[tokio::test]
+async fn with_digest_after_write() {
+    use crate::error::ZipError;
+    use futures_lite::io::AsyncWriteExt;
+    use sha2::{Digest, Sha256};
+
+    let mut writer = ZipFileWriter::new(Cursor::new(Vec::new()));
+    let entry = ZipEntryBuilder::new("foo.txt".into(), Compression::Stored);
+    let mut entry_writer = writer.write_entry_stream(entry).await.unwrap();
+    entry_writer.write_all(b"foo").await.unwrap();
+
+    let result = entry_writer.with_digest(Sha256::new());
+    assert!(matches!(result, Err(ZipError::FeatureNotSupported(_))));
+}
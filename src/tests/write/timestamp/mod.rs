@@ -0,0 +1,54 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::write::ZipFileWriter;
+use crate::{Compression, ZipDateTimeBuilder, ZipEntryBuilder};
+
+use futures_lite::io::AsyncWriteExt;
+
+#[tokio::test]
+async fn emit_extended_timestamp() {
+    let date = ZipDateTimeBuilder::new().year(2024).month(2).day(29).hour(12).minute(34).second(56).build();
+    let mut writer = ZipFileWriter::new(Vec::new()).emit_extended_timestamp(true);
+
+    let entry = ZipEntryBuilder::new("whole.txt".into(), Compression::Stored).last_modification_date(date);
+    writer.write_entry_whole(entry, b"foo").await.unwrap();
+
+    let entry = ZipEntryBuilder::new("stream.txt".into(), Compression::Stored).last_modification_date(date);
+    let mut entry_writer = writer.write_entry_stream(entry).await.unwrap();
+    entry_writer.write_all(b"bar").await.unwrap();
+    entry_writer.close().await.unwrap();
+
+    // Entries without a valid date don't have a field added.
+    let entry = ZipEntryBuilder::new("undated.txt".into(), Compression::Stored);
+    writer.write_entry_whole(entry, b"baz").await.unwrap();
+
+    // Nor do those with a date beyond what the field can store.
+    let date = ZipDateTimeBuilder::new().year(2107).month(1).day(1).build();
+    let entry = ZipEntryBuilder::new("future.txt".into(), Compression::Stored).last_modification_date(date);
+    writer.write_entry_whole(entry, b"qux").await.unwrap();
+
+    let reader = writer.close_into_reader().await.unwrap();
+    let entries = reader.file().entries();
+    assert_eq!(entries[0].extended_modification_time(), Some(1709210096));
+    assert_eq!(entries[1].extended_modification_time(), Some(1709210096));
+    assert_eq!(entries[2].extended_modification_time(), None);
+    assert_eq!(entries[3].extended_modification_time(), None);
+    assert!(entries[3].extra_fields().is_empty());
+
+    for index in 0..2 {
+        let mut data = String::new();
+        reader.reader_with_entry(index).await.unwrap().read_to_string_checked(&mut data).await.unwrap();
+    }
+}
+
+#[tokio::test]
+async fn emit_extended_timestamp_stored_archive_size() {
+    let date = ZipDateTimeBuilder::new().year(2024).month(2).day(29).build();
+    let entry = ZipEntryBuilder::new("foo.txt".into(), Compression::Stored).last_modification_date(date).build();
+
+    let mut writer = ZipFileWriter::new(Vec::new()).emit_extended_timestamp(true);
+    let size = writer.stored_archive_size(&[(entry.clone(), 3)]).unwrap();
+    writer.write_entry_whole(entry, b"foo").await.unwrap();
+    assert_eq!(writer.close().await.unwrap().len() as u64, size);
+}
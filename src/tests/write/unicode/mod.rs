@@ -0,0 +1,97 @@
+// Copyright (c) 2024 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::base::read::mem::ZipFileReader;
+use crate::base::write::{UnicodeStrategy, ZipFileWriter};
+use crate::spec::header::ExtraField;
+use crate::{Compression, ZipEntryBuilder, ZipString};
+
+/// Writes a single entry with the provided filename via both a whole & a stream write, returning both archives.
+async fn write_archives(strategy: UnicodeStrategy, filename: ZipString) -> Vec<Vec<u8>> {
+    let entry = ZipEntryBuilder::new(filename, Compression::Stored);
+
+    let mut writer = ZipFileWriter::new(Vec::new()).unicode_strategy(strategy);
+    writer.write_entry_whole(entry.clone(), b"foo").await.unwrap();
+    let whole = writer.close().await.unwrap();
+
+    let mut writer = ZipFileWriter::new(Vec::new()).unicode_strategy(strategy);
+    writer.write_entry_stream(entry).await.unwrap().close().await.unwrap();
+    let stream = writer.close().await.unwrap();
+
+    vec![whole, stream]
+}
+
+/// Returns whether the UTF-8 flag is set within the first entry's local file header.
+fn has_utf8_flag(data: &[u8]) -> bool {
+    u16::from_le_bytes([data[6], data[7]]) & 0x800 != 0
+}
+
+async fn has_unicode_extra_field(data: Vec<u8>) -> bool {
+    let reader = ZipFileReader::new(data).await.unwrap();
+    let entry = reader.file().entries()[0].clone();
+
+    entry.extra_fields().iter().any(|field| matches!(field, ExtraField::InfoZipUnicodePath(_)))
+}
+
+#[tokio::test]
+async fn unicode_strategy_both() {
+    for data in write_archives(UnicodeStrategy::Both, "héllo.txt".into()).await {
+        assert!(has_utf8_flag(&data));
+        assert!(!has_unicode_extra_field(data).await);
+    }
+
+    let filename = ZipString::new_with_alternative("héllo.txt".into(), b"h\x82llo.txt".to_vec());
+    for data in write_archives(UnicodeStrategy::Both, filename).await {
+        assert!(!has_utf8_flag(&data));
+        assert!(has_unicode_extra_field(data).await);
+    }
+}
+
+#[tokio::test]
+async fn unicode_strategy_utf8_flag_only() {
+    let filename = ZipString::new_with_alternative("héllo.txt".into(), b"h\x82llo.txt".to_vec());
+
+    for data in write_archives(UnicodeStrategy::Utf8FlagOnly, filename).await {
+        assert!(has_utf8_flag(&data));
+
+        let reader = ZipFileReader::new(data.clone()).await.unwrap();
+        let filename = reader.file().entries()[0].filename();
+        assert_eq!(filename.as_str().unwrap(), "héllo.txt");
+        assert!(filename.alternative().is_none());
+
+        assert!(!has_unicode_extra_field(data).await);
+    }
+}
+
+#[tokio::test]
+async fn unicode_strategy_extra_fields_only() {
+    for data in write_archives(UnicodeStrategy::ExtraFieldsOnly, "héllo.txt".into()).await {
+        assert!(!has_utf8_flag(&data));
+
+        let reader = ZipFileReader::new(data.clone()).await.unwrap();
+        assert_eq!(reader.file().entries()[0].filename().as_str().unwrap(), "héllo.txt");
+
+        assert!(has_unicode_extra_field(data).await);
+    }
+
+    // ASCII filenames need neither the flag nor the extra field.
+    for data in write_archives(UnicodeStrategy::ExtraFieldsOnly, "hello.txt".into()).await {
+        assert!(!has_utf8_flag(&data));
+        assert!(!has_unicode_extra_field(data).await);
+    }
+}
+
+#[tokio::test]
+async fn utf8_flag_override() {
+    for (filename, enabled) in [("hello.txt", true), ("héllo.txt", false)] {
+        let entry = ZipEntryBuilder::new(filename.into(), Compression::Stored).utf8_flag(enabled);
+
+        let mut writer = ZipFileWriter::new(Vec::new());
+        writer.write_entry_whole(entry.clone(), b"foo").await.unwrap();
+        assert_eq!(has_utf8_flag(&writer.close().await.unwrap()), enabled);
+
+        let mut writer = ZipFileWriter::new(Vec::new());
+        writer.write_entry_stream(entry).await.unwrap().close().await.unwrap();
+        assert_eq!(has_utf8_flag(&writer.close().await.unwrap()), enabled);
+    }
+}
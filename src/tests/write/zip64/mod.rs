@@ -48,6 +48,102 @@ async fn test_write_zip64_file() {
     assert_eq!(buffer.as_slice(), &[0, 0, 0, 0]);
 }
 
+/// Test that only streamed entries (which always carry a zip64 extended field) are reported as zip64 when read back.
+#[tokio::test]
+async fn test_write_zip64_entry_flag() {
+    init_logger();
+
+    let mut buffer = Vec::new();
+    let mut writer = ZipFileWriter::new(&mut buffer);
+    let entry = ZipEntryBuilder::new("file1".to_string().into(), Compression::Stored);
+    writer.write_entry_whole(entry, &[0, 0, 0, 0]).await.unwrap();
+    let entry = ZipEntryBuilder::new("file2".to_string().into(), Compression::Stored);
+    let mut entry_writer = writer.write_entry_stream(entry).await.unwrap();
+    entry_writer.write_all(&[0, 0, 0, 0]).await.unwrap();
+    entry_writer.close().await.unwrap();
+    writer.close().await.unwrap();
+
+    let reader = crate::base::read::mem::ZipFileReader::new(buffer).await.unwrap();
+    assert!(!reader.file().entries()[0].is_zip64());
+    assert!(reader.file().entries()[1].is_zip64());
+}
+
+/// Writes a small archive containing a whole and a streamed entry with the provided writer options.
+async fn write_small_archive(options: fn(ZipFileWriter<&mut Vec<u8>>) -> ZipFileWriter<&mut Vec<u8>>) -> Vec<u8> {
+    let mut buffer = Vec::new();
+    let mut writer = options(ZipFileWriter::new(&mut buffer));
+    let entry = ZipEntryBuilder::new("file1".to_string().into(), Compression::Stored);
+    writer.write_entry_whole(entry, &[0, 0, 0, 0]).await.unwrap();
+    let entry = ZipEntryBuilder::new("file2".to_string().into(), Compression::Stored);
+    let mut entry_writer = writer.write_entry_stream(entry).await.unwrap();
+    entry_writer.write_all(&[0, 0, 0, 0]).await.unwrap();
+    entry_writer.close().await.unwrap();
+    writer.close().await.unwrap();
+    buffer
+}
+
+/// Returns whether any ZIP64 structures were written to the archive.
+async fn has_zip64_structures(buffer: Vec<u8>) -> bool {
+    let reader = crate::base::read::mem::ZipFileReader::new(buffer).await.unwrap();
+    reader.file().zip64() || reader.file().entries().iter().any(|entry| entry.is_zip64())
+}
+
+#[tokio::test]
+async fn test_default_zip64_mode() {
+    let buffer = write_small_archive(|writer| writer).await;
+    assert!(has_zip64_structures(buffer).await);
+}
+
+#[tokio::test]
+async fn test_force_zip64_mode() {
+    let buffer = write_small_archive(|writer| writer.force_zip64()).await;
+    let reader = crate::base::read::mem::ZipFileReader::new(buffer).await.unwrap();
+    assert!(reader.file().zip64());
+}
+
+#[tokio::test]
+async fn test_force_no_zip64_mode() {
+    let buffer = write_small_archive(|writer| writer.force_no_zip64()).await;
+    assert!(!has_zip64_structures(buffer).await);
+}
+
+#[tokio::test]
+async fn test_auto_zip64_mode() {
+    let buffer = write_small_archive(|writer| writer.auto_zip64()).await;
+    assert!(!has_zip64_structures(buffer).await);
+}
+
+#[tokio::test]
+async fn test_auto_zip64_mode_overrides_force_zip64() {
+    let buffer = write_small_archive(|writer| writer.force_zip64().auto_zip64()).await;
+    assert!(!has_zip64_structures(buffer).await);
+}
+
+#[tokio::test]
+async fn test_zip64_modes_last_call_wins() {
+    let buffer = write_small_archive(|writer| writer.auto_zip64().force_zip64()).await;
+    assert!(has_zip64_structures(buffer).await);
+
+    let buffer = write_small_archive(|writer| writer.force_no_zip64().force_zip64()).await;
+    assert!(has_zip64_structures(buffer).await);
+
+    let buffer = write_small_archive(|writer| writer.force_zip64().force_no_zip64()).await;
+    assert!(!has_zip64_structures(buffer).await);
+}
+
+#[tokio::test]
+async fn test_auto_zip64_mode_many_files() {
+    let mut sink = AsyncSink;
+    let mut writer = ZipFileWriter::new(&mut sink).force_no_zip64().auto_zip64();
+
+    for i in 0..=u16::MAX {
+        let entry = ZipEntryBuilder::new(format!("{i}").into(), Compression::Stored);
+        writer.write_entry_whole(entry, &[]).await.unwrap();
+    }
+
+    assert!(writer.close().await.is_ok());
+}
+
 /// Test writing a large zip64 file. This test will use upwards of 4GB of memory.
 #[tokio::test]
 async fn test_write_large_zip64_file() {
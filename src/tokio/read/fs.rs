@@ -71,25 +71,36 @@
 use crate::base::read::seek;
 
 use crate::base::read::io::entry::{WithEntry, WithoutEntry, ZipEntryReader};
+use crate::codec::{Codec, CodecRegistry};
 use crate::error::{Result, ZipError};
 use crate::file::ZipFile;
 
+#[cfg(feature = "memmap2")]
+use crate::spec::Compression;
+
+#[cfg(feature = "memmap2")]
+use std::ops::Deref;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
+#[cfg(feature = "memmap2")]
+use futures_lite::io::AsyncSeekExt;
+
 use tokio::fs::File;
 use tokio::io::BufReader;
 use tokio_util::compat::{Compat, TokioAsyncReadCompatExt};
 
+#[derive(Debug)]
 struct Inner {
     path: PathBuf,
     file: ZipFile,
 }
 
 /// A concurrent ZIP reader which acts over a file system path.
-#[derive(Clone)]
+#[derive(Clone, Debug)]
 pub struct ZipFileReader {
     inner: Arc<Inner>,
+    codecs: CodecRegistry,
 }
 
 impl ZipFileReader {
@@ -109,7 +120,10 @@ impl ZipFileReader {
     where
         P: AsRef<Path>,
     {
-        ZipFileReader { inner: Arc::new(Inner { path: path.as_ref().to_owned(), file }) }
+        ZipFileReader {
+            inner: Arc::new(Inner { path: path.as_ref().to_owned(), file }),
+            codecs: CodecRegistry::default(),
+        }
     }
 
     /// Returns this ZIP file's information.
@@ -117,6 +131,15 @@ impl ZipFileReader {
         &self.inner.file
     }
 
+    /// Registers a codec for reading entries which use the provided custom compression method id, replacing any codec
+    /// previously registered for it.
+    ///
+    /// Returns [`ZipError::CompressionMethodReserved`] if the method id belongs to a method implemented by this crate.
+    /// See the [`codec`](crate::codec) module for more information.
+    pub fn register_codec(&mut self, method: u16, codec: Arc<dyn Codec>) -> Result<()> {
+        self.codecs.register(method, codec)
+    }
+
     /// Returns the file system path provided to the reader during construction.
     pub fn path(&self) -> &Path {
         &self.inner.path
@@ -132,11 +155,8 @@ impl ZipFileReader {
 
         stored_entry.seek_to_data_offset(&mut fs_file).await?;
 
-        Ok(ZipEntryReader::new_with_owned(
-            fs_file,
-            stored_entry.entry.compression(),
-            stored_entry.entry.compressed_size(),
-        ))
+        let size = stored_entry.entry.compressed_size();
+        ZipEntryReader::new_with_owned(fs_file, &stored_entry.entry, size, &self.codecs)
     }
 
     /// Returns a new entry reader if the provided index is valid.
@@ -149,12 +169,62 @@ impl ZipFileReader {
 
         stored_entry.seek_to_data_offset(&mut fs_file).await?;
 
-        let reader = ZipEntryReader::new_with_owned(
-            fs_file,
-            stored_entry.entry.compression(),
-            stored_entry.entry.compressed_size(),
-        );
+        let size = stored_entry.entry.compressed_size();
+        let reader = ZipEntryReader::new_with_owned(fs_file, &stored_entry.entry, size, &self.codecs)?;
 
         Ok(reader.into_with_entry(stored_entry))
     }
+
+    /// Memory-maps the data of a Stored entry if the provided index is valid.
+    ///
+    /// This allows zero-copy access to the entry's data, which is useful for large uncompressed entries. An error is
+    /// returned if the entry is compressed or encrypted.
+    ///
+    /// Note that the data is neither decompressed nor checked against the entry's CRC32 value. As with any memory map,
+    /// modifying or truncating the file on disk whilst the returned slice is alive is undefined behaviour.
+    #[cfg(feature = "memmap2")]
+    pub async fn mmap_entry(&self, index: usize) -> Result<MmapSlice> {
+        let stored_entry = self.inner.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
+
+        if stored_entry.entry.compression() != Compression::Stored {
+            return Err(ZipError::FeatureNotSupported("memory-mapping entries which aren't Stored"));
+        }
+
+        let mut fs_file = File::open(&self.inner.path).await?.compat();
+        stored_entry.seek_to_data_offset(&mut fs_file).await?;
+        let offset = fs_file.seek(std::io::SeekFrom::Current(0)).await?;
+        let fs_file = fs_file.into_inner().into_std().await;
+
+        let length = stored_entry.entry.compressed_size();
+        let length = usize::try_from(length).map_err(|_| ZipError::TargetZip64NotSupported)?;
+
+        // SAFETY:
+        // the caller is made aware (via the docs above) that the file must not be modified whilst it's mapped
+        let mmap = unsafe { memmap2::MmapOptions::new().offset(offset).len(length).map(&fs_file)? };
+
+        Ok(MmapSlice { mmap })
+    }
+}
+
+/// A memory-mapped slice over the data of a Stored entry, as returned by [`ZipFileReader::mmap_entry()`].
+#[cfg(feature = "memmap2")]
+#[derive(Debug)]
+pub struct MmapSlice {
+    mmap: memmap2::Mmap,
+}
+
+#[cfg(feature = "memmap2")]
+impl Deref for MmapSlice {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.mmap
+    }
+}
+
+#[cfg(feature = "memmap2")]
+impl AsRef<[u8]> for MmapSlice {
+    fn as_ref(&self) -> &[u8] {
+        &self.mmap
+    }
 }
@@ -16,3 +16,67 @@ pub(crate) async fn assert_signature<R: AsyncRead + Unpin>(reader: &mut R, expec
         actual => Err(ZipError::UnexpectedHeaderError(actual, expected)),
     }
 }
+
+/// Returns whether the path matches the shell-style glob pattern, where `*` matches any run of characters other than
+/// `/`, `?` matches any single character other than `/`, and `**` matches any run of characters (including `/`).
+///
+/// A `**/` component only matches whole directories (ie. runs of characters ending in `/`), and may also match none
+/// at all, so `logs/**/*.txt` matches both `logs/a.txt` and `logs/2024/a.txt` but not `logs/xa.txt`. Matching is
+/// case-sensitive.
+pub(crate) fn glob_match(pattern: &str, path: &str) -> bool {
+    enum Token {
+        Char(char),
+        Single,
+        Star,
+        Recursive,
+        RecursiveDirectories,
+    }
+
+    let mut tokens = Vec::new();
+    let mut chars = pattern.chars().peekable();
+    while let Some(c) = chars.next() {
+        let token = match c {
+            '*' if chars.next_if_eq(&'*').is_some() => match chars.next_if_eq(&'/') {
+                Some(_) => Token::RecursiveDirectories,
+                None => Token::Recursive,
+            },
+            '*' => Token::Star,
+            '?' => Token::Single,
+            c => Token::Char(c),
+        };
+        tokens.push(token);
+    }
+
+    // matches[t][p] holds whether the tokens from t onwards match the path from p onwards.
+    let path: Vec<char> = path.chars().collect();
+    let mut matches = vec![vec![false; path.len() + 1]; tokens.len() + 1];
+    matches[tokens.len()][path.len()] = true;
+
+    for t in (0..tokens.len()).rev() {
+        // Whether the path from p onwards starts with a directory, after which the tokens from t onwards still match.
+        let mut directory = false;
+
+        for p in (0..=path.len()).rev() {
+            let current = path.get(p).copied();
+            let consumed = p < path.len() && matches[t][p + 1];
+            let advanced = p < path.len() && matches[t + 1][p + 1];
+
+            matches[t][p] = match tokens[t] {
+                Token::Char(c) => current == Some(c) && advanced,
+                Token::Single => current.is_some_and(|c| c != '/') && advanced,
+                Token::Star => matches[t + 1][p] || (current != Some('/') && consumed),
+                Token::Recursive => matches[t + 1][p] || consumed,
+                Token::RecursiveDirectories => {
+                    directory = match current {
+                        Some('/') => matches[t][p + 1],
+                        Some(_) => directory,
+                        None => false,
+                    };
+                    matches[t + 1][p] || directory
+                }
+            };
+        }
+    }
+
+    matches[0][0]
+}
@@ -1,6 +1,8 @@
 // Copyright (c) 2023 Harry [Majored] [hello@majored.pw]
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
+#![allow(dead_code)]
+
 use async_zip::base::read::mem;
 use async_zip::base::read::seek;
 use async_zip::base::write::ZipFileWriter;
@@ -57,6 +59,24 @@ pub async fn check_decompress_fs(fname: &str) {
     }
 }
 
+#[cfg(feature = "async-std")]
+pub async fn check_decompress_async_std_fs(fname: &str) {
+    use async_zip::async_std::read::fs;
+    let zip = fs::ZipFileReader::new(fname).await.unwrap();
+    for (idx, entry) in zip.file().entries().iter().enumerate() {
+        if entry.dir().unwrap() {
+            continue;
+        }
+        let fname = entry.filename().as_str().unwrap();
+        let mut output = String::new();
+        let mut reader = zip.reader_with_entry(idx).await.unwrap();
+        let _ = reader.read_to_string_checked(&mut output).await.unwrap();
+        let fs_file = format!("{FOLDER_PREFIX}/{fname}");
+        let expected = tokio::fs::read_to_string(fs_file).await.unwrap();
+        assert_eq!(output, expected, "for {fname}, expect zip data to match file data");
+    }
+}
+
 pub async fn check_decompress_seek(fname: &str) {
     let file = BufReader::new(File::open(fname).await.unwrap());
     let mut file_compat = file.compat();
@@ -20,6 +20,13 @@ async fn zip_decompress_in_out() {
     common::check_decompress_mem(zip_data).await
 }
 
+#[cfg(feature = "lzma")]
+#[tokio::test]
+async fn zip_lzma_in_out() {
+    let zip_data = common::compress_to_mem(Compression::Lzma).await;
+    common::check_decompress_mem(zip_data).await
+}
+
 #[tokio::test]
 async fn zip_store_in_out() {
     let zip_data = common::compress_to_mem(Compression::Stored).await;
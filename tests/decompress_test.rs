@@ -6,7 +6,9 @@ use tokio_util::compat::TokioAsyncReadCompatExt;
 
 mod common;
 
+#[cfg(feature = "zstd")]
 const ZSTD_ZIP_FILE: &str = "tests/test_inputs/sample_data.zstd.zip";
+#[cfg(feature = "deflate")]
 const DEFLATE_ZIP_FILE: &str = "tests/test_inputs/sample_data.deflate.zip";
 const STORE_ZIP_FILE: &str = "tests/test_inputs/sample_data.store.zip";
 const UTF8_EXTRA_ZIP_FILE: &str = "tests/test_inputs/sample_data_utf8_extra.zip";
@@ -75,6 +77,12 @@ async fn decompress_store_zip_fs() {
     common::check_decompress_fs(STORE_ZIP_FILE).await
 }
 
+#[cfg(feature = "async-std")]
+#[tokio::test]
+async fn decompress_store_zip_async_std_fs() {
+    common::check_decompress_async_std_fs(STORE_ZIP_FILE).await
+}
+
 #[tokio::test]
 async fn decompress_zip_with_utf8_extra() {
     let file = BufReader::new(tokio::fs::File::open(UTF8_EXTRA_ZIP_FILE).await.unwrap());